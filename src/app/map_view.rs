@@ -2,8 +2,9 @@ use yew::prelude::*;
 
 use super::ViewModelContext;
 use crate::{
-    app::{planet_selector::PlanetSelector, Action, MapSelection},
+    app::{market_panel::MarketPanel, planet_selector::PlanetSelector, Action, MapSelection},
     planet::map_view::planet_map::PlanetMap,
+    universe::galaxy_map::GalaxyMap,
 };
 
 #[function_component(MapView)]
@@ -11,9 +12,12 @@ pub fn create_map_view() -> Html {
     let view_model = use_context::<ViewModelContext>().expect("no view model context found");
 
     match view_model.map_selection {
-        MapSelection::Universe(_universe_id) => {
+        MapSelection::Universe(universe_id) => {
             html! {
-                <PlanetSelector />
+                <div>
+                    <PlanetSelector />
+                    <GalaxyMap universe_id={universe_id} />
+                </div>
             }
         }
         MapSelection::Planet(_universe_id, _planet_id) => {
@@ -24,8 +28,13 @@ pub fn create_map_view() -> Html {
                 </div>
             }
         }
-        MapSelection::Region(_universe_id, planet_id, region_id) => {
-            let region_id: usize = region_id.into();
+        MapSelection::Region(universe_id, planet_id, region_id) => {
+            let landmass_name = {
+                let game = view_model.game.borrow();
+                let planet = game.get_universe(universe_id).get_planet(planet_id);
+                planet.find_landmass(region_id).clone_name()
+            };
+            let region_index: usize = region_id.into();
 
             html! {
                 <>
@@ -33,9 +42,13 @@ pub fn create_map_view() -> Html {
                     <div>
                         <button onclick={move |_| view_model.dispatch(Action::UpdatePlanetId(planet_id))}>{"Map"}</button>
                         <div>
-                            {"Region "} {region_id}
+                            {"Region "} {region_index}
+                        </div>
+                        <div>
+                            {landmass_name}
                         </div>
                     </div>
+                    <MarketPanel {universe_id} {planet_id} {region_id} />
                 </>
             }
         }
@@ -62,7 +75,7 @@ impl App {
                 }
             }
             MapSelection::Region(universe_id, planet_id, region_id) => {
-                let region_id: usize = region_id.into();
+                let region_index: usize = region_id.into();
 
                 let link = ctx.link();
 
@@ -72,7 +85,7 @@ impl App {
                         <div>
                             <button onclick={link.callback(move |_| Msg::UpdatePlanetId(planet_id))}>{"Map"}</button>
                             <div>
-                                {"Region "} {region_id}
+                                {"Region "} {region_index}
                             </div>
                         </div>
                     </>
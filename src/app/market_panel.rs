@@ -0,0 +1,92 @@
+use yew::prelude::*;
+
+use super::{Action, ViewModelContext};
+use crate::{
+    planet::{PlanetId, RegionId},
+    travel::Traveller,
+    universe::UniverseId,
+};
+
+// quantities the player can trade with one click
+const TRADE_LOTS: [u32; 2] = [1, 10];
+
+#[derive(PartialEq, Properties)]
+pub struct MarketPanelProps {
+    pub universe_id: UniverseId,
+    pub planet_id: PlanetId,
+    pub region_id: RegionId,
+}
+
+/// Prices & stock of the local market of the region, where the player can trade.
+#[function_component(MarketPanel)]
+pub fn create_market_panel(props: &MarketPanelProps) -> Html {
+    let view_model = use_context::<ViewModelContext>().expect("no view model context found");
+    let MarketPanelProps {
+        universe_id,
+        planet_id,
+        region_id,
+    } = *props;
+
+    let game = view_model.game.borrow();
+    let player = Traveller::Character(game.get_player_character_id());
+    let cargo = game.get_cargo(player);
+    let market = game
+        .get_universe(universe_id)
+        .get_planet(planet_id)
+        .get_regions()[usize::from(region_id)]
+    .get_local_market();
+
+    let rows = market.get_storage().iter().map(|(commodity, stock)| {
+        let carried = cargo.map(|cargo| cargo.get(commodity)).unwrap_or_default();
+        let buttons = TRADE_LOTS.iter().map(|&quantity| {
+            let buy = {
+                let view_model = view_model.clone();
+                move |_| view_model.dispatch(Action::Buy(commodity, quantity))
+            };
+            let sell = {
+                let view_model = view_model.clone();
+                move |_| view_model.dispatch(Action::Sell(commodity, quantity))
+            };
+            html! {
+                <>
+                    <button onclick={buy} disabled={stock < quantity}>{"Buy "}{quantity}</button>
+                    <button onclick={sell} disabled={carried < quantity}>{"Sell "}{quantity}</button>
+                </>
+            }
+        });
+
+        html! {
+            <tr>
+                <td>{format!("{:?}", commodity)}</td>
+                <td>{format!("{:.2}", market.get_price(commodity))}</td>
+                <td>{stock}</td>
+                <td>{carried}</td>
+                <td>{for buttons}</td>
+            </tr>
+        }
+    });
+
+    let carried = cargo.map(|cargo| cargo.get_total()).unwrap_or_default();
+    let trade_error = view_model
+        .trade_error
+        .map(|error| format!("Cannot trade: {:?}", error))
+        .unwrap_or_default();
+
+    html! {
+        <fieldset>
+            <legend>{"Market"}</legend>
+            <div>{"Cargo: "}{carried}{" / "}{game.get_cargo_capacity(player)}</div>
+            <div>{trade_error}</div>
+            <table>
+                <tr>
+                    <th>{"Commodity"}</th>
+                    <th>{"Price"}</th>
+                    <th>{"Stock"}</th>
+                    <th>{"Carried"}</th>
+                    <th></th>
+                </tr>
+                {for rows}
+            </table>
+        </fieldset>
+    }
+}
@@ -1,6 +1,7 @@
 use std::{cell::RefCell, rc::Rc};
 
 use crate::{
+    economy::{Commodity, Quantity, TradeError},
     planet::{PlanetId, RegionId},
     universe::UniverseId,
     Game,
@@ -9,6 +10,7 @@ use crate::{
 mod character_view;
 pub(crate) mod main;
 mod map_view;
+mod market_panel;
 mod planet_selector;
 mod view_model;
 
@@ -30,6 +32,8 @@ pub enum Action {
     SwitchView(View),
     ResumeGame,
     PauseGame,
+    Buy(Commodity, Quantity),
+    Sell(Commodity, Quantity),
 }
 
 #[derive(Clone)]
@@ -40,6 +44,7 @@ pub struct Model {
     pub should_redraw_map: Rc<RefCell<bool>>,
     pub map_selection: MapSelection,
     pub grid_size: f64,
+    pub trade_error: Option<TradeError>, // of the player's last trade
 }
 
 pub type ViewModelContext = yew::UseReducerHandle<Model>;
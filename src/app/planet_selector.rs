@@ -53,8 +53,9 @@ pub fn create_planet_selector() -> Html {
             .enumerate()
             .map(|(index, universe)| {
                 let value = index.to_string();
+                let is_selected = UniverseId::new_unsafe(index) == universe_id;
                 html! {
-                    <option value={value}>{universe.borrow_name()}</option>
+                    <option value={value} selected={is_selected}>{universe.borrow_name()}</option>
                 }
             });
 
@@ -4,14 +4,16 @@ use yew::prelude::*;
 
 use super::{Action, MapSelection, Model, View};
 use crate::{
+    economy::{Commodity, Quantity},
     planet::{PlanetId, RegionId},
+    travel::Traveller,
     universe::UniverseId,
-    Game,
+    Game, WorldConfig,
 };
 
 impl Default for Model {
     fn default() -> Self {
-        let game = Game::create();
+        let game = Game::create(&WorldConfig::default());
 
         Self {
             current_view: View::Map,
@@ -20,6 +22,7 @@ impl Default for Model {
             should_game_loop_run: false,
             grid_size: 15.,
             should_redraw_map: Rc::new(RefCell::new(true)),
+            trade_error: None,
         }
     }
 }
@@ -37,6 +40,8 @@ impl Reducible for Model {
             Action::SwitchView(view) => next.switch_view(view),
             Action::PauseGame => next.try_pause_game(),
             Action::ResumeGame => next.try_resume_game(),
+            Action::Buy(commodity, quantity) => next.trade(commodity, quantity, true),
+            Action::Sell(commodity, quantity) => next.trade(commodity, quantity, false),
         };
 
         next.into()
@@ -48,6 +53,7 @@ impl PartialEq for Model {
         self.current_view == other.current_view
             && self.map_selection == other.map_selection
             && self.should_game_loop_run == other.should_game_loop_run
+            && self.trade_error == other.trade_error
             && self.game.borrow().generation == other.game.borrow().generation
     }
 }
@@ -100,6 +106,18 @@ impl Model {
         };
     }
 
+    /// Has the player buy or sell at the local market they are at.
+    pub fn trade(&mut self, commodity: Commodity, quantity: Quantity, is_buying: bool) {
+        let mut game = self.game.borrow_mut();
+        let player = Traveller::Character(game.get_player_character_id());
+        let result = if is_buying {
+            game.buy(player, commodity, quantity)
+        } else {
+            game.sell(player, commodity, quantity)
+        };
+        self.trade_error = result.err();
+    }
+
     pub fn try_resume_game(&mut self) {
         self.should_game_loop_run = true;
     }
@@ -129,12 +147,4 @@ impl Model {
             | MapSelection::Region(universe_id, planet_id, _) => (*universe_id, Some(*planet_id)),
         }
     }
-
-    pub fn get_selected_universe_id(&self) -> UniverseId {
-        match &self.map_selection {
-            MapSelection::Universe(universe_id)
-            | MapSelection::Planet(universe_id, _)
-            | MapSelection::Region(universe_id, _, _) => *universe_id,
-        }
-    }
 }
@@ -78,5 +78,5 @@ pub struct Character {
     pub relationships: HashMap<CharacterId, Relationship>,
 }
 
-#[derive(Clone, Copy, PartialEq, Hash, Eq)]
+#[derive(Clone, Copy, PartialEq, Hash, Eq, Debug)]
 pub struct CharacterId(usize);
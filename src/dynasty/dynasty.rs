@@ -12,10 +12,17 @@ pub struct NewCharacter {
     pub coor: CompleteCoor,
 }
 
+impl Dynasty {
+    pub fn clone_name(&self) -> String {
+        self.name.to_owned()
+    }
+}
+
 impl Game {
     pub fn start_dynasty(
         dynasties: &mut Vec<Dynasty>,
         characters: &mut Vec<Character>,
+        dynasty_name: String,
         NewCharacter { name, coor }: NewCharacter,
     ) -> (DynastyId, CharacterId) {
         let dynasty_id = DynastyId::next(dynasties);
@@ -33,6 +40,7 @@ impl Game {
         characters.push(character);
 
         let dynasty = Dynasty {
+            name: dynasty_name,
             head: character_id,
             members: HashSet::from([character_id]),
         };
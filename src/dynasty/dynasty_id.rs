@@ -1,5 +1,11 @@
 use super::{Dynasty, DynastyId};
 
+impl DynastyId {
+    pub fn new_unsafe(index: usize) -> Self {
+        Self(index)
+    }
+}
+
 impl From<DynastyId> for usize {
     fn from(id: DynastyId) -> usize {
         id.0
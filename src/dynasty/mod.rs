@@ -6,9 +6,10 @@ pub mod dynasty;
 mod dynasty_id;
 
 pub struct Dynasty {
+    pub name: String, // surname shared by the members
     pub head: CharacterId,
     pub members: HashSet<CharacterId>, // dies out if no member remains
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct DynastyId(usize);
@@ -1 +1,49 @@
+use super::{Brand, BrandId, Commodity, CompanyId, Quality, Quantity};
+use crate::Time;
 
+// sales it takes for a brand to be judged half by the new goods & half by its old reputation
+const REPUTATION_INERTIA: f64 = 1000.;
+// portion of the reputation forgotten every brand period
+const REPUTATION_FADE: f64 = 0.01;
+
+impl BrandId {
+    pub fn new_unsafe(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl From<BrandId> for usize {
+    fn from(id: BrandId) -> Self {
+        id.0
+    }
+}
+
+impl Brand {
+    pub fn get_company_id(&self) -> CompanyId {
+        self.company_id
+    }
+
+    pub fn get_commodity(&self) -> Commodity {
+        self.commodity
+    }
+
+    pub fn get_created_at(&self) -> Time {
+        self.created_at
+    }
+
+    /// How well known the brand is for quality, as rated by shoppers.
+    pub fn get_reputation(&self) -> u32 {
+        self.reputation.round() as u32
+    }
+
+    /// Shoppers judge the brand by the goods they've bought, more so the more they've bought.
+    pub fn record_sales(&mut self, quality: Quality, quantity: Quantity) {
+        let weight = quantity as f64 / (quantity as f64 + REPUTATION_INERTIA);
+        self.reputation += weight * (quality as f64 - self.reputation);
+    }
+
+    /// Every brand is slowly forgotten, so only steady sales keep up its reputation.
+    pub(super) fn fade(&mut self) {
+        self.reputation *= 1. - REPUTATION_FADE;
+    }
+}
@@ -0,0 +1,95 @@
+use super::{Commodity, Price};
+
+impl Commodity {
+    /// Price when supply meets demand, before any planet or galaxy events.
+    pub fn get_default_intrinsic_price(&self) -> Price {
+        match self {
+            Commodity::Crop => 1.,
+            Commodity::Meat => 3.,
+            Commodity::Salt => 2.,
+            Commodity::Essence => 40.,
+            Commodity::Spice => 200.,
+            Commodity::Water => 0.5,
+            Commodity::Beverage => 4.,
+
+            Commodity::Chemical => 6.,
+            Commodity::Fiber => 2.,
+            Commodity::Polymer => 5.,
+            Commodity::Glass => 3.,
+            Commodity::Incense => 8.,
+            Commodity::Wood => 2.,
+
+            Commodity::Fuel => 4.,
+
+            Commodity::Gold => 500.,
+            Commodity::Gem => 300.,
+            Commodity::Metal => 8.,
+            Commodity::Alloy => 20.,
+
+            Commodity::Apparel => 15.,
+            Commodity::Paper => 2.,
+            Commodity::Accessory => 30.,
+            Commodity::Furniture => 60.,
+            Commodity::Appliance => 120.,
+            Commodity::Gadget => 200.,
+            Commodity::Vehicle => 2000.,
+            Commodity::Medicine => 25.,
+
+            Commodity::Stillsuit => 150.,
+
+            Commodity::Fertilizer => 5.,
+            Commodity::Tool => 25.,
+            Commodity::Machine => 400.,
+            Commodity::Computer => 600.,
+            Commodity::Printer => 800.,
+
+            Commodity::Plate => 30.,
+            Commodity::Parts => 40.,
+            Commodity::Scaffold => 10.,
+            Commodity::Structure => 80.,
+        }
+    }
+
+    /// How strongly the price follows the ratio of demand to supply.
+    pub fn get_default_elasticity(&self) -> f64 {
+        match self {
+            // people pay anything to eat, and little for food nobody wants
+            Commodity::Crop | Commodity::Meat | Commodity::Water => 1.5,
+            Commodity::Medicine => 1.2,
+            // stores of value hold their price
+            Commodity::Gold | Commodity::Gem => 0.3,
+            _ => 1.,
+        }
+    }
+
+    /// Portion that spoils every day in an ordinary warehouse.
+    pub fn get_decay_rate(&self) -> f64 {
+        match self {
+            Commodity::Meat => 0.03,
+            Commodity::Crop => 0.01,
+            Commodity::Beverage => 0.005,
+            Commodity::Medicine | Commodity::Fertilizer => 0.002,
+            Commodity::Fiber | Commodity::Paper => 0.001,
+            Commodity::Wood => 0.0005,
+            // minerals & manufactured goods keep
+            _ => 0.,
+        }
+    }
+
+    /// Amount a person uses up in a market period.
+    pub fn get_consumption_per_capita(&self) -> f64 {
+        match self {
+            Commodity::Crop => 1.,
+            Commodity::Water => 1.,
+            Commodity::Meat => 0.3,
+            Commodity::Beverage => 0.2,
+            Commodity::Salt => 0.05,
+            Commodity::Apparel => 0.05,
+            Commodity::Paper => 0.05,
+            Commodity::Medicine => 0.02,
+            Commodity::Fuel => 0.02,
+            Commodity::Furniture => 0.005,
+            _ => 0.,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use super::{
+    Brand, BrandId, Commodity, Companies, Company, CompanyError, CompanyId, Quality, Quantity,
+};
+use crate::{character::CharacterId, Time};
+
+// brand reputations fade once per period
+const BRAND_PERIOD: Time = 24 * 30;
+
+impl Companies {
+    /// Starts a company that is owned & run by its founder.
+    pub fn found(&mut self, founder: CharacterId, name: String, time: Time) -> CompanyId {
+        self.companies.push(Company {
+            name,
+            owner: founder,
+            ceo: founder,
+            employees: [founder].into_iter().collect(),
+            founded_at: time,
+            brands: Default::default(),
+        });
+        CompanyId::new_unsafe(self.companies.len() - 1)
+    }
+
+    pub fn get(&self, company_id: CompanyId) -> Option<&Company> {
+        self.companies.get(usize::from(company_id))
+    }
+
+    pub fn get_mut(&mut self, company_id: CompanyId) -> Option<&mut Company> {
+        self.companies.get_mut(usize::from(company_id))
+    }
+
+    pub fn get_brand(&self, brand_id: BrandId) -> &Brand {
+        &self.brands[usize::from(brand_id)]
+    }
+
+    pub fn get_brand_mut(&mut self, brand_id: BrandId) -> &mut Brand {
+        &mut self.brands[usize::from(brand_id)]
+    }
+
+    /// Gives the company a brand to sell the commodity under, which starts out unknown.
+    pub fn launch_brand(
+        &mut self,
+        company_id: CompanyId,
+        commodity: Commodity,
+        time: Time,
+    ) -> Result<BrandId, CompanyError> {
+        let brand_id = BrandId::new_unsafe(self.brands.len());
+        let company = self
+            .companies
+            .get_mut(usize::from(company_id))
+            .ok_or(CompanyError::UnknownCompany)?;
+        if company.brands[commodity].is_some() {
+            return Err(CompanyError::AlreadyHasBrand);
+        }
+
+        company.brands[commodity] = Some(brand_id);
+        self.brands.push(Brand {
+            company_id,
+            commodity,
+            created_at: time,
+            reputation: 0.,
+        });
+        Ok(brand_id)
+    }
+
+    /// Lets shoppers judge the company's brand of the commodity, if it has one, by what they bought.
+    pub fn record_sales(
+        &mut self,
+        company_id: CompanyId,
+        commodity: Commodity,
+        quality: Quality,
+        quantity: Quantity,
+    ) {
+        let brand_id = self
+            .get(company_id)
+            .and_then(|company| company.get_brand(commodity));
+        if let Some(brand_id) = brand_id {
+            self.get_brand_mut(brand_id).record_sales(quality, quantity);
+        }
+    }
+
+    pub fn progress(&mut self, time: Time) {
+        if !time.is_multiple_of(BRAND_PERIOD) {
+            return;
+        }
+        for brand in &mut self.brands {
+            brand.fade();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BRAND_PERIOD;
+    use crate::{
+        character::CharacterId,
+        economy::{Commodity, Companies, CompanyError},
+    };
+
+    #[test]
+    fn companies_have_owners_and_employees() {
+        let mut companies = Companies::default();
+        let founder = CharacterId::new_unsafe(0);
+        let manager = CharacterId::new_unsafe(1);
+        let company_id = companies.found(founder, "Acme".into(), 0);
+
+        let company = companies.get_mut(company_id).unwrap();
+        assert_eq!(company.get_ceo(), founder);
+        assert_eq!(company.appoint_ceo(manager), Err(CompanyError::NotEmployee));
+        company.hire(manager);
+        company.appoint_ceo(manager).unwrap();
+        assert_eq!(company.get_owner(), founder);
+        assert_eq!(company.get_ceo(), manager);
+        assert_eq!(company.fire(manager), Err(CompanyError::IsCeo));
+        company.fire(founder).unwrap();
+        assert!(!company.get_employees().contains(&founder));
+        assert_eq!(company.fire(founder), Err(CompanyError::NotEmployee));
+    }
+
+    #[test]
+    fn brands_build_reputation_from_quality() {
+        let mut companies = Companies::default();
+        let company_id = companies.found(CharacterId::new_unsafe(0), "Acme".into(), 0);
+        let brand_id = companies
+            .launch_brand(company_id, Commodity::Apparel, 0)
+            .unwrap();
+        assert_eq!(
+            companies.launch_brand(company_id, Commodity::Apparel, 0),
+            Err(CompanyError::AlreadyHasBrand)
+        );
+        assert_eq!(
+            companies
+                .get(company_id)
+                .unwrap()
+                .get_brand(Commodity::Apparel),
+            Some(brand_id)
+        );
+
+        let brand = companies.get_brand_mut(brand_id);
+        assert_eq!(brand.get_reputation(), 0);
+        brand.record_sales(200, 100);
+        let small = brand.get_reputation();
+        brand.record_sales(200, 10000);
+        let big = brand.get_reputation();
+        assert!(0 < small && small < big && big <= 200);
+
+        // poor goods drag it back down
+        brand.record_sales(50, 10000);
+        assert!(brand.get_reputation() < big);
+
+        let before = companies.get_brand(brand_id).get_reputation();
+        // forgotten when off the shelves for a year
+        for month in 1..=12 {
+            companies.progress(BRAND_PERIOD * month);
+        }
+        assert!(companies.get_brand(brand_id).get_reputation() < before);
+    }
+}
@@ -1 +1,55 @@
+use std::collections::HashSet;
 
+use super::{BrandId, Commodity, Company, CompanyError};
+use crate::{character::CharacterId, Time};
+
+impl Company {
+    pub fn clone_name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    pub fn get_owner(&self) -> CharacterId {
+        self.owner
+    }
+
+    pub fn get_ceo(&self) -> CharacterId {
+        self.ceo
+    }
+
+    pub fn get_employees(&self) -> &HashSet<CharacterId> {
+        &self.employees
+    }
+
+    pub fn get_founded_at(&self) -> Time {
+        self.founded_at
+    }
+
+    pub fn get_brand(&self, commodity: Commodity) -> Option<BrandId> {
+        self.brands[commodity]
+    }
+
+    pub fn hire(&mut self, character_id: CharacterId) {
+        self.employees.insert(character_id);
+    }
+
+    /// Lets an employee go; the CEO has to be replaced first.
+    pub fn fire(&mut self, character_id: CharacterId) -> Result<(), CompanyError> {
+        if character_id == self.ceo {
+            return Err(CompanyError::IsCeo);
+        }
+        if !self.employees.remove(&character_id) {
+            return Err(CompanyError::NotEmployee);
+        }
+        Ok(())
+    }
+
+    /// Puts an employee, or the owner, in charge.
+    pub fn appoint_ceo(&mut self, character_id: CharacterId) -> Result<(), CompanyError> {
+        if character_id != self.owner && !self.employees.contains(&character_id) {
+            return Err(CompanyError::NotEmployee);
+        }
+        self.employees.insert(character_id);
+        self.ceo = character_id;
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+use super::CompanyId;
+
+impl CompanyId {
+    pub fn new_unsafe(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl From<CompanyId> for usize {
+    fn from(id: CompanyId) -> Self {
+        id.0
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use super::{Account, Ledger, LedgerError, Money, Transaction, TransferReason};
+use crate::Time;
+
+impl Account {
+    /// Sources & sinks are the only accounts allowed to go below 0.
+    pub fn can_overdraw(&self) -> bool {
+        matches!(self, Account::Outside(_))
+    }
+}
+
+impl Transaction {
+    pub fn get_time(&self) -> Time {
+        self.time
+    }
+
+    pub fn get_from(&self) -> Account {
+        self.from
+    }
+
+    pub fn get_to(&self) -> Account {
+        self.to
+    }
+
+    pub fn get_amount(&self) -> Money {
+        self.amount
+    }
+
+    pub fn get_reason(&self) -> TransferReason {
+        self.reason
+    }
+
+    pub fn involves(&self, account: Account) -> bool {
+        self.from == account || self.to == account
+    }
+}
+
+impl Ledger {
+    pub fn get_balance(&self, account: Account) -> Money {
+        self.balances.get(&account).copied().unwrap_or_default()
+    }
+
+    /// Moves money between 2 accounts, debiting one & crediting the other by the same amount.
+    pub fn transfer(
+        &mut self,
+        from: Account,
+        to: Account,
+        amount: Money,
+        reason: TransferReason,
+        time: Time,
+    ) -> Result<&Transaction, LedgerError> {
+        if amount <= 0 {
+            return Err(LedgerError::InvalidAmount);
+        }
+        if from == to {
+            return Err(LedgerError::SameAccount);
+        }
+        if !from.can_overdraw() && self.get_balance(from) < amount {
+            return Err(LedgerError::InsufficientFunds);
+        }
+
+        *self.balances.entry(from).or_default() -= amount;
+        *self.balances.entry(to).or_default() += amount;
+        self.transactions.push(Transaction {
+            time,
+            from,
+            to,
+            amount,
+            reason,
+        });
+        Ok(self.transactions.last().expect("just pushed"))
+    }
+
+    pub fn get_transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    /// Transactions in & out of the account, oldest first.
+    pub fn iter_history(&self, account: Account) -> impl Iterator<Item = &Transaction> + '_ {
+        self.transactions
+            .iter()
+            .filter(move |transaction| transaction.involves(account))
+    }
+
+    /// Money held by everyone in the economy, i.e. all that has come in from the sources & sinks.
+    pub fn get_money_supply(&self) -> Money {
+        self.balances
+            .iter()
+            .filter(|(account, _)| !account.can_overdraw())
+            .map(|(_, balance)| balance)
+            .sum()
+    }
+
+    /**
+    Checks that the balances are what the transactions add up to, that they sum up to 0 so money
+    is only made or destroyed through the sources & sinks, and that nobody else is overdrawn.
+    */
+    pub fn verify(&self) -> Result<(), LedgerError> {
+        let mut replayed: HashMap<Account, Money> = HashMap::new();
+        for transaction in &self.transactions {
+            *replayed.entry(transaction.from).or_default() -= transaction.amount;
+            *replayed.entry(transaction.to).or_default() += transaction.amount;
+        }
+
+        let is_consistent = replayed.len() == self.balances.len()
+            && replayed
+                .iter()
+                .all(|(account, balance)| self.balances.get(account) == Some(balance));
+        let total: Money = self.balances.values().sum();
+        let is_overdrawn = self
+            .balances
+            .iter()
+            .any(|(account, &balance)| balance < 0 && !account.can_overdraw());
+
+        if !is_consistent || total != 0 || is_overdrawn {
+            return Err(LedgerError::Unbalanced);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        character::CharacterId,
+        economy::{Account, Commodity, CompanyId, Ledger, LedgerError, MoneyFlow, TransferReason},
+    };
+
+    #[test]
+    fn transfers_are_double_entry() {
+        let mut ledger = Ledger::default();
+        let mint = Account::Outside(MoneyFlow::Mint);
+        let alice = Account::Character(CharacterId::new_unsafe(0));
+        let company = Account::Company(CompanyId::new_unsafe(0));
+
+        ledger
+            .transfer(mint, alice, 1000, TransferReason::Issuance, 0)
+            .unwrap();
+        ledger
+            .transfer(
+                alice,
+                company,
+                300,
+                TransferReason::Trade(Commodity::Tool, 3),
+                1,
+            )
+            .unwrap();
+
+        assert_eq!(ledger.get_balance(alice), 700);
+        assert_eq!(ledger.get_balance(company), 300);
+        assert_eq!(ledger.get_balance(mint), -1000);
+        assert_eq!(ledger.get_money_supply(), 1000);
+        assert_eq!(ledger.iter_history(alice).count(), 2);
+        assert_eq!(ledger.iter_history(company).count(), 1);
+        assert_eq!(
+            ledger.iter_history(company).next().unwrap().get_reason(),
+            TransferReason::Trade(Commodity::Tool, 3)
+        );
+        assert_eq!(ledger.verify(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_transfers_are_rejected() {
+        let mut ledger = Ledger::default();
+        let alice = Account::Character(CharacterId::new_unsafe(0));
+        let bob = Account::Character(CharacterId::new_unsafe(1));
+        let gift = TransferReason::Gift;
+
+        assert_eq!(
+            ledger.transfer(alice, bob, 1, gift, 0).err(),
+            Some(LedgerError::InsufficientFunds)
+        );
+        assert_eq!(
+            ledger.transfer(alice, bob, 0, gift, 0).err(),
+            Some(LedgerError::InvalidAmount)
+        );
+        assert_eq!(
+            ledger.transfer(alice, alice, 1, gift, 0).err(),
+            Some(LedgerError::SameAccount)
+        );
+        assert!(ledger.get_transactions().is_empty());
+
+        // money spent on upkeep leaves the economy
+        let mint = Account::Outside(MoneyFlow::Mint);
+        ledger
+            .transfer(mint, alice, 100, TransferReason::Issuance, 0)
+            .unwrap();
+        ledger
+            .transfer(
+                alice,
+                Account::Outside(MoneyFlow::Upkeep),
+                40,
+                TransferReason::Upkeep,
+                0,
+            )
+            .unwrap();
+        assert_eq!(ledger.get_money_supply(), 60);
+        assert_eq!(ledger.verify(), Ok(()));
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let mut ledger = Ledger::default();
+        let alice = Account::Character(CharacterId::new_unsafe(0));
+        ledger
+            .transfer(
+                Account::Outside(MoneyFlow::Mint),
+                alice,
+                100,
+                TransferReason::Issuance,
+                0,
+            )
+            .unwrap();
+        *ledger.balances.get_mut(&alice).unwrap() += 1;
+        assert_eq!(ledger.verify(), Err(LedgerError::Unbalanced));
+    }
+}
@@ -0,0 +1,34 @@
+use enum_map::enum_map;
+
+use super::{Commodity, MarketConfig, Price};
+
+impl Default for MarketConfig {
+    fn default() -> Self {
+        Self {
+            intrinsic_prices: enum_map! {
+                commodity => commodity.get_default_intrinsic_price(),
+            },
+            elasticities: enum_map! {
+                commodity => commodity.get_default_elasticity(),
+            },
+        }
+    }
+}
+
+impl MarketConfig {
+    pub fn get_intrinsic_price(&self, commodity: Commodity) -> Price {
+        self.intrinsic_prices[commodity]
+    }
+
+    pub fn set_intrinsic_price(&mut self, commodity: Commodity, price: Price) {
+        self.intrinsic_prices[commodity] = price;
+    }
+
+    pub fn get_elasticity(&self, commodity: Commodity) -> f64 {
+        self.elasticities[commodity]
+    }
+
+    pub fn set_elasticity(&mut self, commodity: Commodity, elasticity: f64) {
+        self.elasticities[commodity] = elasticity;
+    }
+}
@@ -1,11 +1,28 @@
+use std::collections::{HashMap, HashSet};
+
 use enum_map::{Enum, EnumMap};
 
-use crate::Time;
+use crate::{
+    character::CharacterId,
+    dynasty::DynastyId,
+    planet::{PlanetId, RegionId, RegionSpecialty},
+    pop::PopCount,
+    universe::UniverseId,
+    Time,
+};
 
 mod brand;
+mod commodity;
+mod companies;
 mod company;
-
-#[derive(Enum, Clone)]
+mod company_id;
+mod ledger;
+mod market_config;
+pub mod recipe;
+mod recipe_id;
+mod wage;
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq)]
 pub enum Commodity {
     // food
     Crop, // from farms and water; cereal, fruit, etc.
@@ -24,6 +41,7 @@ pub enum Commodity {
     Polymer,  // from Wood, FossilFuel, Crop, recycled waste
     Glass,    // from nothing; assuming sand is free
     Incense,
+    Wood, // from forests
 
     Fuel,
 
@@ -63,6 +81,29 @@ pub enum Commodity {
 
 pub type Quality = u32;
 pub type Quantity = u32;
+pub type Price = f64;
+pub type Money = i64; // in cents, so it adds up exactly
+
+/// How the local markets price each commodity.
+#[derive(Clone, Debug)]
+pub struct MarketConfig {
+    intrinsic_prices: EnumMap<Commodity, Price>,
+    elasticities: EnumMap<Commodity, f64>,
+}
+
+/// A way to turn inputs into commodities, e.g. at a factory.
+pub struct Recipe {
+    name: &'static str,
+    inputs: &'static [(Commodity, Quantity)], // used up every cycle
+    outputs: &'static [(Commodity, Quantity)],
+    tools: &'static [(Commodity, Quantity)], // have to be around, but aren't used up
+    labor: PopCount,
+    time: Time,                              // ticks per cycle
+    specialties: &'static [RegionSpecialty], // of the regions it can be run in
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecipeId(usize);
 
 pub struct Product {
     commodity: Commodity,
@@ -70,25 +111,117 @@ pub struct Product {
 }
 
 pub struct Company {
-    brands: EnumMap<Commodity, BrandId>,
+    name: String,
+    owner: CharacterId,
+    ceo: CharacterId,
+    employees: HashSet<CharacterId>,
+    founded_at: Time,
+    brands: EnumMap<Commodity, Option<BrandId>>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct CompanyId(usize);
 
+/// The name a company sells one commodity under, which people come to trust over time.
 pub struct Brand {
+    company_id: CompanyId,
+    commodity: Commodity,
     created_at: Time,
+    reputation: f64, // on the same scale as quality
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct BrandId(usize);
 
-#[derive(Clone)]
+#[derive(Default)]
+pub struct Companies {
+    companies: Vec<Company>,
+    brands: Vec<Brand>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeError {
+    InvalidQuantity,
+    UnknownTrader,
+    NotAtMarket, // has to stand in a region, not be on the way
+    NotEnoughStock,
+    NotEnoughCargo,
+    CargoFull,
+    InsufficientFunds,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompanyError {
+    UnknownCompany,
+    AlreadyHasBrand,
+    NotEmployee,
+    IsCeo, // has to be replaced first
+    InsufficientFunds,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Ownership {
     Company(CompanyId), // contributes to warehouses, then local market, then discard remaining
     Independent,        // contributes to local market
 }
 
+/// Someone who can hold money.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Account {
+    Character(CharacterId),
+    Dynasty(DynastyId),
+    Company(CompanyId),
+    Retailer(UniverseId, PlanetId, RegionId, usize), // by its index in the region; never closed
+    Government(UniverseId, PlanetId),
+    Outside(MoneyFlow), // where money comes from or goes to outside of the economy
+}
+
+/// Sources & sinks of money, the only accounts that can go below 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MoneyFlow {
+    Mint,      // newly issued money
+    Consumers, // the population buying goods & paying for services, and working for wages
+    Upkeep,    // spent on things that don't belong to anyone, e.g. road maintenance
+    Merchants, // the independent merchants behind the local markets
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferReason {
+    Issuance,
+    Investment,
+    Wage,
+    Trade(Commodity, Quantity),
+    Tax,
+    Fee,
+    Upkeep,
+    Transport,
+    Gift,
+}
+
+#[derive(Clone, Debug)]
+pub struct Transaction {
+    time: Time,
+    from: Account,
+    to: Account,
+    amount: Money,
+    reason: TransferReason,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LedgerError {
+    InvalidAmount, // not above 0
+    SameAccount,
+    InsufficientFunds,
+    Unbalanced, // the balances don't add up to the transactions
+}
+
+/// Double-entry record of all the money in the game; every transfer is kept with its reason.
+#[derive(Default)]
+pub struct Ledger {
+    balances: HashMap<Account, Money>,
+    transactions: Vec<Transaction>,
+}
+
 #[derive(Clone)]
 pub enum Wage {
     Low,
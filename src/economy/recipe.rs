@@ -0,0 +1,548 @@
+use super::{Commodity, Quantity, Recipe};
+use crate::{planet::RegionSpecialty, pop::PopCount, Time};
+
+pub(super) static RECIPES: &[Recipe] = &[
+    Recipe {
+        name: "farming",
+        inputs: &[],
+        outputs: &[(Commodity::Crop, 10)],
+        tools: &[],
+        labor: 10,
+        time: 24,
+        specialties: &[RegionSpecialty::Rural, RegionSpecialty::Estate],
+    },
+    Recipe {
+        name: "fertilized farming",
+        inputs: &[(Commodity::Fertilizer, 1)],
+        outputs: &[(Commodity::Crop, 20)],
+        tools: &[],
+        labor: 10,
+        time: 24,
+        specialties: &[RegionSpecialty::Rural, RegionSpecialty::Estate],
+    },
+    Recipe {
+        name: "ranching",
+        inputs: &[(Commodity::Crop, 5)],
+        outputs: &[(Commodity::Meat, 3)],
+        tools: &[],
+        labor: 5,
+        time: 48,
+        specialties: &[RegionSpecialty::Rural],
+    },
+    Recipe {
+        name: "salt panning",
+        inputs: &[],
+        outputs: &[(Commodity::Salt, 5)],
+        tools: &[],
+        labor: 5,
+        time: 24,
+        specialties: &[RegionSpecialty::Rural, RegionSpecialty::Mining],
+    },
+    Recipe {
+        name: "essence extraction",
+        inputs: &[(Commodity::Crop, 10)],
+        outputs: &[(Commodity::Essence, 1)],
+        tools: &[],
+        labor: 3,
+        time: 72,
+        specialties: &[RegionSpecialty::Arcane],
+    },
+    Recipe {
+        name: "spice harvesting",
+        inputs: &[],
+        outputs: &[(Commodity::Spice, 1)],
+        tools: &[(Commodity::Tool, 2)],
+        labor: 20,
+        time: 168,
+        specialties: &[RegionSpecialty::Mining],
+    },
+    Recipe {
+        name: "well",
+        inputs: &[],
+        outputs: &[(Commodity::Water, 20)],
+        tools: &[],
+        labor: 2,
+        time: 24,
+        specialties: &[
+            RegionSpecialty::Rural,
+            RegionSpecialty::Estate,
+            RegionSpecialty::Urban,
+        ],
+    },
+    Recipe {
+        name: "water purification",
+        inputs: &[(Commodity::Chemical, 1)],
+        outputs: &[(Commodity::Water, 100)],
+        tools: &[(Commodity::Machine, 1)],
+        labor: 5,
+        time: 24,
+        specialties: &[RegionSpecialty::Urban, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "brewing",
+        inputs: &[(Commodity::Crop, 4), (Commodity::Water, 4)],
+        outputs: &[(Commodity::Beverage, 4)],
+        tools: &[],
+        labor: 4,
+        time: 48,
+        specialties: &[
+            RegionSpecialty::Rural,
+            RegionSpecialty::Urban,
+            RegionSpecialty::Industry,
+        ],
+    },
+    Recipe {
+        name: "crop refining",
+        inputs: &[(Commodity::Crop, 5)],
+        outputs: &[(Commodity::Chemical, 2)],
+        tools: &[],
+        labor: 5,
+        time: 48,
+        specialties: &[RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "metal leaching",
+        inputs: &[(Commodity::Metal, 1)],
+        outputs: &[(Commodity::Chemical, 2)],
+        tools: &[(Commodity::Tool, 1)],
+        labor: 5,
+        time: 48,
+        specialties: &[RegionSpecialty::Industry, RegionSpecialty::Mining],
+    },
+    Recipe {
+        name: "spinning",
+        inputs: &[(Commodity::Crop, 4)],
+        outputs: &[(Commodity::Fiber, 4)],
+        tools: &[],
+        labor: 6,
+        time: 24,
+        specialties: &[RegionSpecialty::Rural, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "polymerization",
+        inputs: &[(Commodity::Fuel, 2), (Commodity::Chemical, 1)],
+        outputs: &[(Commodity::Polymer, 3)],
+        tools: &[(Commodity::Machine, 1)],
+        labor: 5,
+        time: 48,
+        specialties: &[RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "glassblowing",
+        inputs: &[(Commodity::Fuel, 1)],
+        outputs: &[(Commodity::Glass, 4)],
+        tools: &[],
+        labor: 4,
+        time: 24,
+        specialties: &[RegionSpecialty::Urban, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "incense making",
+        inputs: &[(Commodity::Wood, 2), (Commodity::Crop, 1)],
+        outputs: &[(Commodity::Incense, 2)],
+        tools: &[],
+        labor: 3,
+        time: 48,
+        specialties: &[RegionSpecialty::Rural, RegionSpecialty::Arcane],
+    },
+    Recipe {
+        name: "logging",
+        inputs: &[],
+        outputs: &[(Commodity::Wood, 10)],
+        tools: &[],
+        labor: 8,
+        time: 24,
+        specialties: &[RegionSpecialty::Forestry],
+    },
+    Recipe {
+        name: "drilling",
+        inputs: &[],
+        outputs: &[(Commodity::Fuel, 10)],
+        tools: &[(Commodity::Tool, 2)],
+        labor: 10,
+        time: 24,
+        specialties: &[RegionSpecialty::Mining],
+    },
+    Recipe {
+        name: "biofuel refining",
+        inputs: &[(Commodity::Crop, 10)],
+        outputs: &[(Commodity::Fuel, 3)],
+        tools: &[],
+        labor: 4,
+        time: 48,
+        specialties: &[RegionSpecialty::Rural, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "gold mining",
+        inputs: &[],
+        outputs: &[(Commodity::Gold, 1)],
+        tools: &[(Commodity::Tool, 2)],
+        labor: 15,
+        time: 72,
+        specialties: &[RegionSpecialty::Mining],
+    },
+    Recipe {
+        name: "gem mining",
+        inputs: &[],
+        outputs: &[(Commodity::Gem, 1)],
+        tools: &[(Commodity::Tool, 2)],
+        labor: 15,
+        time: 72,
+        specialties: &[RegionSpecialty::Mining],
+    },
+    Recipe {
+        name: "hand mining",
+        inputs: &[],
+        outputs: &[(Commodity::Metal, 2)],
+        tools: &[],
+        labor: 20,
+        time: 48,
+        specialties: &[RegionSpecialty::Mining],
+    },
+    Recipe {
+        name: "mining",
+        inputs: &[],
+        outputs: &[(Commodity::Metal, 5)],
+        tools: &[(Commodity::Tool, 2)],
+        labor: 10,
+        time: 24,
+        specialties: &[RegionSpecialty::Mining],
+    },
+    Recipe {
+        name: "smelting",
+        inputs: &[(Commodity::Metal, 3), (Commodity::Fuel, 1)],
+        outputs: &[(Commodity::Alloy, 2)],
+        tools: &[],
+        labor: 6,
+        time: 48,
+        specialties: &[RegionSpecialty::Mining, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "tailoring",
+        inputs: &[(Commodity::Fiber, 3)],
+        outputs: &[(Commodity::Apparel, 1)],
+        tools: &[],
+        labor: 3,
+        time: 24,
+        specialties: &[RegionSpecialty::Urban, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "papermaking",
+        inputs: &[(Commodity::Wood, 2)],
+        outputs: &[(Commodity::Paper, 5)],
+        tools: &[],
+        labor: 3,
+        time: 24,
+        specialties: &[RegionSpecialty::Forestry, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "jewelcrafting",
+        inputs: &[(Commodity::Gem, 1), (Commodity::Metal, 1)],
+        outputs: &[(Commodity::Accessory, 2)],
+        tools: &[(Commodity::Tool, 1)],
+        labor: 2,
+        time: 72,
+        specialties: &[RegionSpecialty::Urban, RegionSpecialty::Arcane],
+    },
+    Recipe {
+        name: "carpentry",
+        inputs: &[(Commodity::Wood, 5)],
+        outputs: &[(Commodity::Furniture, 1)],
+        tools: &[(Commodity::Tool, 1)],
+        labor: 2,
+        time: 48,
+        specialties: &[RegionSpecialty::Forestry, RegionSpecialty::Urban],
+    },
+    Recipe {
+        name: "appliance assembly",
+        inputs: &[(Commodity::Parts, 3), (Commodity::Polymer, 2)],
+        outputs: &[(Commodity::Appliance, 1)],
+        tools: &[(Commodity::Tool, 1)],
+        labor: 4,
+        time: 48,
+        specialties: &[RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "gadget assembly",
+        inputs: &[
+            (Commodity::Computer, 1),
+            (Commodity::Polymer, 1),
+            (Commodity::Parts, 1),
+        ],
+        outputs: &[(Commodity::Gadget, 4)],
+        tools: &[(Commodity::Machine, 1)],
+        labor: 4,
+        time: 48,
+        specialties: &[RegionSpecialty::Industry, RegionSpecialty::DataCenter],
+    },
+    Recipe {
+        name: "vehicle assembly",
+        inputs: &[
+            (Commodity::Parts, 20),
+            (Commodity::Polymer, 10),
+            (Commodity::Plate, 10),
+        ],
+        outputs: &[(Commodity::Vehicle, 1)],
+        tools: &[(Commodity::Machine, 2)],
+        labor: 20,
+        time: 168,
+        specialties: &[RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "herbalism",
+        inputs: &[(Commodity::Crop, 5), (Commodity::Essence, 1)],
+        outputs: &[(Commodity::Medicine, 1)],
+        tools: &[],
+        labor: 2,
+        time: 72,
+        specialties: &[RegionSpecialty::Arcane, RegionSpecialty::Rural],
+    },
+    Recipe {
+        name: "pharmacy",
+        inputs: &[(Commodity::Chemical, 2), (Commodity::Crop, 2)],
+        outputs: &[(Commodity::Medicine, 1)],
+        tools: &[(Commodity::Machine, 1)],
+        labor: 3,
+        time: 48,
+        specialties: &[RegionSpecialty::Urban, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "stillsuit sewing",
+        inputs: &[(Commodity::Polymer, 2), (Commodity::Fiber, 2)],
+        outputs: &[(Commodity::Stillsuit, 1)],
+        tools: &[(Commodity::Tool, 1)],
+        labor: 3,
+        time: 72,
+        specialties: &[
+            RegionSpecialty::Industry,
+            RegionSpecialty::RocketPad,
+            RegionSpecialty::SpaceElevator,
+        ],
+    },
+    Recipe {
+        name: "composting",
+        inputs: &[(Commodity::Chemical, 1), (Commodity::Crop, 1)],
+        outputs: &[(Commodity::Fertilizer, 3)],
+        tools: &[],
+        labor: 3,
+        time: 48,
+        specialties: &[RegionSpecialty::Rural, RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "smithing",
+        inputs: &[(Commodity::Metal, 2), (Commodity::Wood, 1)],
+        outputs: &[(Commodity::Tool, 2)],
+        tools: &[],
+        labor: 3,
+        time: 48,
+        specialties: &[
+            RegionSpecialty::Urban,
+            RegionSpecialty::Mining,
+            RegionSpecialty::Industry,
+        ],
+    },
+    Recipe {
+        name: "machining",
+        inputs: &[(Commodity::Parts, 10), (Commodity::Plate, 2)],
+        outputs: &[(Commodity::Machine, 1)],
+        tools: &[(Commodity::Tool, 2)],
+        labor: 8,
+        time: 96,
+        specialties: &[RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "computer fabrication",
+        inputs: &[
+            (Commodity::Parts, 2),
+            (Commodity::Metal, 1),
+            (Commodity::Chemical, 2),
+            (Commodity::Polymer, 1),
+            (Commodity::Glass, 1),
+        ],
+        outputs: &[(Commodity::Computer, 1)],
+        tools: &[(Commodity::Machine, 2)],
+        labor: 6,
+        time: 96,
+        specialties: &[RegionSpecialty::Industry, RegionSpecialty::DataCenter],
+    },
+    Recipe {
+        name: "printer assembly",
+        inputs: &[
+            (Commodity::Computer, 1),
+            (Commodity::Polymer, 4),
+            (Commodity::Parts, 4),
+        ],
+        outputs: &[(Commodity::Printer, 1)],
+        tools: &[(Commodity::Machine, 1)],
+        labor: 4,
+        time: 96,
+        specialties: &[RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "plate rolling",
+        inputs: &[(Commodity::Alloy, 2)],
+        outputs: &[(Commodity::Plate, 2)],
+        tools: &[(Commodity::Tool, 1)],
+        labor: 4,
+        time: 24,
+        specialties: &[RegionSpecialty::Industry],
+    },
+    Recipe {
+        name: "parts making",
+        inputs: &[(Commodity::Metal, 2)],
+        outputs: &[(Commodity::Parts, 2)],
+        tools: &[(Commodity::Tool, 1)],
+        labor: 4,
+        time: 24,
+        specialties: &[RegionSpecialty::Industry, RegionSpecialty::Urban],
+    },
+    Recipe {
+        name: "scaffolding",
+        inputs: &[(Commodity::Wood, 4)],
+        outputs: &[(Commodity::Scaffold, 2)],
+        tools: &[],
+        labor: 3,
+        time: 24,
+        specialties: &[
+            RegionSpecialty::Forestry,
+            RegionSpecialty::Urban,
+            RegionSpecialty::Industry,
+        ],
+    },
+    Recipe {
+        name: "structure welding",
+        inputs: &[(Commodity::Plate, 4)],
+        outputs: &[(Commodity::Structure, 1)],
+        tools: &[(Commodity::Machine, 1)],
+        labor: 8,
+        time: 72,
+        specialties: &[RegionSpecialty::Industry, RegionSpecialty::SpaceElevator],
+    },
+];
+
+impl Recipe {
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_inputs(&self) -> &'static [(Commodity, Quantity)] {
+        self.inputs
+    }
+
+    pub fn get_outputs(&self) -> &'static [(Commodity, Quantity)] {
+        self.outputs
+    }
+
+    pub fn get_tools(&self) -> &'static [(Commodity, Quantity)] {
+        self.tools
+    }
+
+    pub fn get_labor(&self) -> PopCount {
+        self.labor
+    }
+
+    pub fn get_time(&self) -> Time {
+        self.time
+    }
+
+    pub fn is_allowed_in(&self, specialty: RegionSpecialty) -> bool {
+        self.specialties.contains(&specialty)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use enum_map::EnumMap;
+
+    use super::RECIPES;
+    use crate::{
+        economy::{Commodity, Recipe, RecipeId},
+        planet::RegionSpecialty,
+    };
+
+    pub fn get_recipes() -> impl Iterator<Item = (RecipeId, &'static Recipe)> {
+        RECIPES
+            .iter()
+            .enumerate()
+            .map(|(index, recipe)| (RecipeId::new_unsafe(index), recipe))
+    }
+
+    /**
+    Commodities that can't be made from scratch with the recipes, i.e. all of their recipes need
+    something that can't be made, maybe in a loop like tools needed to mine the metal for tools.
+    */
+    fn find_unproducible(recipes: &[Recipe]) -> Vec<Commodity> {
+        let mut producible = EnumMap::<Commodity, bool>::default();
+        let mut is_done = vec![false; recipes.len()];
+
+        loop {
+            let mut has_changed = false;
+            for (recipe, is_done) in recipes.iter().zip(is_done.iter_mut()) {
+                let can_run = recipe
+                    .inputs
+                    .iter()
+                    .chain(recipe.tools)
+                    .all(|&(commodity, _)| producible[commodity]);
+                if *is_done || !can_run {
+                    continue;
+                }
+                for &(commodity, _) in recipe.outputs {
+                    producible[commodity] = true;
+                }
+                *is_done = true;
+                has_changed = true;
+            }
+            if !has_changed {
+                break;
+            }
+        }
+
+        producible
+            .iter()
+            .filter(|(_, &is_producible)| !is_producible)
+            .map(|(commodity, _)| commodity)
+            .collect()
+    }
+
+    #[test]
+    fn every_commodity_is_producible() {
+        assert_eq!(find_unproducible(RECIPES), vec![]);
+        for recipe in RECIPES {
+            assert!(!recipe.outputs.is_empty(), "{} makes nothing", recipe.name);
+            assert!(recipe.time > 0, "{} takes no time", recipe.name);
+            assert!(
+                !recipe.specialties.is_empty(),
+                "{} can't be run",
+                recipe.name
+            );
+        }
+    }
+
+    #[test]
+    fn loops_are_not_producible() {
+        let recipes = [
+            Recipe {
+                name: "mining",
+                inputs: &[],
+                outputs: &[(Commodity::Metal, 1)],
+                tools: &[(Commodity::Tool, 1)],
+                labor: 1,
+                time: 1,
+                specialties: &[RegionSpecialty::Mining],
+            },
+            Recipe {
+                name: "smithing",
+                inputs: &[(Commodity::Metal, 1)],
+                outputs: &[(Commodity::Tool, 1)],
+                tools: &[],
+                labor: 1,
+                time: 1,
+                specialties: &[RegionSpecialty::Urban],
+            },
+        ];
+        let unproducible = find_unproducible(&recipes);
+        assert!(unproducible.contains(&Commodity::Metal));
+        assert!(unproducible.contains(&Commodity::Tool));
+        assert!(unproducible.contains(&Commodity::Crop));
+    }
+}
@@ -0,0 +1,17 @@
+use super::{recipe::RECIPES, Recipe, RecipeId};
+
+impl RecipeId {
+    pub fn new_unsafe(index: usize) -> Self {
+        Self(index)
+    }
+
+    pub fn get_recipe(&self) -> &'static Recipe {
+        &RECIPES[self.0]
+    }
+}
+
+impl From<RecipeId> for usize {
+    fn from(id: RecipeId) -> Self {
+        id.0
+    }
+}
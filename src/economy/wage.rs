@@ -0,0 +1,12 @@
+use super::{Money, Wage};
+
+impl Wage {
+    /// What a worker is paid for a season of work.
+    pub fn get_seasonal_pay(&self) -> Money {
+        match self {
+            Wage::Low => 6_000,
+            Wage::Normal => 10_000,
+            Wage::High => 15_000,
+        }
+    }
+}
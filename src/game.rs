@@ -1,105 +1,222 @@
-use noise::{utils::NoiseMapBuilder, Seedable};
-use poisson_diskus::bridson;
+use std::collections::HashMap;
+
 use rand::{
     prelude::{SliceRandom, StdRng},
     Rng, SeedableRng,
 };
-use voronoice::{BoundingBox, VoronoiBuilder};
 
 use crate::{
+    character::CharacterId,
     dynasty::dynasty::NewCharacter,
+    dynasty::DynastyId,
+    economy::{
+        Account, Commodity, Companies, CompanyError, CompanyId, Ledger, LedgerError, Money,
+        MoneyFlow, Ownership, Quantity, TradeError, TransferReason,
+    },
+    movement::{MovementError, MovementEvent, Vehicle},
+    naming::{Culture, NameGenerator},
+    planet::{
+        layout::LayoutKind, LocalMarket, Planet, PlanetConfig, PlanetId, RegionId, RouteCache,
+        Storage, Wrapping,
+    },
+    portal::{PortalAccess, PortalEnd, PortalError, PortalId, Portals},
+    travel::{FreightError, Spaceport, TravelError, Traveller},
+    unit::{Parties, PartyError, PartyId},
     universe::{Universe, UniverseId, Universes},
-    CompleteCoor, Game, Time,
+    CompleteCoor, Game, Time, WorldConfig,
 };
 
-impl Game {
-    pub fn create() -> Self {
-        // let _creation_rng = StdRng::seed_from_u64(123);
-        let mut creation_rng = StdRng::from_entropy();
+// money every dynasty starts with: 1000.00
+const STARTING_FUNDS: Money = 100_000;
+// goods each member of a party can carry
+const CARGO_PER_MEMBER: Quantity = 50;
+// merchants look for deals between planets once per period
+const FREIGHT_PERIOD: Time = 24 * 7;
+// portion of the retail takings paid to the government of the planet
+const SALES_TAX_RATE: f64 = 0.1;
 
-        let mut universes = Universes::default();
-
-        let (base_universe_id, base_universe) = universes.create(&mut creation_rng);
+/// Shoppers get to know the brands of the company-owned retailers they bought from.
+fn record_retail_sales(companies: &mut Companies, planet: &Planet) {
+    for region in planet.get_regions() {
+        for retailer in region.get_retailers() {
+            let Ownership::Company(company_id) = *retailer.get_ownership() else {
+                continue;
+            };
+            for (commodity, storage) in retailer.iter_storage() {
+                if storage.get_sales() > 0 {
+                    companies.record_sales(
+                        company_id,
+                        commodity,
+                        storage.get_avg_quality(),
+                        storage.get_sales(),
+                    );
+                }
+            }
+        }
+    }
+}
 
-        let (base_planet_id, _base_planet) = base_universe.create_planet(&mut creation_rng);
+/**
+Shoppers pay the retailers for what they bought, and the retailers pay the sales tax to the
+government of the planet. The takings of company-owned retailers go to the company.
+*/
+fn collect_retail_revenue(
+    ledger: &mut Ledger,
+    universe_id: UniverseId,
+    planet_id: PlanetId,
+    planet: &Planet,
+    time: Time,
+) {
+    let government = Account::Government(universe_id, planet_id);
+    for (region_index, region) in planet.get_regions().iter().enumerate() {
+        let region_id = RegionId::new_unsafe(region_index);
+        for (index, retailer) in region.get_retailers().iter().enumerate() {
+            let account = match *retailer.get_ownership() {
+                Ownership::Company(company_id) => Account::Company(company_id),
+                Ownership::Independent => {
+                    Account::Retailer(universe_id, planet_id, region_id, index)
+                }
+            };
+            for (commodity, storage) in retailer.iter_storage() {
+                let revenue = storage.get_revenue();
+                if revenue <= 0 {
+                    continue;
+                }
+                ledger
+                    .transfer(
+                        Account::Outside(MoneyFlow::Consumers),
+                        account,
+                        revenue,
+                        TransferReason::Trade(commodity, storage.get_sales()),
+                        time,
+                    )
+                    .expect("shoppers can always pay");
+                let tax = (revenue as f64 * SALES_TAX_RATE).round() as Money;
+                if tax > 0 {
+                    ledger
+                        .transfer(account, government, tax, TransferReason::Tax, time)
+                        .expect("the takings should cover the tax");
+                }
+            }
+        }
+    }
+}
 
-        base_universe.create_planet(&mut creation_rng);
-        base_universe.create_planet(&mut creation_rng);
+/// Companies pay for the upkeep of their warehouses; those they can't pay for are closed.
+fn charge_storage(ledger: &mut Ledger, planet: &mut Planet, time: Time) {
+    let mut unpaid = Vec::new();
+    for (region_index, region) in planet.get_regions().iter().enumerate() {
+        for (index, warehouse) in region.get_warehouses().iter().enumerate() {
+            if let Ownership::Company(company_id) = *warehouse.get_ownership() {
+                let paid = ledger.transfer(
+                    Account::Company(company_id),
+                    Account::Outside(MoneyFlow::Upkeep),
+                    warehouse.get_upkeep(),
+                    TransferReason::Upkeep,
+                    time,
+                );
+                if let Err(LedgerError::InsufficientFunds) = paid {
+                    unpaid.push((RegionId::new_unsafe(region_index), index));
+                }
+            }
+        }
+    }
+    // last first, so the indices of the others stay the same
+    for (region_id, index) in unpaid.into_iter().rev() {
+        planet.close_warehouse(region_id, index);
+    }
+}
 
-        let graph_width = 10.;
-        let graph_half_width = graph_width / 2.;
+/// Companies pay their farm workers for the season; the farms they can't pay are left unworked.
+fn pay_farm_wages(ledger: &mut Ledger, planet: &mut Planet, time: Time) {
+    for (region_id, owner, wages) in planet.hire_farm_workers(time) {
+        let Ownership::Company(company_id) = owner else {
+            continue;
+        };
+        if wages == 0 {
+            continue;
+        }
+        let paid = ledger.transfer(
+            Account::Company(company_id),
+            Account::Outside(MoneyFlow::Consumers),
+            wages,
+            TransferReason::Wage,
+            time,
+        );
+        if let Err(LedgerError::InsufficientFunds) = paid {
+            planet.lay_off_farm_workers(region_id, &owner);
+        }
+    }
+}
 
-        let sites = {
-            let box_size = [graph_width, graph_width];
-            let rmin = graph_half_width / 10.;
-            let k = 30;
-            let use_pbc = false;
+/// Companies pay the merchants who carry the goods on their trade routes, if they can.
+fn charge_transport(
+    ledger: &mut Ledger,
+    owner: &Ownership,
+    cost: Money,
+    time: Time,
+) -> Result<(), LedgerError> {
+    if let Ownership::Company(company_id) = *owner {
+        ledger.transfer(
+            Account::Company(company_id),
+            Account::Outside(MoneyFlow::Merchants),
+            cost,
+            TransferReason::Transport,
+            time,
+        )?;
+    }
+    Ok(())
+}
 
-            bridson(&box_size, rmin, k, use_pbc)
-                .expect("The map site wasn't generated")
-                .into_iter()
-                .map(|[x, y]| voronoice::Point {
-                    x: x - graph_half_width,
-                    y: y - graph_half_width,
-                })
-                .collect()
+impl Default for WorldConfig {
+    fn default() -> Self {
+        let planet = |wrapping| PlanetConfig {
+            layout: LayoutKind::Voronoi,
+            wrapping,
+            model_width: None,
         };
 
-        // builds a voronoi diagram from the set of sites above, bounded by a square of size 4
-        let graph = VoronoiBuilder::default()
-            .set_sites(sites)
-            .set_bounding_box(BoundingBox::new_centered_square(graph_width))
-            .set_lloyd_relaxation_iterations(2)
-            .build()
-            .unwrap();
+        Self {
+            planets: vec![
+                planet(Wrapping::Horizontal),
+                planet(Wrapping::Horizontal),
+                planet(Wrapping::None),
+            ],
+            parallel_universes: vec![vec![planet(Wrapping::Horizontal), planet(Wrapping::None)]],
+            market: Default::default(),
+        }
+    }
+}
 
-        use noise::{utils::PlaneMapBuilder, Fbm};
+impl Game {
+    pub fn create(config: &WorldConfig) -> Self {
+        // let _creation_rng = StdRng::seed_from_u64(123);
+        let mut creation_rng = StdRng::from_entropy();
 
-        let fbm = Fbm::new().set_seed(creation_rng.gen());
+        let names = NameGenerator::default();
 
-        let side = 15;
-        let noise_model_ratio = side as f64 / graph_width;
-        let noise_map = PlaneMapBuilder::new(&fbm)
-            .set_size(side, side)
-            .set_x_bounds(-graph_half_width / 2., graph_half_width / 2.)
-            .set_y_bounds(-graph_half_width / 2., graph_half_width / 2.)
-            .set_is_seamless(true)
-            .build();
+        let mut universes = Universes::default();
 
-        let mut noise_map: Vec<_> = graph
-            .iter_cells()
-            .map(|cell| {
-                let pos = cell.site_position();
-                let (x, y) = (pos.x + graph_half_width, pos.y + graph_half_width);
-                let (x, y) = (noise_model_ratio as f64 * x, noise_model_ratio as f64 * y);
-                let noise = noise_map.get_value(x as usize, y as usize);
+        let (base_universe_id, base_universe) = universes.create(&mut creation_rng, &names);
 
-                noise
-            })
-            .collect();
+        let (home_planet_config, other_planet_configs) = config
+            .planets
+            .split_first()
+            .expect("should have at least 1 planet");
 
-        fn get_min_max(data: &Vec<f64>) -> (f64, f64) {
-            if data.len() == 0 {
-                unreachable!("should have at least 1 element in the noise vector");
-            }
-            let start = data[0];
-            data.iter().fold((start, start), |(min, max), &cur| {
-                if cur > max {
-                    (min, cur)
-                } else if cur < min {
-                    (cur, max)
-                } else {
-                    (min, max)
-                }
-            })
-        }
+        let (base_planet_id, _base_planet) =
+            base_universe.create_planet(&mut creation_rng, home_planet_config, &names);
 
-        let (min_noise, max_noise) = get_min_max(&noise_map);
-        let normalize = |value: f64| value - min_noise / (max_noise - min_noise);
+        for planet_config in other_planet_configs {
+            base_universe.create_planet(&mut creation_rng, planet_config, &names);
+        }
 
-        noise_map
-            .iter_mut()
-            .for_each(|noise| *noise = normalize(*noise));
+        for planet_configs in &config.parallel_universes {
+            let (_, universe) = universes.create(&mut creation_rng, &names);
+            for planet_config in planet_configs {
+                universe.create_planet(&mut creation_rng, planet_config, &names);
+            }
+        }
 
         let (dynasties, characters): (Vec<_>, Vec<_>) = {
             let planet = universes.get_universes()[0].get_planet(base_planet_id);
@@ -107,6 +224,7 @@ impl Game {
 
             let pre_build = vec![(
                 "John".into(),
+                names.surname(&mut creation_rng, Culture::Terran),
                 CompleteCoor::OnPlanetRegion(
                     base_universe_id,
                     base_planet_id,
@@ -114,8 +232,10 @@ impl Game {
                 ),
             )];
 
-            let random = (0..2000).map(|index| {
-                let name = format!("C{}", index);
+            let random = (0..2000).map(|_| {
+                let culture = Culture::random(&mut creation_rng);
+                let name = names.person(&mut creation_rng, culture);
+                let surname = names.surname(&mut creation_rng, culture);
                 let coor = CompleteCoor::OnPlanetRegion(
                     base_universe_id,
                     base_planet_id,
@@ -124,17 +244,18 @@ impl Game {
                         .expect("cannot randomly choose a land region")
                         .clone(),
                 );
-                (name, coor)
+                (name, surname, coor)
             });
 
             let total_len = random.len() + 1;
 
             pre_build.into_iter().chain(random).fold(
                 (Vec::with_capacity(total_len), Vec::with_capacity(total_len)),
-                |(mut dynasties, mut characters), (name, coor)| {
+                |(mut dynasties, mut characters), (name, surname, coor)| {
                     Self::start_dynasty(
                         &mut dynasties,
                         &mut characters,
+                        surname,
                         NewCharacter { name, coor },
                     );
                     (dynasties, characters)
@@ -142,11 +263,74 @@ impl Game {
             )
         };
 
+        let mut ledger = Ledger::default();
+        for index in 0..dynasties.len() {
+            ledger
+                .transfer(
+                    Account::Outside(MoneyFlow::Mint),
+                    Account::Dynasty(DynastyId::new_unsafe(index)),
+                    STARTING_FUNDS,
+                    TransferReason::Issuance,
+                    0,
+                )
+                .expect("issuing money should always work");
+        }
+
+        // chain the universes with open portals, plus a one-way portal claimed by a random dynasty
+        let mut portals = Portals::default();
+        let all_universes = universes.get_universes();
+        for (index, pair) in all_universes.windows(2).enumerate() {
+            let from = (UniverseId::new_unsafe(index), &pair[0]);
+            let to = (UniverseId::new_unsafe(index + 1), &pair[1]);
+            portals.create_random(
+                &mut creation_rng,
+                &names,
+                from,
+                to,
+                PortalAccess::Anyone,
+                false,
+            );
+
+            let dynasty_id = DynastyId::new_unsafe(creation_rng.gen_range(0..dynasties.len()));
+            portals.create_random(
+                &mut creation_rng,
+                &names,
+                from,
+                to,
+                PortalAccess::Dynasty(dynasty_id),
+                true,
+            );
+        }
+
+        // most movers head to spaceports & big towns, and markets open with their first prices
+        let mut route_caches = HashMap::new();
+        for (universe_index, universe) in universes.get_universes_mut().iter_mut().enumerate() {
+            for (planet_index, planet) in universe.get_planets_mut().iter_mut().enumerate() {
+                planet.progress_markets(0, &config.market);
+                let mut cache = RouteCache::default();
+                cache.prepare(planet, Some(planet.get_road_bonus()));
+                let key = (
+                    UniverseId::new_unsafe(universe_index),
+                    PlanetId::new_unsafe(planet_index),
+                );
+                route_caches.insert(key, cache);
+            }
+        }
+
         Self {
             characters,
             dynasties,
             generation: 0,
             parties: Default::default(),
+            travels: Default::default(),
+            portals,
+            movements: Default::default(),
+            movement_events: Default::default(),
+            route_caches,
+            market_config: config.market.clone(),
+            ledger,
+            companies: Default::default(),
+            cargoes: Default::default(),
             player_character_id: 0,
             tick: 0,
             universes,
@@ -155,6 +339,670 @@ impl Game {
 
     pub fn progress(&mut self) {
         self.tick += 1;
+        self.progress_travels();
+        self.progress_freights();
+        self.progress_portals();
+        self.progress_planets();
+        self.companies.progress(self.tick);
+        self.progress_movements();
+    }
+
+    /// Moves everyone walking or riding on a planet, and records who arrived or got blocked.
+    fn progress_movements(&mut self) {
+        let ended = self.movements.progress(&self.universes);
+        let universes = self.universes.get_universes();
+
+        for movement in self
+            .movements
+            .iter_movements()
+            .chain(ended.iter().map(|(movement, _)| movement))
+        {
+            let (universe_id, planet_id) = movement.get_location();
+            let planet = universes[usize::from(universe_id)].get_planet(planet_id);
+            let coor = movement.get_coor(planet);
+            for character_id in get_members(&self.parties, movement.get_traveller()) {
+                self.characters[usize::from(character_id)].coor = coor;
+            }
+        }
+
+        self.movement_events = ended.into_iter().map(|(_, event)| event).collect();
+        if !self.movement_events.is_empty() {
+            self.generation += 1;
+        }
+    }
+
+    /// Sets a character or a party off to a region of the planet they are on.
+    pub fn move_to(
+        &mut self,
+        traveller: Traveller,
+        destination: RegionId,
+        vehicle: Vehicle,
+    ) -> Result<(), MovementError> {
+        if self.is_travelling(traveller) {
+            return Err(MovementError::AlreadyTravelling);
+        }
+
+        let members = get_members(&self.parties, traveller);
+        let (universe_id, planet_id, from) = match members
+            .first()
+            .map(|&id| self.characters[usize::from(id)].coor)
+        {
+            Some(CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id)) => {
+                (universe_id, planet_id, region_id)
+            }
+            Some(_) => return Err(MovementError::NotInRegion),
+            None => return Err(MovementError::UnknownTraveller),
+        };
+
+        let planet = self.universes.get_universes()[usize::from(universe_id)].get_planet(planet_id);
+        let route = match vehicle {
+            // they go at the same pace on any road, so the fastest way on foot is theirs too
+            Vehicle::OnFoot | Vehicle::Horse => self
+                .route_caches
+                .entry((universe_id, planet_id))
+                .or_default()
+                .find_route(planet, Some(planet.get_road_bonus()), from, destination),
+            Vehicle::Car => vehicle.find_route(planet, from, destination),
+            Vehicle::Sailboat | Vehicle::Steamship => {
+                if !planet.get_regions()[usize::from(from)].has_port() {
+                    return Err(MovementError::NotAtPort);
+                }
+                vehicle.find_route(planet, from, destination)
+            }
+        }
+        .ok_or(MovementError::NoRoute)?;
+
+        self.movements
+            .start(traveller, (universe_id, planet_id), route, vehicle)?;
+        Ok(())
+    }
+
+    pub fn get_movement_events(&self) -> &[MovementEvent] {
+        &self.movement_events
+    }
+
+    /**
+    Wears down roads, has farms hire & pay their workers, lets farms & fisheries bring in their
+    harvests, runs the factories, lets goods spoil in warehouses, ships goods along trade routes,
+    finishes rezonings, changes land use, and reprices the markets, where the retailers take their
+    sales.
+    */
+    fn progress_planets(&mut self) {
+        let time = self.tick;
+        for (universe_index, universe) in self.universes.get_universes_mut().iter_mut().enumerate()
+        {
+            let universe_id = UniverseId::new_unsafe(universe_index);
+            for (planet_index, planet) in universe.get_planets_mut().iter_mut().enumerate() {
+                let planet_id = PlanetId::new_unsafe(planet_index);
+                planet.progress_roads();
+                pay_farm_wages(&mut self.ledger, planet, time);
+                planet.progress_farms(time);
+                planet.progress_fisheries(time);
+                planet.progress_factories();
+                if planet.progress_warehouses(time) {
+                    charge_storage(&mut self.ledger, planet, time);
+                }
+                // shipments that can't be paid for don't leave
+                for (index, owner, cost) in planet.progress_trade_routes(time) {
+                    let paid = charge_transport(&mut self.ledger, &owner, cost, time);
+                    if let Err(LedgerError::InsufficientFunds) = paid {
+                        planet.hold_shipment(index);
+                    }
+                }
+                planet.progress_zoning(time);
+                planet.progress_spots(time);
+                if planet.progress_markets(time, &self.market_config) {
+                    record_retail_sales(&mut self.companies, planet);
+                    collect_retail_revenue(&mut self.ledger, universe_id, planet_id, planet, time);
+                    // towns grow into markets worth keeping routes to
+                    self.route_caches
+                        .entry((universe_id, planet_id))
+                        .or_default()
+                        .prepare(planet, Some(planet.get_road_bonus()));
+                }
+            }
+        }
+    }
+
+    /// Drops off those who have come out of a portal.
+    fn progress_portals(&mut self) {
+        let arrived = self.portals.progress(self.tick);
+        if arrived.is_empty() {
+            return;
+        }
+
+        for transit in arrived {
+            let PortalEnd {
+                universe_id,
+                planet_id,
+                region_id,
+            } = transit.get_exit();
+            for character_id in get_members(&self.parties, transit.get_traveller()) {
+                self.characters[usize::from(character_id)].coor =
+                    CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id);
+            }
+        }
+        self.generation += 1;
+    }
+
+    /// Sends a character or a party through a portal at their location. They stay at the entrance
+    /// until they come out, at the returned time.
+    pub fn traverse_portal(
+        &mut self,
+        traveller: Traveller,
+        portal_id: PortalId,
+    ) -> Result<Time, PortalError> {
+        if self.is_travelling(traveller) {
+            return Err(PortalError::AlreadyTravelling);
+        }
+
+        let members = get_members(&self.parties, traveller);
+        let entrance = match members
+            .first()
+            .map(|&id| self.characters[usize::from(id)].coor)
+        {
+            Some(CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id)) => PortalEnd {
+                universe_id,
+                planet_id,
+                region_id,
+            },
+            Some(_) => return Err(PortalError::NotAtPortal),
+            None => return Err(PortalError::UnknownTraveller),
+        };
+        let dynasty_ids: Vec<_> = members
+            .iter()
+            .map(|&id| self.characters[usize::from(id)].dynasty_id)
+            .collect();
+
+        let transit =
+            self.portals
+                .traverse(portal_id, traveller, entrance, &dynasty_ids, self.tick)?;
+
+        self.generation += 1;
+        Ok(transit.get_arrival())
+    }
+
+    pub fn get_ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    pub fn get_companies(&self) -> &Companies {
+        &self.companies
+    }
+
+    /// Has a character start a company, put up with capital from their dynasty's funds.
+    pub fn found_company(
+        &mut self,
+        founder: CharacterId,
+        name: String,
+        capital: Money,
+    ) -> Result<CompanyId, CompanyError> {
+        let dynasty_id = self.characters[usize::from(founder)].dynasty_id;
+        if capital > self.ledger.get_balance(Account::Dynasty(dynasty_id)) {
+            return Err(CompanyError::InsufficientFunds);
+        }
+
+        let company_id = self.companies.found(founder, name, self.tick);
+        if capital > 0 {
+            self.ledger
+                .transfer(
+                    Account::Dynasty(dynasty_id),
+                    Account::Company(company_id),
+                    capital,
+                    TransferReason::Investment,
+                    self.tick,
+                )
+                .expect("funds were checked");
+        }
+        self.generation += 1;
+        Ok(company_id)
+    }
+
+    pub fn get_player_character_id(&self) -> CharacterId {
+        CharacterId::new_unsafe(self.player_character_id)
+    }
+
+    /// Goods the character or party carries; nothing if they haven't traded yet.
+    pub fn get_cargo(&self, traveller: Traveller) -> Option<&Storage> {
+        self.cargoes.get(&traveller)
+    }
+
+    /**
+    Groups characters standing in the same region into a party, led by the first of them. Nobody
+    can be in two parties, nor join one on the way somewhere.
+    */
+    pub fn form_party(&mut self, characters: &[CharacterId]) -> Result<PartyId, PartyError> {
+        let (&leader, others) = characters.split_first().ok_or(PartyError::NoMembers)?;
+        for &character_id in characters {
+            if self.parties.find_party(character_id).is_some() {
+                return Err(PartyError::AlreadyInParty);
+            }
+            if self.is_travelling(Traveller::Character(character_id)) {
+                return Err(PartyError::AlreadyTravelling);
+            }
+        }
+
+        let region_of =
+            |character_id: CharacterId| match self.characters[usize::from(character_id)].coor {
+                CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id) => {
+                    Some((universe_id, planet_id, region_id))
+                }
+                _ => None,
+            };
+        let region = region_of(leader);
+        if region.is_none() || others.iter().any(|&id| region_of(id) != region) {
+            return Err(PartyError::NotTogether);
+        }
+
+        self.generation += 1;
+        Ok(self.parties.create(leader, others))
+    }
+
+    /// Whether the traveller, any of their members, or a party one of them is in is on the way.
+    fn is_travelling(&self, traveller: Traveller) -> bool {
+        let members = get_members(&self.parties, traveller);
+        let mut travellers = members
+            .iter()
+            .flat_map(|&id| {
+                let party = self.parties.find_party(id).map(Traveller::Party);
+                [Some(Traveller::Character(id)), party]
+            })
+            .flatten()
+            .chain([traveller]);
+        travellers.any(|traveller| {
+            self.movements.get_movement(traveller).is_some()
+                || self.travels.get_voyage(traveller).is_some()
+                || self.portals.get_transit(traveller).is_some()
+        })
+    }
+
+    pub fn get_cargo_capacity(&self, traveller: Traveller) -> Quantity {
+        get_members(&self.parties, traveller).len() as Quantity * CARGO_PER_MEMBER
+    }
+
+    /// Where the trader stands, and the dynasty paying for their trades.
+    fn find_trader(&self, traveller: Traveller) -> Result<(CompleteCoor, DynastyId), TradeError> {
+        if self.is_travelling(traveller) {
+            return Err(TradeError::NotAtMarket);
+        }
+        let character = get_members(&self.parties, traveller)
+            .first()
+            .map(|&id| &self.characters[usize::from(id)])
+            .ok_or(TradeError::UnknownTrader)?;
+        match character.coor {
+            CompleteCoor::OnPlanetRegion(..) => Ok((character.coor, character.dynasty_id)),
+            _ => Err(TradeError::NotAtMarket),
+        }
+    }
+
+    fn get_local_market_mut(&mut self, coor: CompleteCoor) -> &mut LocalMarket {
+        let CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id) = coor else {
+            unreachable!("traders stand in a region");
+        };
+        self.universes.get_universes_mut()[usize::from(universe_id)].get_planets_mut()
+            [usize::from(planet_id)]
+        .get_region_mut(region_id)
+        .get_local_market_mut()
+    }
+
+    /**
+    Buys goods at the local market where the character or party stands, paid for by their dynasty
+    and loaded into their cargo. Returns what it cost.
+    */
+    pub fn buy(
+        &mut self,
+        traveller: Traveller,
+        commodity: Commodity,
+        quantity: Quantity,
+    ) -> Result<Money, TradeError> {
+        if quantity == 0 {
+            return Err(TradeError::InvalidQuantity);
+        }
+        let (coor, dynasty_id) = self.find_trader(traveller)?;
+        let carried = self
+            .cargoes
+            .get(&traveller)
+            .map(Storage::get_total)
+            .unwrap_or_default();
+        if carried + quantity > self.get_cargo_capacity(traveller) {
+            return Err(TradeError::CargoFull);
+        }
+        let account = Account::Dynasty(dynasty_id);
+        let cost = self
+            .get_local_market_mut(coor)
+            .quote_buy(commodity, quantity)
+            .ok_or(TradeError::NotEnoughStock)?;
+        if cost > self.ledger.get_balance(account) {
+            return Err(TradeError::InsufficientFunds);
+        }
+
+        self.get_local_market_mut(coor).buy(commodity, quantity);
+        if cost > 0 {
+            self.ledger
+                .transfer(
+                    account,
+                    Account::Outside(MoneyFlow::Merchants),
+                    cost,
+                    TransferReason::Trade(commodity, quantity),
+                    self.tick,
+                )
+                .expect("funds were checked");
+        }
+        self.cargoes
+            .entry(traveller)
+            .or_default()
+            .add(commodity, quantity);
+        self.generation += 1;
+        Ok(cost)
+    }
+
+    /// Sells goods out of the cargo at the local market, and returns what the dynasty got for them.
+    pub fn sell(
+        &mut self,
+        traveller: Traveller,
+        commodity: Commodity,
+        quantity: Quantity,
+    ) -> Result<Money, TradeError> {
+        if quantity == 0 {
+            return Err(TradeError::InvalidQuantity);
+        }
+        let (coor, dynasty_id) = self.find_trader(traveller)?;
+        let cargo = self
+            .cargoes
+            .get_mut(&traveller)
+            .ok_or(TradeError::NotEnoughCargo)?;
+        if cargo.get(commodity) < quantity {
+            return Err(TradeError::NotEnoughCargo);
+        }
+
+        cargo.take(commodity, quantity);
+        let revenue = self.get_local_market_mut(coor).sell(commodity, quantity);
+        if revenue > 0 {
+            self.ledger
+                .transfer(
+                    Account::Outside(MoneyFlow::Merchants),
+                    Account::Dynasty(dynasty_id),
+                    revenue,
+                    TransferReason::Trade(commodity, quantity),
+                    self.tick,
+                )
+                .expect("merchants can always pay");
+        }
+        self.generation += 1;
+        Ok(revenue)
+    }
+
+    pub fn get_portals(&self) -> &Portals {
+        &self.portals
+    }
+
+    /**
+    Launches goods from a spaceport to the orbit of another planet, and returns when they get
+    there. Merchants launch out of the local market & burn its fuel, while companies launch out of
+    their warehouses at the spaceport and buy the fuel at the local market.
+    */
+    pub fn ship_freight(
+        &mut self,
+        shipper: &Ownership,
+        origin: Spaceport,
+        destination_planet_id: PlanetId,
+        commodity: Commodity,
+        quantity: Quantity,
+    ) -> Result<Time, FreightError> {
+        let universe = &self.universes.get_universes()[usize::from(origin.universe_id)];
+        let region =
+            &universe.get_planet(origin.planet_id).get_regions()[usize::from(origin.region_id)];
+        let storage = region.get_local_market().get_storage();
+        let fuel = region.get_specialty().get_launch_fuel(quantity);
+        let fuel_cost = match *shipper {
+            Ownership::Company(company_id) => {
+                if region.get_stored(shipper, commodity) < quantity {
+                    return Err(FreightError::NotEnoughGoods);
+                }
+                let fuel_cost = region
+                    .get_local_market()
+                    .quote_buy(Commodity::Fuel, fuel)
+                    .ok_or(FreightError::NotEnoughFuel)?;
+                if fuel_cost > self.ledger.get_balance(Account::Company(company_id)) {
+                    return Err(FreightError::InsufficientFunds);
+                }
+                Some((company_id, fuel_cost))
+            }
+            Ownership::Independent => {
+                if storage.get(commodity) < quantity {
+                    return Err(FreightError::NotEnoughGoods);
+                }
+                let fuel_needed = fuel
+                    + if commodity == Commodity::Fuel {
+                        quantity
+                    } else {
+                        0
+                    };
+                if storage.get(Commodity::Fuel) < fuel_needed {
+                    return Err(FreightError::NotEnoughFuel);
+                }
+                None
+            }
+        };
+
+        let arrival = self
+            .travels
+            .send_freight(
+                universe,
+                origin,
+                destination_planet_id,
+                commodity,
+                quantity,
+                self.tick,
+            )?
+            .get_arrival();
+        let region = self.universes.get_universes_mut()[usize::from(origin.universe_id)]
+            .get_planets_mut()[usize::from(origin.planet_id)]
+        .get_region_mut(origin.region_id);
+        match fuel_cost {
+            Some((company_id, fuel_cost)) => {
+                region.take_stored(shipper, commodity, quantity);
+                region.get_local_market_mut().buy(Commodity::Fuel, fuel);
+                if fuel_cost > 0 {
+                    self.ledger
+                        .transfer(
+                            Account::Company(company_id),
+                            Account::Outside(MoneyFlow::Merchants),
+                            fuel_cost,
+                            TransferReason::Trade(Commodity::Fuel, fuel),
+                            self.tick,
+                        )
+                        .expect("funds were checked");
+                }
+            }
+            None => {
+                let storage = region.get_local_market_mut().get_storage_mut();
+                storage.take(commodity, quantity);
+                storage.take(Commodity::Fuel, fuel);
+            }
+        }
+        self.generation += 1;
+        Ok(arrival)
+    }
+
+    /**
+    Puts the freights that have arrived into the orbital stations, and lands what the spaceports
+    can take at their local markets. Every freight period, merchants launch goods to the planets
+    where they sell for more than they cost plus the fuel.
+    */
+    fn progress_freights(&mut self) {
+        let time = self.tick;
+        for freight in self.travels.progress_freights(time) {
+            let origin = freight.get_origin();
+            self.universes.get_universes_mut()[usize::from(origin.universe_id)].get_planets_mut()
+                [usize::from(freight.get_destination_planet_id())]
+            .get_station_mut()
+            .add(freight.get_commodity(), freight.get_quantity());
+        }
+
+        let mut landings = Vec::new();
+        for (universe_index, universe) in self.universes.get_universes().iter().enumerate() {
+            for (planet_index, planet) in universe.get_planets().iter().enumerate() {
+                let mut station = planet.get_station().clone();
+                for region_id in planet.get_spaceports() {
+                    let spaceport = Spaceport {
+                        universe_id: UniverseId::new_unsafe(universe_index),
+                        planet_id: PlanetId::new_unsafe(planet_index),
+                        region_id,
+                    };
+                    let waiting: Vec<_> = station.iter().collect();
+                    for (commodity, quantity) in waiting {
+                        let landed = self.travels.use_throughput(universe, spaceport, quantity);
+                        if landed > 0 {
+                            station.take(commodity, landed);
+                            landings.push((spaceport, commodity, landed));
+                        }
+                    }
+                }
+            }
+        }
+        for (spaceport, commodity, quantity) in landings {
+            let planet = &mut self.universes.get_universes_mut()
+                [usize::from(spaceport.universe_id)]
+            .get_planets_mut()[usize::from(spaceport.planet_id)];
+            planet.get_station_mut().take(commodity, quantity);
+            planet
+                .get_region_mut(spaceport.region_id)
+                .get_local_market_mut()
+                .deliver(commodity, quantity);
+        }
+
+        if time.is_multiple_of(FREIGHT_PERIOD) {
+            self.plan_freights();
+        }
+    }
+
+    /// Merchants launch goods from the first spaceport of every planet to the others, where it pays.
+    fn plan_freights(&mut self) {
+        let mut orders = Vec::new();
+        for (universe_index, universe) in self.universes.get_universes().iter().enumerate() {
+            let ports: Vec<_> = universe
+                .get_planets()
+                .iter()
+                .enumerate()
+                .filter_map(|(planet_index, planet)| {
+                    let region_id = *planet.get_spaceports().first()?;
+                    let region = &planet.get_regions()[usize::from(region_id)];
+                    let spaceport = Spaceport {
+                        universe_id: UniverseId::new_unsafe(universe_index),
+                        planet_id: PlanetId::new_unsafe(planet_index),
+                        region_id,
+                    };
+                    Some((spaceport, region))
+                })
+                .collect();
+
+            for &(origin, from) in &ports {
+                let market = from.get_local_market();
+                let fuel_price = market.get_price(Commodity::Fuel);
+                let throughput = self.travels.get_remaining_throughput(universe, origin);
+                for &(destination, to) in &ports {
+                    if origin.planet_id == destination.planet_id {
+                        continue;
+                    }
+                    for (commodity, stock) in market.get_storage().iter() {
+                        let quantity = stock.min(throughput);
+                        if quantity == 0 {
+                            continue;
+                        }
+                        let fuel = from.get_specialty().get_launch_fuel(quantity);
+                        let margin = (to.get_local_market().get_price(commodity)
+                            - market.get_price(commodity))
+                            * quantity as f64
+                            - fuel_price * fuel as f64;
+                        if margin > 0. {
+                            orders.push((
+                                margin,
+                                origin,
+                                destination.planet_id,
+                                commodity,
+                                quantity,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        // the best deals go first, until the spaceports are full
+        orders.sort_by(|a, b| b.0.total_cmp(&a.0));
+        for (_, origin, destination_planet_id, commodity, quantity) in orders {
+            let universe = &self.universes.get_universes()[usize::from(origin.universe_id)];
+            let quantity = quantity.min(self.travels.get_remaining_throughput(universe, origin));
+            if quantity == 0 {
+                continue;
+            }
+            let shipped = self.ship_freight(
+                &Ownership::Independent,
+                origin,
+                destination_planet_id,
+                commodity,
+                quantity,
+            );
+            // the launches before may have used up the goods or the fuel
+            match shipped {
+                Ok(_) | Err(FreightError::NotEnoughGoods | FreightError::NotEnoughFuel) => {}
+                Err(error) => panic!("merchants cannot launch their freight: {:?}", error),
+            }
+        }
+    }
+
+    /// Moves everyone who is on a voyage, and drops off those who have arrived.
+    fn progress_travels(&mut self) {
+        let time = self.tick;
+        let arrived = self.travels.progress(time);
+
+        for voyage in self.travels.iter_voyages().chain(arrived.iter()) {
+            let universe =
+                &self.universes.get_universes()[usize::from(voyage.get_origin().universe_id)];
+            let coor = voyage.get_coor(universe, time);
+            for character_id in get_members(&self.parties, voyage.get_traveller()) {
+                self.characters[usize::from(character_id)].coor = coor;
+            }
+        }
+    }
+
+    /// Sends a character or a party from the spaceport they are at to another planet in the same universe.
+    pub fn travel(
+        &mut self,
+        traveller: Traveller,
+        destination_planet_id: PlanetId,
+    ) -> Result<Time, TravelError> {
+        if self.is_travelling(traveller) {
+            return Err(TravelError::AlreadyTravelling);
+        }
+
+        let members = get_members(&self.parties, traveller);
+        let origin = match members
+            .first()
+            .map(|&id| self.characters[usize::from(id)].coor)
+        {
+            Some(CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id)) => Spaceport {
+                universe_id,
+                planet_id,
+                region_id,
+            },
+            Some(_) => return Err(TravelError::NotAtSpaceport),
+            None => return Err(TravelError::UnknownTraveller),
+        };
+
+        let universe = &self.universes.get_universes()[usize::from(origin.universe_id)];
+        let voyage = self.travels.launch(
+            universe,
+            traveller,
+            members.len() as u32,
+            origin,
+            destination_planet_id,
+            self.tick,
+        )?;
+
+        self.generation += 1;
+        Ok(voyage.get_arrival())
     }
 
     pub fn get_universes(&self) -> &[Universe] {
@@ -169,3 +1017,365 @@ impl Game {
         self.tick
     }
 }
+
+/// The characters of the traveller, the leader of a party first.
+fn get_members(parties: &Parties, traveller: Traveller) -> Vec<CharacterId> {
+    match traveller {
+        Traveller::Character(character_id) => vec![character_id],
+        Traveller::Party(party_id) => parties
+            .get(party_id)
+            .map(|party| {
+                let leader = party.get_leader();
+                let others = party.get_characters().iter().copied();
+                std::iter::once(leader)
+                    .chain(others.filter(|&id| id != leader))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        charge_storage, collect_retail_revenue, Game, CARGO_PER_MEMBER, FREIGHT_PERIOD,
+        SALES_TAX_RATE,
+    };
+    use crate::{
+        character::CharacterId,
+        economy::{
+            Account, Commodity, CompanyId, Ledger, MarketConfig, Money, MoneyFlow, Ownership,
+            Quantity, TradeError, TransferReason,
+        },
+        planet::{
+            layout::LayoutKind,
+            test_util::{add_village_farm, planet_with_town},
+            Planet, PlanetConfig, PlanetId, RegionId, Retailer, Warehouse, Wrapping,
+            HARVEST_PERIOD,
+        },
+        portal::{Portal, PortalAccess, PortalEnd},
+        travel::{FreightError, Spaceport, TravelError, Traveller},
+        unit::PartyError,
+        universe::UniverseId,
+        CompleteCoor, WorldConfig,
+    };
+
+    /// Two small planets, plus one in a parallel universe.
+    fn small_game() -> Game {
+        let planet = || PlanetConfig {
+            layout: LayoutKind::SquareGrid,
+            wrapping: Wrapping::None,
+            model_width: Some(20.),
+        };
+        Game::create(&WorldConfig {
+            planets: vec![planet(), planet()],
+            parallel_universes: vec![vec![planet()]],
+            market: Default::default(),
+        })
+    }
+
+    /// Puts the player at the first spaceport of the home planet.
+    fn stand_at_spaceport(game: &mut Game) -> PortalEnd {
+        let (universe_id, planet_id) = (UniverseId::new_unsafe(0), PlanetId::new_unsafe(0));
+        let region_id = game
+            .get_universe(universe_id)
+            .get_planet(planet_id)
+            .get_spaceports()[0];
+        let player = game.get_player_character_id();
+        game.characters[usize::from(player)].coor =
+            CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id);
+        PortalEnd {
+            universe_id,
+            planet_id,
+            region_id,
+        }
+    }
+
+    #[test]
+    fn travellers_cannot_trade() {
+        let mut game = small_game();
+        let traveller = Traveller::Character(game.get_player_character_id());
+        stand_at_spaceport(&mut game);
+        assert_ne!(
+            game.buy(traveller, Commodity::Crop, 1),
+            Err(TradeError::NotAtMarket)
+        );
+        game.travel(traveller, PlanetId::new_unsafe(1)).unwrap();
+        assert_eq!(
+            game.buy(traveller, Commodity::Crop, 1),
+            Err(TradeError::NotAtMarket)
+        );
+
+        let mut game = small_game();
+        let entrance = stand_at_spaceport(&mut game);
+        let exit = PortalEnd {
+            universe_id: UniverseId::new_unsafe(1),
+            ..entrance
+        };
+        let portal = Portal::new(
+            "Gate".into(),
+            [entrance, exit],
+            PortalAccess::Anyone,
+            false,
+            1,
+            10,
+        );
+        let portal_id = game.portals.create(portal);
+        game.traverse_portal(traveller, portal_id).unwrap();
+        assert_eq!(
+            game.sell(traveller, Commodity::Crop, 1),
+            Err(TradeError::NotAtMarket)
+        );
+    }
+
+    #[test]
+    fn unpaid_warehouses_are_closed() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let (paying, broke) = (CompanyId::new_unsafe(0), CompanyId::new_unsafe(1));
+        let region_id = RegionId::new_unsafe(4);
+        let region = planet.get_region_mut(region_id);
+        for company_id in [broke, paying] {
+            let ownership = Ownership::Company(company_id);
+            // of ordinary quality
+            region.add_warehouse(Warehouse::new(ownership.clone(), 1, 100));
+            region.deliver(&ownership, Commodity::Tool, 20);
+        }
+
+        let mut ledger = Ledger::default();
+        ledger
+            .transfer(
+                Account::Outside(MoneyFlow::Mint),
+                Account::Company(paying),
+                100_000,
+                TransferReason::Issuance,
+                0,
+            )
+            .unwrap();
+        charge_storage(&mut ledger, &mut planet, 24);
+
+        let region = &planet.get_regions()[usize::from(region_id)];
+        assert_eq!(region.get_warehouses().len(), 1);
+        assert_eq!(
+            region.get_warehouses()[0].get_ownership(),
+            &Ownership::Company(paying)
+        );
+        let market = region.get_local_market().get_storage();
+        assert_eq!(market.get(Commodity::Tool), 20);
+        assert!(ledger.get_balance(Account::Company(paying)) < 100_000);
+    }
+
+    #[test]
+    fn retailers_take_their_sales() {
+        let mut planet = planet_with_town();
+        let config = MarketConfig::default();
+        let acme = CompanyId::new_unsafe(0);
+        let region_id = RegionId::new_unsafe(4);
+        let region = planet.get_region_mut(region_id);
+        for ownership in [Ownership::Company(acme), Ownership::Independent] {
+            let mut retailer = Retailer::new(ownership, None, 10);
+            retailer.stock(Commodity::Crop, 100, 100, 0);
+            retailer.set_price(Commodity::Crop, config.get_intrinsic_price(Commodity::Crop));
+            region.add_retailer(retailer);
+        }
+        assert!(planet.progress_markets(0, &config));
+
+        let mut ledger = Ledger::default();
+        let (universe_id, planet_id) = (UniverseId::new_unsafe(0), PlanetId::new_unsafe(0));
+        collect_retail_revenue(&mut ledger, universe_id, planet_id, &planet, 0);
+        let retailer = Account::Retailer(universe_id, planet_id, region_id, 1);
+        let government = Account::Government(universe_id, planet_id);
+        for account in [Account::Company(acme), retailer, government] {
+            assert!(ledger.get_balance(account) > 0);
+        }
+        let revenue = planet.get_regions()[4].get_retailers()[1]
+            .get_storage(Commodity::Crop)
+            .get_revenue();
+        let tax = (revenue as f64 * SALES_TAX_RATE).round() as Money;
+        assert_eq!(ledger.get_balance(retailer), revenue - tax);
+        ledger.verify().unwrap();
+    }
+
+    fn get_home_planet_mut(game: &mut Game) -> &mut Planet {
+        &mut game.universes.get_universes_mut()[0].get_planets_mut()[0]
+    }
+
+    #[test]
+    fn farms_hire_and_harvest() {
+        let mut game = small_game();
+        let acme = CompanyId::new_unsafe(0);
+        let ownership = Ownership::Company(acme);
+        let planet = get_home_planet_mut(&mut game);
+        let spaceports = planet.get_spaceports();
+        let region_id = planet
+            .get_land_indices()
+            .into_iter()
+            .filter(|region_id| !spaceports.contains(region_id))
+            .max_by_key(|&region_id| {
+                planet.get_regions()[usize::from(region_id)].get_land_capacity()
+            })
+            .unwrap();
+        let region = planet.get_region_mut(region_id);
+        add_village_farm(region, ownership.clone());
+        region.add_warehouse(Warehouse::new(ownership.clone(), 1, 100));
+        game.ledger
+            .transfer(
+                Account::Outside(MoneyFlow::Mint),
+                Account::Company(acme),
+                1_000_000,
+                TransferReason::Issuance,
+                0,
+            )
+            .unwrap();
+
+        game.tick = HARVEST_PERIOD - 1;
+        game.progress();
+        let region = &get_home_planet_mut(&mut game).get_regions()[usize::from(region_id)];
+        assert!(region.get_farm(&ownership).unwrap().get_employed() > 0);
+        assert!(region.get_stored(&ownership, Commodity::Crop) > 0);
+        assert!(game.ledger.get_balance(Account::Company(acme)) < 1_000_000);
+    }
+
+    #[test]
+    fn companies_launch_out_of_their_warehouses() {
+        let mut game = small_game();
+        let acme = CompanyId::new_unsafe(0);
+        let ownership = Ownership::Company(acme);
+        let origin = Spaceport {
+            universe_id: UniverseId::new_unsafe(0),
+            planet_id: PlanetId::new_unsafe(0),
+            region_id: get_home_planet_mut(&mut game).get_spaceports()[0],
+        };
+        let region = get_home_planet_mut(&mut game).get_region_mut(origin.region_id);
+        region.add_warehouse(Warehouse::new(ownership.clone(), 1, 100));
+        region.deliver(&ownership, Commodity::Tool, 10);
+        region.get_local_market_mut().deliver(Commodity::Fuel, 100);
+        let destination = PlanetId::new_unsafe(1);
+
+        assert_eq!(
+            game.ship_freight(&ownership, origin, destination, Commodity::Tool, 20),
+            Err(FreightError::NotEnoughGoods)
+        );
+        assert_eq!(
+            game.ship_freight(&ownership, origin, destination, Commodity::Tool, 10),
+            Err(FreightError::InsufficientFunds)
+        );
+
+        game.ledger
+            .transfer(
+                Account::Outside(MoneyFlow::Mint),
+                Account::Company(acme),
+                100_000,
+                TransferReason::Issuance,
+                0,
+            )
+            .unwrap();
+        game.ship_freight(&ownership, origin, destination, Commodity::Tool, 10)
+            .unwrap();
+        let region = get_home_planet_mut(&mut game).get_region_mut(origin.region_id);
+        assert_eq!(region.get_stored(&ownership, Commodity::Tool), 0);
+        assert!(game.ledger.get_balance(Account::Company(acme)) < 100_000);
+        assert!(matches!(
+            game.ledger.get_transactions().last().unwrap().get_reason(),
+            TransferReason::Trade(Commodity::Fuel, _)
+        ));
+    }
+
+    #[test]
+    fn goods_land_once_over_several_spaceports() {
+        let mut game = small_game();
+        game.tick = FREIGHT_PERIOD + 1;
+        let planet = get_home_planet_mut(&mut game);
+        let spaceports = planet.get_spaceports();
+        assert_eq!(spaceports.len(), 2);
+        let landed = |planet: &Planet| -> Quantity {
+            spaceports
+                .iter()
+                .map(|&region_id| {
+                    planet.get_regions()[usize::from(region_id)]
+                        .get_local_market()
+                        .get_storage()
+                        .get(Commodity::Tool)
+                })
+                .sum()
+        };
+        let before = landed(planet);
+        planet.get_station_mut().add(Commodity::Tool, 10_000);
+
+        game.progress_freights();
+        let planet = get_home_planet_mut(&mut game);
+        let waiting = planet.get_station().get(Commodity::Tool);
+        assert!(waiting > 0);
+        assert_eq!(landed(planet) - before + waiting, 10_000);
+    }
+
+    /// Puts the characters at the first spaceport of the home planet.
+    fn gather_at_spaceport(game: &mut Game, characters: &[CharacterId]) -> PortalEnd {
+        let spaceport = stand_at_spaceport(game);
+        for &character_id in characters {
+            game.characters[usize::from(character_id)].coor = CompleteCoor::OnPlanetRegion(
+                spaceport.universe_id,
+                spaceport.planet_id,
+                spaceport.region_id,
+            );
+        }
+        spaceport
+    }
+
+    #[test]
+    fn parties_carry_more() {
+        let mut game = small_game();
+        let characters: Vec<_> = (0..3).map(CharacterId::new_unsafe).collect();
+        gather_at_spaceport(&mut game, &characters);
+        let party_id = game.form_party(&characters).unwrap();
+        assert_eq!(
+            game.get_cargo_capacity(Traveller::Party(party_id)),
+            CARGO_PER_MEMBER * 3
+        );
+    }
+
+    #[test]
+    fn parties_form_in_one_place() {
+        let mut game = small_game();
+        let characters: Vec<_> = (0..4).map(CharacterId::new_unsafe).collect();
+        let spaceport = gather_at_spaceport(&mut game, &characters);
+        let other_spaceport = game
+            .get_universe(spaceport.universe_id)
+            .get_planet(spaceport.planet_id)
+            .get_spaceports()[1];
+        game.characters[3].coor = CompleteCoor::OnPlanetRegion(
+            spaceport.universe_id,
+            spaceport.planet_id,
+            other_spaceport,
+        );
+
+        assert_eq!(game.form_party(&[]), Err(PartyError::NoMembers));
+        assert_eq!(
+            game.form_party(&characters[2..]),
+            Err(PartyError::NotTogether)
+        );
+        let party_id = game.form_party(&characters[..2]).unwrap();
+        assert_eq!(
+            game.parties.get(party_id).unwrap().get_leader(),
+            characters[0]
+        );
+        assert_eq!(
+            game.form_party(&characters[1..3]),
+            Err(PartyError::AlreadyInParty)
+        );
+
+        // the party waits for all of its members
+        let planet_id = PlanetId::new_unsafe(1);
+        game.travel(Traveller::Character(characters[1]), planet_id)
+            .unwrap();
+        assert_eq!(
+            game.travel(Traveller::Party(party_id), planet_id),
+            Err(TravelError::AlreadyTravelling)
+        );
+        game.travel(Traveller::Character(characters[2]), planet_id)
+            .unwrap();
+        assert_eq!(
+            game.form_party(&characters[2..3]),
+            Err(PartyError::AlreadyTravelling)
+        );
+    }
+}
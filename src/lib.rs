@@ -1,8 +1,14 @@
+use std::collections::HashMap;
+
 use character::Character;
 use dynasty::Dynasty;
+use economy::{Companies, Ledger, MarketConfig};
 use entity::Succ;
 use log::Level;
-use planet::{PlanetId, RegionId};
+use movement::{MovementEvent, Movements};
+use planet::{PlanetConfig, PlanetId, RegionId, RouteCache, Storage};
+use portal::Portals;
+use travel::{Traveller, Travels};
 use unit::Parties;
 use universe::{UniverseId, Universes};
 use wasm_bindgen::prelude::*;
@@ -16,9 +22,13 @@ mod entity;
 mod faction;
 mod game;
 mod id;
+mod movement;
+mod naming;
 mod planet;
 mod pop;
+mod portal;
 mod terrain;
+mod travel;
 mod unit;
 mod universe;
 pub mod util;
@@ -43,12 +53,28 @@ pub enum CompleteCoor {
     InSpace(UniverseId, Coor),
 }
 
+/// Settings for generating a new game.
+pub struct WorldConfig {
+    pub planets: Vec<PlanetConfig>, // planets of the base universe; the first one is the home planet
+    pub parallel_universes: Vec<Vec<PlanetConfig>>, // planets of each universe reachable through portals
+    pub market: MarketConfig,
+}
+
 pub struct Game {
     tick: u64,
     characters: Vec<Character>,
     universes: Universes,
     dynasties: Vec<Dynasty>,
     parties: Parties,
+    travels: Travels,
+    portals: Portals,
+    movements: Movements,
+    movement_events: Vec<MovementEvent>, // of the last tick
+    route_caches: HashMap<(UniverseId, PlanetId), RouteCache>,
+    market_config: MarketConfig,
+    ledger: Ledger,
+    companies: Companies,
+    cargoes: HashMap<Traveller, Storage>, // goods carried by characters & parties
     player_character_id: usize,
 
     // gets incremented whenever side-effects to the object happen
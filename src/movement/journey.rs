@@ -0,0 +1,277 @@
+use super::{Movement, MovementEvent, Vehicle};
+use crate::{
+    planet::{Mobility, Planet, PlanetId, RegionId, RoadKind, Route},
+    travel::Traveller,
+    universe::UniverseId,
+    CompleteCoor,
+};
+
+impl Vehicle {
+    /// Multiplier of the walking speed, depending on the road being travelled on.
+    pub fn get_speed(&self, road: Option<RoadKind>) -> f64 {
+        match (self, road) {
+            (Vehicle::OnFoot, _) => 1.,
+            (Vehicle::Horse, _) => 2.,
+            (Vehicle::Car, Some(RoadKind::Trail) | None) => 0.5,
+            (Vehicle::Car, Some(_)) => 3.,
+            (Vehicle::Sailboat, _) => 3.,
+            (Vehicle::Steamship, _) => 5.,
+        }
+    }
+
+    /// Multiplier of the walking speed on the best roads.
+    pub fn get_max_speed(&self) -> f64 {
+        self.get_speed(Some(RoadKind::Rail))
+    }
+
+    pub fn get_mobility(&self) -> Mobility {
+        match self {
+            Vehicle::OnFoot | Vehicle::Horse | Vehicle::Car => Mobility::Foot,
+            Vehicle::Sailboat => Mobility::CoastalShip,
+            Vehicle::Steamship => Mobility::OceanShip,
+        }
+    }
+
+    /// Fastest route for the vehicle, at the speeds it goes on the roads along the way.
+    pub fn find_route(&self, planet: &Planet, from: RegionId, to: RegionId) -> Option<Route> {
+        planet.find_route_with(
+            self.get_mobility(),
+            from,
+            to,
+            Some(planet.get_road_bonus()),
+            self.get_max_speed(),
+            |a, b| self.get_speed(planet.get_road(a, b).map(|road| road.get_kind())),
+        )
+    }
+}
+
+impl Movement {
+    pub(super) fn new(
+        traveller: Traveller,
+        universe_id: UniverseId,
+        planet_id: PlanetId,
+        vehicle: Vehicle,
+        route: Vec<RegionId>,
+    ) -> Self {
+        Self {
+            traveller,
+            universe_id,
+            planet_id,
+            vehicle,
+            route,
+            leg: 0,
+            progress: 0.,
+        }
+    }
+
+    pub fn get_traveller(&self) -> Traveller {
+        self.traveller
+    }
+
+    pub fn get_location(&self) -> (UniverseId, PlanetId) {
+        (self.universe_id, self.planet_id)
+    }
+
+    pub fn get_destination(&self) -> RegionId {
+        *self
+            .route
+            .last()
+            .expect("routes should have at least 1 region")
+    }
+
+    /// Ticks the current leg takes from start to end, or None if it can't be walked.
+    fn get_leg_time(&self, planet: &Planet) -> Option<f64> {
+        let (from, to) = (self.route[self.leg], self.route[self.leg + 1]);
+        let step_time = planet.get_step_time_by(
+            self.vehicle.get_mobility(),
+            from,
+            to,
+            Some(planet.get_road_bonus()),
+        )?;
+        let road = planet.get_road(from, to).map(|road| road.get_kind());
+        Some(step_time / self.vehicle.get_speed(road))
+    }
+
+    /// Moves along the route for a tick. Returns an event when the movement is over.
+    pub(super) fn progress(&mut self, planet: &Planet) -> Option<MovementEvent> {
+        let mut time_left = 1.;
+
+        loop {
+            if self.leg + 1 == self.route.len() {
+                return Some(MovementEvent::Arrived(
+                    self.traveller,
+                    self.get_destination(),
+                ));
+            }
+
+            let leg_time = match self.get_leg_time(planet) {
+                Some(leg_time) => leg_time,
+                None => {
+                    // turn back to the last region reached
+                    self.progress = 0.;
+                    self.route.truncate(self.leg + 1);
+                    return Some(MovementEvent::Blocked(self.traveller, self.route[self.leg]));
+                }
+            };
+
+            let time_to_next = (1. - self.progress) * leg_time;
+            if time_to_next > time_left {
+                self.progress += time_left / leg_time;
+                return None;
+            }
+
+            time_left -= time_to_next;
+            self.leg += 1;
+            self.progress = 0.;
+        }
+    }
+
+    /// On a region when between legs, otherwise somewhere on the way to the next region.
+    pub fn get_coor(&self, planet: &Planet) -> CompleteCoor {
+        let from = self.route[self.leg];
+        if self.progress == 0. {
+            return CompleteCoor::OnPlanetRegion(self.universe_id, self.planet_id, from);
+        }
+
+        let to = self.route[self.leg + 1];
+        CompleteCoor::OnPlanetRoad(
+            self.universe_id,
+            self.planet_id,
+            planet.get_road_coor(from, to, self.progress),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Movement, MovementEvent, Vehicle};
+    use crate::{
+        character::CharacterId,
+        planet::{test_util::ids, Planet, PlanetId, RegionId, Route, Wrapping},
+        terrain::Terrain,
+        travel::Traveller,
+        universe::UniverseId,
+        CompleteCoor,
+    };
+
+    fn traveller() -> Traveller {
+        Traveller::Character(CharacterId::new_unsafe(0))
+    }
+
+    fn start(planet: &Planet, vehicle: Vehicle, to: usize) -> Movement {
+        let route = planet
+            .find_route(
+                RegionId::new_unsafe(0),
+                RegionId::new_unsafe(to),
+                Some(planet.get_road_bonus()),
+            )
+            .unwrap();
+        Movement::new(
+            traveller(),
+            UniverseId::new_unsafe(0),
+            PlanetId::new_unsafe(0),
+            vehicle,
+            route.get_regions().to_vec(),
+        )
+    }
+
+    /// Ticks until the movement ends, with the event.
+    fn run(planet: &Planet, movement: &mut Movement) -> (u64, MovementEvent) {
+        (1..)
+            .find_map(|tick| movement.progress(planet).map(|event| (tick, event)))
+            .unwrap()
+    }
+
+    #[test]
+    fn walks_to_the_destination() {
+        let planet = Planet::new_test_map(&["...", "hhh", "..."], Wrapping::None);
+        let expected = planet
+            .find_route(
+                RegionId::new_unsafe(0),
+                RegionId::new_unsafe(2),
+                Some(planet.get_road_bonus()),
+            )
+            .unwrap()
+            .get_travel_time();
+        let mut movement = start(&planet, Vehicle::OnFoot, 2);
+
+        movement.progress(&planet);
+        assert!(matches!(
+            movement.get_coor(&planet),
+            CompleteCoor::OnPlanetRoad(..)
+        ));
+
+        let (ticks, event) = run(&planet, &mut movement);
+        assert_eq!(
+            event,
+            MovementEvent::Arrived(traveller(), RegionId::new_unsafe(2))
+        );
+        assert_eq!(ticks + 1, expected);
+    }
+
+    #[test]
+    fn blocked_by_terrain_change() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let mut movement = start(&planet, Vehicle::OnFoot, 2);
+        assert_eq!(movement.route, ids(&[0, 1, 2]));
+
+        movement.progress(&planet);
+        planet.set_terrain(RegionId::new_unsafe(1), Terrain::DeepOcean);
+
+        let (_, event) = run(&planet, &mut movement);
+        assert_eq!(
+            event,
+            MovementEvent::Blocked(traveller(), RegionId::new_unsafe(0))
+        );
+        assert!(matches!(
+            movement.get_coor(&planet),
+            CompleteCoor::OnPlanetRegion(_, _, region_id) if region_id == RegionId::new_unsafe(0)
+        ));
+    }
+
+    #[test]
+    fn vehicles_and_roads() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let ticks = |planet: &Planet, vehicle| run(planet, &mut start(planet, vehicle, 2)).0;
+
+        let walking = ticks(&planet, Vehicle::OnFoot);
+        assert!(ticks(&planet, Vehicle::Horse) < walking);
+        assert!(ticks(&planet, Vehicle::Car) > walking);
+
+        for (a, b) in [(0, 1), (1, 2)] {
+            let (a, b) = (RegionId::new_unsafe(a), RegionId::new_unsafe(b));
+            planet.build_road(a, b).unwrap();
+            planet.upgrade_road(a, b).unwrap();
+        }
+        assert!(ticks(&planet, Vehicle::Car) < ticks(&planet, Vehicle::Horse));
+    }
+
+    #[test]
+    fn cars_keep_to_good_roads() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let (from, to) = (RegionId::new_unsafe(0), RegionId::new_unsafe(2));
+        // trails straight across, and a paved detour
+        planet.build_road_along(&ids(&[0, 1, 2])).unwrap();
+        planet.build_road_along(&ids(&[0, 3, 4, 5, 2])).unwrap();
+        for pair in ids(&[0, 3, 4, 5, 2]).windows(2) {
+            planet.upgrade_road(pair[0], pair[1]).unwrap();
+        }
+
+        let walk = Vehicle::OnFoot.find_route(&planet, from, to).unwrap();
+        assert_eq!(walk.get_regions(), ids(&[0, 1, 2]));
+        let drive = Vehicle::Car.find_route(&planet, from, to).unwrap();
+        assert_eq!(drive.get_regions(), ids(&[0, 3, 4, 5, 2]));
+
+        let ticks = |route: &Route| {
+            let mut movement = Movement::new(
+                traveller(),
+                UniverseId::new_unsafe(0),
+                PlanetId::new_unsafe(0),
+                Vehicle::Car,
+                route.get_regions().to_vec(),
+            );
+            run(&planet, &mut movement).0
+        };
+        assert!(ticks(&drive) < ticks(&walk));
+    }
+}
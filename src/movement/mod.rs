@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use crate::{
+    planet::{PlanetId, RegionId},
+    travel::Traveller,
+    universe::UniverseId,
+};
+
+mod journey;
+mod movements;
+
+/// What a mover travels with on a planet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Vehicle {
+    OnFoot,
+    Horse,
+    Car,       // fast on roads, but slow everywhere else
+    Sailboat,  // stays in shallow water
+    Steamship, // can cross oceans
+}
+
+/// A walk along a route between regions of a planet.
+pub struct Movement {
+    traveller: Traveller,
+    universe_id: UniverseId,
+    planet_id: PlanetId,
+    vehicle: Vehicle,
+    route: Vec<RegionId>,
+    leg: usize,    // index of the region in the route that was last left or reached
+    progress: f64, // along the leg to the next region, from 0 to 1
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MovementEvent {
+    Arrived(Traveller, RegionId),
+    Blocked(Traveller, RegionId), // the rest of the route can't be walked anymore; stopped at the region
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MovementError {
+    UnknownTraveller,
+    AlreadyTravelling,
+    NotInRegion, // e.g. in space
+    NotAtPort,   // ships have to set off from a port
+    NoRoute,
+}
+
+#[derive(Default)]
+pub struct Movements {
+    movements: HashMap<Traveller, Movement>,
+}
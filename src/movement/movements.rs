@@ -0,0 +1,55 @@
+use super::{Movement, MovementError, MovementEvent, Movements, Vehicle};
+use crate::{
+    planet::{PlanetId, Route},
+    travel::Traveller,
+    universe::{UniverseId, Universes},
+};
+
+impl Movements {
+    pub fn get_movement(&self, traveller: Traveller) -> Option<&Movement> {
+        self.movements.get(&traveller)
+    }
+
+    pub fn iter_movements(&self) -> impl Iterator<Item = &Movement> {
+        self.movements.values()
+    }
+
+    /// Sets the traveller off along a route found beforehand.
+    pub fn start(
+        &mut self,
+        traveller: Traveller,
+        (universe_id, planet_id): (UniverseId, PlanetId),
+        route: Route,
+        vehicle: Vehicle,
+    ) -> Result<&Movement, MovementError> {
+        if self.movements.contains_key(&traveller) {
+            return Err(MovementError::AlreadyTravelling);
+        }
+
+        let route = route.get_regions().to_vec();
+        let movement = Movement::new(traveller, universe_id, planet_id, vehicle, route);
+        Ok(self.movements.entry(traveller).or_insert(movement))
+    }
+
+    /// Moves everyone for a tick. Movements that are over are returned with their events.
+    pub fn progress(&mut self, universes: &Universes) -> Vec<(Movement, MovementEvent)> {
+        let ended: Vec<_> = self
+            .movements
+            .iter_mut()
+            .filter_map(|(&traveller, movement)| {
+                let planet = universes.get_universes()[usize::from(movement.universe_id)]
+                    .get_planet(movement.planet_id);
+                movement.progress(planet).map(|event| (traveller, event))
+            })
+            .collect();
+
+        ended
+            .into_iter()
+            .filter_map(|(traveller, event)| {
+                self.movements
+                    .remove(&traveller)
+                    .map(|movement| (movement, event))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,175 @@
+use super::{Culture, NameStyle};
+
+// sample names that the markov chains are trained on
+
+const TERRAN_PERSON: &[&str] = &[
+    "alfred",
+    "amelia",
+    "benedict",
+    "beatrice",
+    "cedric",
+    "clara",
+    "darius",
+    "eleanor",
+    "edmund",
+    "florence",
+    "gregory",
+    "helena",
+    "julian",
+    "lucia",
+    "marcus",
+    "matilda",
+    "oliver",
+    "rosalind",
+    "sebastian",
+    "victoria",
+];
+
+const TERRAN_SURNAME: &[&str] = &[
+    "ashford",
+    "blackwood",
+    "carrington",
+    "davenport",
+    "everett",
+    "fairfax",
+    "hartley",
+    "kingsley",
+    "lancaster",
+    "montague",
+    "northcott",
+    "pemberton",
+    "radcliffe",
+    "stanhope",
+    "thornton",
+    "whitmore",
+];
+
+const TERRAN_PLACE: &[&str] = &[
+    "albion",
+    "avalon",
+    "brittany",
+    "caledon",
+    "cornwall",
+    "essex",
+    "galicia",
+    "hibernia",
+    "lusitania",
+    "mercia",
+    "normandy",
+    "provence",
+    "tuscany",
+    "umbria",
+    "wessex",
+    "york",
+];
+
+const TERRAN_PLANET: &[&str] = &[
+    "aurora", "caelum", "ceres", "gaia", "janus", "juno", "minerva", "neptune", "pallas",
+    "proxima", "terra", "vesta", "vulcan",
+];
+
+const SYLVAN_PERSON: &[&str] = &[
+    "aelin", "arwen", "caladwen", "elaria", "elrond", "faelar", "galen", "ilyana", "lirael",
+    "lorien", "melian", "nimue", "saelis", "thalion", "vaeril", "yavanna",
+];
+
+const SYLVAN_SURNAME: &[&str] = &[
+    "amakiir",
+    "brightleaf",
+    "galanodel",
+    "holimion",
+    "ilphelkiir",
+    "liadon",
+    "meliamne",
+    "nailo",
+    "siannodel",
+    "silverfrond",
+    "windrivver",
+    "xiloscient",
+];
+
+const SYLVAN_PLACE: &[&str] = &[
+    "aelinor",
+    "caras galadon",
+    "doriath",
+    "eldamar",
+    "evermeet",
+    "lothlorien",
+    "mirkwood",
+    "nimbrethil",
+    "rivendell",
+    "silvanesti",
+    "tirion",
+    "valinor",
+];
+
+const SYLVAN_PLANET: &[&str] = &[
+    "aeloria",
+    "elenna",
+    "ithilien",
+    "lumena",
+    "menelia",
+    "nenya",
+    "silmaril",
+    "telperion",
+    "vilya",
+    "ylvenor",
+];
+
+const DWARVEN_PERSON: &[&str] = &[
+    "balin", "bardrik", "dain", "dagna", "durin", "eberk", "gimli", "gunnloda", "hlin", "kildrak",
+    "morgran", "orsik", "rurik", "thorin", "torbera", "vondal",
+];
+
+const DWARVEN_SURNAME: &[&str] = &[
+    "balderk",
+    "battlehammer",
+    "brawnanvil",
+    "dankil",
+    "fireforge",
+    "frostbeard",
+    "gorunn",
+    "holderhek",
+    "ironfist",
+    "loderr",
+    "rumnaheim",
+    "strakeln",
+    "torunn",
+    "ungart",
+];
+
+const DWARVEN_PLACE: &[&str] = &[
+    "belegost",
+    "erebor",
+    "gundabad",
+    "ironforge",
+    "karak azgal",
+    "khazad dum",
+    "mithral hall",
+    "nogrod",
+    "orzammar",
+    "thaigar",
+    "zhentarim",
+];
+
+const DWARVEN_PLANET: &[&str] = &[
+    "barazul", "dunrak", "grimholt", "khazrak", "morndur", "stonheim", "thrakor", "vordrun",
+    "zarakhul",
+];
+
+pub fn get_samples(culture: Culture, style: NameStyle) -> &'static [&'static str] {
+    match (culture, style) {
+        (Culture::Terran, NameStyle::Person) => TERRAN_PERSON,
+        (Culture::Terran, NameStyle::Surname) => TERRAN_SURNAME,
+        (Culture::Terran, NameStyle::Place) => TERRAN_PLACE,
+        (Culture::Terran, NameStyle::Planet) => TERRAN_PLANET,
+        (Culture::Sylvan, NameStyle::Person) => SYLVAN_PERSON,
+        (Culture::Sylvan, NameStyle::Surname) => SYLVAN_SURNAME,
+        (Culture::Sylvan, NameStyle::Place) => SYLVAN_PLACE,
+        (Culture::Sylvan, NameStyle::Planet) => SYLVAN_PLANET,
+        (Culture::Dwarven, NameStyle::Person) => DWARVEN_PERSON,
+        (Culture::Dwarven, NameStyle::Surname) => DWARVEN_SURNAME,
+        (Culture::Dwarven, NameStyle::Place) => DWARVEN_PLACE,
+        (Culture::Dwarven, NameStyle::Planet) => DWARVEN_PLANET,
+    }
+}
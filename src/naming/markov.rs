@@ -0,0 +1,110 @@
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+};
+
+use rand::Rng;
+
+use super::MarkovChain;
+
+// padding characters that mark the start & the end of a name
+const START: char = '^';
+const END: char = '$';
+
+const MAX_ATTEMPTS: usize = 30;
+
+impl MarkovChain {
+    pub fn new(order: usize, samples: &[&str]) -> Self {
+        assert!(
+            !samples.is_empty(),
+            "need at least 1 sample to train a chain"
+        );
+
+        let samples: Vec<String> = samples.iter().map(|sample| sample.to_lowercase()).collect();
+
+        let mut transitions: HashMap<String, Vec<char>> = HashMap::new();
+        for sample in &samples {
+            let padded: Vec<char> = vec![START; order]
+                .into_iter()
+                .chain(sample.chars())
+                .chain(iter::once(END))
+                .collect();
+
+            for window in padded.windows(order + 1) {
+                let (state, next) = window.split_at(order);
+                transitions
+                    .entry(state.iter().collect())
+                    .or_default()
+                    .push(next[0]);
+            }
+        }
+
+        let lengths = samples.iter().map(|sample| sample.chars().count());
+        let min_len = lengths.clone().min().expect("samples are not empty").max(3);
+        let max_len = lengths.max().expect("samples are not empty") + 2;
+
+        let known = samples.iter().cloned().collect::<HashSet<_>>();
+
+        Self {
+            order,
+            transitions,
+            samples,
+            known,
+            min_len,
+            max_len,
+        }
+    }
+
+    /**
+    Generates a new capitalized name. Names that are too short, too long, or are copies of the
+    samples are rejected; after too many rejections a sample name is returned instead.
+    */
+    pub fn generate(&self, rng: &mut dyn rand_core::RngCore) -> String {
+        let name = (0..MAX_ATTEMPTS)
+            .filter_map(|_| self.try_generate(rng))
+            .find(|name| {
+                let len = name.chars().count();
+                len >= self.min_len && !self.known.contains(name)
+            })
+            .unwrap_or_else(|| self.samples[rng.gen_range(0..self.samples.len())].clone());
+
+        capitalize(&name)
+    }
+
+    fn try_generate(&self, rng: &mut dyn rand_core::RngCore) -> Option<String> {
+        let mut state: Vec<char> = vec![START; self.order];
+        let mut name = String::new();
+
+        loop {
+            let key: String = state[state.len() - self.order..].iter().collect();
+            let candidates = self.transitions.get(&key)?;
+            let next = candidates[rng.gen_range(0..candidates.len())];
+
+            if next == END {
+                return Some(name);
+            }
+
+            name.push(next);
+            if name.chars().count() > self.max_len {
+                return None;
+            }
+            state.push(next);
+        }
+    }
+}
+
+/// Upper-cases the first letter of every word.
+pub fn capitalize(name: &str) -> String {
+    let mut is_word_start = true;
+    name.chars()
+        .flat_map(|c| {
+            let ret: Vec<char> = if is_word_start {
+                c.to_uppercase().collect()
+            } else {
+                vec![c]
+            };
+            is_word_start = c == ' ' || c == '-';
+            ret
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use std::collections::{HashMap, HashSet};
+
+use enum_map::{Enum, EnumMap};
+
+mod corpus;
+mod markov;
+mod name_generator;
+
+/// Name sets are grouped by culture, so people & places from the same culture sound alike.
+#[derive(Enum, Clone, Copy, Debug, PartialEq)]
+pub enum Culture {
+    Terran,  // anglo-latin
+    Sylvan,  // soft, vowel-heavy; elves & forest folks
+    Dwarven, // hard consonants; mountain folks
+}
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq)]
+pub enum NameStyle {
+    Person,  // given names
+    Surname, // dynasty names
+    Place,   // landmasses, regions, settlements
+    Planet,  // planets & universes
+}
+
+/**
+Character-level Markov chain trained on a small corpus of sample names.
+Each state is the last `order` characters of the name being built.
+*/
+pub struct MarkovChain {
+    order: usize,
+    transitions: HashMap<String, Vec<char>>,
+    samples: Vec<String>,
+    known: HashSet<String>,
+    min_len: usize,
+    max_len: usize,
+}
+
+/// Seeded name generation; all randomness comes from the caller's rng.
+pub struct NameGenerator {
+    chains: EnumMap<Culture, EnumMap<NameStyle, MarkovChain>>,
+}
@@ -0,0 +1,110 @@
+use enum_map::enum_map;
+use rand::prelude::SliceRandom;
+
+use super::{corpus::get_samples, Culture, MarkovChain, NameGenerator, NameStyle};
+
+// number of previous characters that decide the next one
+const CHAIN_ORDER: usize = 2;
+
+const CULTURES: [Culture; 3] = [Culture::Terran, Culture::Sylvan, Culture::Dwarven];
+
+impl Culture {
+    pub fn random(rng: &mut dyn rand_core::RngCore) -> Self {
+        *CULTURES
+            .choose(rng)
+            .expect("there should be at least 1 culture")
+    }
+}
+
+impl Default for NameGenerator {
+    fn default() -> Self {
+        let chains = enum_map! {
+            culture => enum_map! {
+                style => MarkovChain::new(CHAIN_ORDER, get_samples(culture, style)),
+            },
+        };
+
+        Self { chains }
+    }
+}
+
+impl NameGenerator {
+    pub fn generate(
+        &self,
+        rng: &mut dyn rand_core::RngCore,
+        culture: Culture,
+        style: NameStyle,
+    ) -> String {
+        self.chains[culture][style].generate(rng)
+    }
+
+    pub fn person(&self, rng: &mut dyn rand_core::RngCore, culture: Culture) -> String {
+        self.generate(rng, culture, NameStyle::Person)
+    }
+
+    pub fn surname(&self, rng: &mut dyn rand_core::RngCore, culture: Culture) -> String {
+        self.generate(rng, culture, NameStyle::Surname)
+    }
+
+    pub fn place(&self, rng: &mut dyn rand_core::RngCore, culture: Culture) -> String {
+        self.generate(rng, culture, NameStyle::Place)
+    }
+
+    pub fn planet(&self, rng: &mut dyn rand_core::RngCore, culture: Culture) -> String {
+        self.generate(rng, culture, NameStyle::Planet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{prelude::StdRng, SeedableRng};
+
+    use super::CULTURES;
+    use crate::naming::{markov::capitalize, NameGenerator, NameStyle};
+
+    const STYLES: [NameStyle; 4] = [
+        NameStyle::Person,
+        NameStyle::Surname,
+        NameStyle::Place,
+        NameStyle::Planet,
+    ];
+
+    #[test]
+    fn same_seed_same_names() {
+        let generator = NameGenerator::default();
+        let mut rng1 = StdRng::seed_from_u64(42);
+        let mut rng2 = StdRng::seed_from_u64(42);
+
+        for culture in CULTURES {
+            for style in STYLES {
+                assert_eq!(
+                    generator.generate(&mut rng1, culture, style),
+                    generator.generate(&mut rng2, culture, style)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn names_are_capitalized_and_not_empty() {
+        let generator = NameGenerator::default();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for culture in CULTURES {
+            for style in STYLES {
+                for _ in 0..50 {
+                    let name = generator.generate(&mut rng, culture, style);
+                    let first = name.chars().next().expect("name should not be empty");
+                    assert!(first.is_uppercase(), "not capitalized: {}", name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn capitalize_words() {
+        assert_eq!(capitalize("khazad dum"), "Khazad Dum");
+        assert_eq!(capitalize("caras-galadon"), "Caras-Galadon");
+        assert_eq!(capitalize(""), "");
+    }
+}
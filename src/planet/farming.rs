@@ -0,0 +1,285 @@
+use super::{
+    producer::farm::Farm, warehouse::with_supplies, FarmError, LandSize, Planet, Region, RegionId,
+    Zone,
+};
+use crate::{
+    economy::{Commodity, Money, Ownership, Wage},
+    pop::PopCount,
+    Time,
+};
+
+// farms harvest once per season
+pub const HARVEST_PERIOD: Time = 24 * 90;
+
+impl Region {
+    pub fn get_farm(&self, ownership: &Ownership) -> Option<&Farm> {
+        self.farms.get(ownership)
+    }
+
+    pub fn get_farm_mut(&mut self, ownership: &Ownership) -> Option<&mut Farm> {
+        self.farms.get_mut(ownership)
+    }
+
+    /// Land on the plots of the owner that is zoned for farming.
+    pub fn get_farmland(&self, ownership: &Ownership) -> LandSize {
+        self.plots
+            .iter()
+            .filter(|plot| plot.zone == Zone::Farming && &plot.owner == ownership)
+            .map(|plot| plot.size)
+            .sum()
+    }
+
+    /// Farms more of the owner's farmland, with a new farm if they don't have one here yet.
+    pub fn establish_farm(
+        &mut self,
+        ownership: Ownership,
+        land: LandSize,
+    ) -> Result<&mut Farm, FarmError> {
+        let farmed = self
+            .farms
+            .get(&ownership)
+            .map(Farm::get_owned_land)
+            .unwrap_or_default();
+        if farmed + land > self.get_farmland(&ownership) {
+            return Err(FarmError::NotEnoughFarmland);
+        }
+
+        let farm = self
+            .farms
+            .entry(ownership.clone())
+            .or_insert_with(|| Farm::new(ownership, 0, Wage::Normal));
+        farm.add_land(land);
+        Ok(farm)
+    }
+}
+
+impl Planet {
+    /**
+    Before every harvest, the farms take on the workers for the season from the people of the
+    region. When there aren't enough people around, each farm gets the same portion of what it
+    needs. Returns the wages every farm owes for the season.
+    */
+    pub fn hire_farm_workers(&mut self, time: Time) -> Vec<(RegionId, Ownership, Money)> {
+        let mut wages = Vec::new();
+        if !time.is_multiple_of(HARVEST_PERIOD) {
+            return wages;
+        }
+
+        for (index, region) in self.regions.iter_mut().enumerate() {
+            let needed: PopCount = region.farms.values().map(Farm::get_workers_needed).sum();
+            let available = region.get_spot_population();
+            let portion = if needed > available {
+                available as f64 / needed as f64
+            } else {
+                1.
+            };
+            for (ownership, farm) in &mut region.farms {
+                farm.hire((farm.get_workers_needed() as f64 * portion) as PopCount);
+                let region_id = RegionId::new_unsafe(index);
+                wages.push((region_id, ownership.clone(), farm.get_wages()));
+            }
+        }
+        wages
+    }
+
+    /// The workers of a farm that can't pay them leave for the season.
+    pub fn lay_off_farm_workers(&mut self, region_id: RegionId, ownership: &Ownership) {
+        let region = &mut self.regions[usize::from(region_id)];
+        if let Some(farm) = region.farms.get_mut(ownership) {
+            farm.hire(0);
+        }
+    }
+
+    /**
+    Every season, the farms bring their harvests to their owners' warehouses or the local market.
+    Company farms use the fertilizer & tools in the company's warehouses in the region, and the
+    others those at the local market.
+    */
+    pub fn progress_farms(&mut self, time: Time) {
+        if !time.is_multiple_of(HARVEST_PERIOD) {
+            return;
+        }
+
+        for region in &mut self.regions {
+            let crop_yield = region.get_crop_yield();
+            let mut harvests = Vec::new();
+            for (ownership, farm) in &region.farms {
+                let harvest = with_supplies(
+                    &mut region.warehouses,
+                    &mut region.local_market,
+                    ownership,
+                    |storage| farm.harvest(region.terrain, crop_yield, storage),
+                );
+                harvests.push((ownership.clone(), harvest));
+            }
+            for (ownership, (crop, meat)) in harvests {
+                region.deliver(&ownership, Commodity::Crop, crop);
+                region.deliver(&ownership, Commodity::Meat, meat);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HARVEST_PERIOD;
+    use crate::{
+        economy::{Commodity, CompanyId, Ownership, Wage},
+        planet::{
+            retailer::BASE_QUALITY,
+            test_util::{farming_planet, id},
+            FarmError, Planet, RegionSpecialty, Spot, Warehouse, Zone,
+        },
+    };
+
+    fn harvest(planet: &mut Planet, index: usize) -> (u32, u32) {
+        let before = |planet: &Planet, commodity| {
+            planet.regions[index]
+                .local_market
+                .get_storage()
+                .get(commodity)
+        };
+        let (crop, meat) = (
+            before(planet, Commodity::Crop),
+            before(planet, Commodity::Meat),
+        );
+        planet.progress_farms(HARVEST_PERIOD);
+        (
+            before(planet, Commodity::Crop) - crop,
+            before(planet, Commodity::Meat) - meat,
+        )
+    }
+
+    #[test]
+    fn farms_need_farmland() {
+        let mut planet = farming_planet();
+        let region = &mut planet.regions[3];
+        assert!(region.establish_farm(Ownership::Independent, 1).is_err());
+        assert_eq!(
+            planet.regions[0]
+                .establish_farm(Ownership::Independent, 10)
+                .err(),
+            Some(FarmError::NotEnoughFarmland)
+        );
+    }
+
+    #[test]
+    fn harvests_depend_on_terrain_workers_and_inputs() {
+        let mut planet = farming_planet();
+
+        let (plain_crop, plain_meat) = harvest(&mut planet, 3);
+        let (hill_crop, hill_meat) = harvest(&mut planet, 4);
+        assert!(plain_crop > hill_crop);
+        assert!(plain_meat < hill_meat);
+
+        // only half the workers
+        let farm = planet.regions[3]
+            .get_farm_mut(&Ownership::Independent)
+            .unwrap();
+        farm.hire(5);
+        let (half_crop, _) = harvest(&mut planet, 3);
+        assert_eq!(half_crop, plain_crop / 2);
+
+        // fertilizer is spread, and tools stay
+        planet.regions[3]
+            .get_farm_mut(&Ownership::Independent)
+            .unwrap()
+            .hire(10);
+        let storage = planet.regions[3].local_market.get_storage_mut();
+        storage.add(Commodity::Fertilizer, 100);
+        storage.add(Commodity::Tool, 100);
+        let (boosted_crop, _) = harvest(&mut planet, 3);
+        assert!(boosted_crop > plain_crop);
+        let storage = planet.regions[3].local_market.get_storage();
+        assert_eq!(storage.get(Commodity::Fertilizer), 95);
+        assert_eq!(storage.get(Commodity::Tool), 100);
+
+        // farmland grows more than the wild
+        planet.regions[3].spots = vec![Spot::Farmland {
+            population: 0,
+            owned_land: 0,
+        }];
+        let (farmland_crop, _) = harvest(&mut planet, 3);
+        assert!(farmland_crop > boosted_crop);
+
+        // nothing grows between harvests
+        let crop = planet.regions[3]
+            .local_market
+            .get_storage()
+            .get(Commodity::Crop);
+        planet.progress_farms(HARVEST_PERIOD + 1);
+        let storage = planet.regions[3].local_market.get_storage();
+        assert_eq!(storage.get(Commodity::Crop), crop);
+    }
+
+    #[test]
+    fn farms_hire_the_people_of_the_region() {
+        let mut planet = farming_planet();
+        planet.regions[3].spots = vec![Spot::Farmland {
+            population: 4,
+            owned_land: 0,
+        }];
+        planet.regions[4].spots = vec![Spot::Settlement {
+            population: 1000,
+            owned_land: 0,
+        }];
+
+        assert!(planet.hire_farm_workers(HARVEST_PERIOD + 1).is_empty());
+        let wages = planet.hire_farm_workers(HARVEST_PERIOD);
+        let employed = |planet: &Planet, index: usize| {
+            planet.regions[index]
+                .get_farm(&Ownership::Independent)
+                .unwrap()
+                .get_employed()
+        };
+        assert_eq!(employed(&planet, 3), 4);
+        assert_eq!(employed(&planet, 4), 10);
+        assert!(wages.contains(&(
+            id(4),
+            Ownership::Independent,
+            10 * Wage::Normal.get_seasonal_pay()
+        )));
+
+        // farms share the few people around
+        let acme = Ownership::Company(CompanyId::new_unsafe(0));
+        let region = &mut planet.regions[3];
+        region
+            .allocate_land(Zone::Farming, acme.clone(), 100)
+            .unwrap();
+        region.establish_farm(acme.clone(), 100).unwrap();
+        planet.hire_farm_workers(HARVEST_PERIOD);
+        assert_eq!(employed(&planet, 3), 2);
+        let acme_farm = planet.regions[3].get_farm(&acme).unwrap();
+        assert_eq!(acme_farm.get_employed(), 2);
+
+        planet.lay_off_farm_workers(id(3), &acme);
+        assert_eq!(planet.regions[3].get_farm(&acme).unwrap().get_employed(), 0);
+    }
+
+    #[test]
+    fn company_farms_use_their_warehouses() {
+        let mut planet = farming_planet();
+        let acme = Ownership::Company(CompanyId::new_unsafe(0));
+        let region = &mut planet.regions[5];
+        region.specialty = RegionSpecialty::Rural;
+        region
+            .allocate_land(Zone::Farming, acme.clone(), 100)
+            .unwrap();
+        region.establish_farm(acme.clone(), 100).unwrap().hire(10);
+        let warehouse = region.add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        region.warehouses[warehouse].store(Commodity::Fertilizer, 100);
+        region.warehouses[warehouse].store(Commodity::Tool, 100);
+        region
+            .local_market
+            .get_storage_mut()
+            .add(Commodity::Fertilizer, 100);
+
+        planet.progress_farms(HARVEST_PERIOD);
+        let region = &planet.regions[5];
+        assert_eq!(region.get_stored(&acme, Commodity::Fertilizer), 95);
+        assert_eq!(region.get_stored(&acme, Commodity::Tool), 100);
+        assert!(region.get_stored(&acme, Commodity::Crop) > 0);
+        let market = region.local_market.get_storage();
+        assert_eq!(market.get(Commodity::Fertilizer), 100);
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+
+use super::{Landmass, LandmassId, LandmassKind, Region, RegionId};
+use crate::naming::{Culture, NameGenerator};
+
+// components smaller than these portions of the planet are islands & seas
+const CONTINENT_MIN_PROPORTION: f64 = 0.05;
+const OCEAN_MIN_PROPORTION: f64 = 0.1;
+
+impl Landmass {
+    pub fn clone_name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    pub fn get_kind(&self) -> LandmassKind {
+        self.kind
+    }
+
+    pub fn get_regions(&self) -> &[RegionId] {
+        &self.regions
+    }
+
+    pub fn is_land(&self) -> bool {
+        match self.kind {
+            LandmassKind::Continent | LandmassKind::Island => true,
+            LandmassKind::Ocean | LandmassKind::Sea => false,
+        }
+    }
+}
+
+/**
+Flood-fills the region graph into connected components of land and water, and names them.
+Returns the landmasses and the landmass of each region.
+*/
+pub(super) fn detect_landmasses(
+    regions: &[Region],
+    creation_rng: &mut dyn rand_core::RngCore,
+    names: &NameGenerator,
+    culture: Culture,
+) -> (Vec<Landmass>, Vec<LandmassId>) {
+    let num_regions = regions.len();
+    let mut landmasses = Vec::new();
+    let mut region_landmasses: Vec<Option<LandmassId>> = vec![None; num_regions];
+
+    for start in 0..num_regions {
+        if region_landmasses[start].is_some() {
+            continue;
+        }
+
+        let landmass_id = LandmassId::new_unsafe(landmasses.len());
+        let is_land = regions[start].terrain.is_land();
+
+        let mut members = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        region_landmasses[start] = Some(landmass_id);
+
+        while let Some(index) = queue.pop_front() {
+            members.push(RegionId::new_unsafe(index));
+
+            for &neighbour in &regions[index].neighbours {
+                let neighbour: usize = neighbour.into();
+                if region_landmasses[neighbour].is_none()
+                    && regions[neighbour].terrain.is_land() == is_land
+                {
+                    region_landmasses[neighbour] = Some(landmass_id);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        let proportion = members.len() as f64 / num_regions as f64;
+        let kind = match (is_land, proportion) {
+            (true, p) if p >= CONTINENT_MIN_PROPORTION => LandmassKind::Continent,
+            (true, _) => LandmassKind::Island,
+            (false, p) if p >= OCEAN_MIN_PROPORTION => LandmassKind::Ocean,
+            (false, _) => LandmassKind::Sea,
+        };
+
+        landmasses.push(Landmass {
+            name: generate_name(creation_rng, names, culture, kind),
+            kind,
+            regions: members,
+        });
+    }
+
+    let region_landmasses = region_landmasses
+        .into_iter()
+        .map(|landmass_id| landmass_id.expect("every region should belong to a landmass"))
+        .collect();
+
+    (landmasses, region_landmasses)
+}
+
+fn generate_name(
+    creation_rng: &mut dyn rand_core::RngCore,
+    names: &NameGenerator,
+    culture: Culture,
+    kind: LandmassKind,
+) -> String {
+    let base = names.place(creation_rng, culture);
+
+    match kind {
+        LandmassKind::Continent => base,
+        LandmassKind::Island => format!("{} Isle", base),
+        LandmassKind::Ocean => format!("{} Ocean", base),
+        LandmassKind::Sea => format!("Sea of {}", base),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::planet::{LandmassKind, Planet, RegionId, Wrapping};
+
+    #[test]
+    fn land_and_water_are_split_into_landmasses() {
+        #[rustfmt::skip]
+        let planet = Planet::new_test_map(&[
+            ".....",
+            ".~...",
+            ".....",
+            "OOOOO",
+            "OOO.O",
+        ], Wrapping::None);
+        let landmass_of = |index: usize| planet.get_landmass_id(RegionId::new_unsafe(index));
+
+        // landmasses are numbered in the order of their first region
+        let ids: Vec<_> = [0, 6, 15, 23].into_iter().map(landmass_of).collect();
+        assert_eq!(
+            ids.iter().map(|&id| usize::from(id)).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        let kinds: Vec<_> = ids
+            .iter()
+            .map(|&id| planet.get_landmass(id).get_kind())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LandmassKind::Continent,
+                LandmassKind::Sea,
+                LandmassKind::Ocean,
+                LandmassKind::Island,
+            ]
+        );
+        assert_eq!(planet.landmasses().len(), 4);
+
+        assert_eq!(landmass_of(14), landmass_of(0));
+        assert_eq!(landmass_of(24), landmass_of(15));
+        assert_eq!(planet.get_landmass(ids[0]).get_regions().len(), 14);
+        assert_eq!(planet.get_landmass(ids[2]).get_regions().len(), 9);
+        assert!(planet.get_landmass(ids[3]).is_land());
+        assert!(!planet.get_landmass(ids[1]).is_land());
+        assert!(planet
+            .get_landmass(ids[1])
+            .clone_name()
+            .starts_with("Sea of "));
+        assert!(planet.get_landmass(ids[3]).clone_name().ends_with(" Isle"));
+    }
+}
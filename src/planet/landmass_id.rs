@@ -0,0 +1,13 @@
+use super::LandmassId;
+
+impl LandmassId {
+    pub fn new_unsafe(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl From<LandmassId> for usize {
+    fn from(id: LandmassId) -> Self {
+        id.0
+    }
+}
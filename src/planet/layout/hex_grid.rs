@@ -0,0 +1,76 @@
+use super::{
+    count_grid_cells, shift_column, CellData, HexGridLayout, RegionLayout, GRID_CELL_SIZE,
+};
+use crate::planet::{PlanetCoor, RegionId, Wrapping};
+
+impl RegionLayout for HexGridLayout {
+    /**
+    Regular hexagons whose widths add up to the map width. Odd rows stick out half a hexagon past
+    the east edge, which crosses the seam on wrapping planets. Rows are centered vertically.
+    */
+    fn build_cells(
+        &self,
+        _creation_rng: &mut dyn rand_core::RngCore,
+        model_width: f64,
+        model_height: f64,
+        wrapping: Wrapping,
+    ) -> Vec<CellData> {
+        let num_columns = count_grid_cells(model_width, GRID_CELL_SIZE);
+        let hex_width = model_width / num_columns as f64;
+        let radius = hex_width / 3f64.sqrt();
+        let row_height = radius * 1.5;
+
+        // the top & bottom rows need an extra half radius for their tips
+        let num_rows = (((model_height - radius / 2.) / row_height).floor() as usize).max(1);
+        let top_margin = (model_height - (num_rows as f64 * row_height + radius / 2.)) / 2.;
+
+        let to_id = |column: usize, row: usize| RegionId::new_unsafe(row * num_columns + column);
+
+        (0..num_rows)
+            .flat_map(|row| (0..num_columns).map(move |column| (column, row)))
+            .map(|(column, row)| {
+                let is_odd_row = row % 2 == 1;
+                let row_offset = if is_odd_row { hex_width / 2. } else { 0. };
+
+                let x = column as f64 * hex_width + hex_width / 2. + row_offset;
+                let y = top_margin + radius + row as f64 * row_height;
+                // keep the centroid on the map; the border may still cross the seam
+                let centroid_x = match wrapping {
+                    Wrapping::Horizontal => x.rem_euclid(model_width),
+                    Wrapping::None => x,
+                };
+                let centroid_coor = PlanetCoor::new(centroid_x, y);
+
+                let border_vertices = (0..6)
+                    .map(|corner| {
+                        let angle = (60. * corner as f64 + 30.).to_radians();
+                        PlanetCoor::new(centroid_x + radius * angle.cos(), y + radius * angle.sin())
+                    })
+                    .collect();
+
+                // odd rows are shifted east, so their diagonal neighbours are 1 column further east
+                let diagonal_shift = if is_odd_row { 0 } else { -1 };
+                let same_row = [-1, 1].map(|delta| (delta, row as isize));
+                let other_rows = [row as isize - 1, row as isize + 1]
+                    .into_iter()
+                    .flat_map(|r| [(diagonal_shift, r), (diagonal_shift + 1, r)]);
+
+                let neighbours = same_row
+                    .into_iter()
+                    .chain(other_rows)
+                    .filter(|&(_, r)| (0..num_rows as isize).contains(&r))
+                    .filter_map(|(delta, r)| {
+                        shift_column(column, delta, num_columns, wrapping)
+                            .map(|c| to_id(c, r as usize))
+                    })
+                    .collect();
+
+                CellData {
+                    centroid_coor,
+                    border_vertices,
+                    neighbours,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use super::{PlanetCoor, RegionId, Wrapping};
+
+mod hex_grid;
+mod square_grid;
+mod voronoi;
+
+/// Geometry of a cell, before it gets turned into a region.
+pub struct CellData {
+    centroid_coor: PlanetCoor,
+    border_vertices: Vec<PlanetCoor>,
+    neighbours: HashSet<RegionId>,
+}
+
+/**
+Splits the map of a planet into cells, which become the regions of the planet.
+
+For horizontally wrapping planets, cells at the seam list the cells on the other side as
+neighbours, and their border vertices may go past the east or west edge of the map.
+*/
+pub trait RegionLayout {
+    fn build_cells(
+        &self,
+        creation_rng: &mut dyn rand_core::RngCore,
+        model_width: f64,
+        model_height: f64,
+        wrapping: Wrapping,
+    ) -> Vec<CellData>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayoutKind {
+    Voronoi,
+    HexGrid,
+    SquareGrid,
+}
+
+pub struct VoronoiLayout;
+
+// pointy-top hexagons, with odd rows shifted east by half a hexagon
+pub struct HexGridLayout;
+
+pub struct SquareGridLayout;
+
+// approximate width of a cell in grid layouts, similar to the size of the voronoi cells
+pub(super) const GRID_CELL_SIZE: f64 = 1.25;
+
+impl LayoutKind {
+    pub fn get_layout(&self) -> &'static dyn RegionLayout {
+        match self {
+            LayoutKind::Voronoi => &VoronoiLayout,
+            LayoutKind::HexGrid => &HexGridLayout,
+            LayoutKind::SquareGrid => &SquareGridLayout,
+        }
+    }
+}
+
+impl CellData {
+    pub fn centroid_coor(&self) -> PlanetCoor {
+        self.centroid_coor
+    }
+
+    pub fn into_parts(self) -> (PlanetCoor, Vec<PlanetCoor>, HashSet<RegionId>) {
+        (self.centroid_coor, self.border_vertices, self.neighbours)
+    }
+}
+
+/// Number of grid cells that fit in a length, with at least 3 so wrapping neighbours are distinct.
+fn count_grid_cells(length: f64, cell_size: f64) -> usize {
+    ((length / cell_size).round() as usize).max(3)
+}
+
+/// Neighbouring column, or None when it's past the edge of a non-wrapping map.
+fn shift_column(
+    column: usize,
+    delta: isize,
+    num_columns: usize,
+    wrapping: Wrapping,
+) -> Option<usize> {
+    let shifted = column as isize + delta;
+    match wrapping {
+        Wrapping::Horizontal => Some(shifted.rem_euclid(num_columns as isize) as usize),
+        Wrapping::None if (0..num_columns as isize).contains(&shifted) => Some(shifted as usize),
+        Wrapping::None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{prelude::StdRng, SeedableRng};
+
+    use super::{CellData, LayoutKind};
+    use crate::planet::{Planet, RegionId, Wrapping};
+
+    fn build(layout: LayoutKind, width: f64, height: f64, wrapping: Wrapping) -> Vec<CellData> {
+        let mut rng = StdRng::seed_from_u64(0);
+        layout
+            .get_layout()
+            .build_cells(&mut rng, width, height, wrapping)
+    }
+
+    fn assert_symmetric(cells: &[CellData]) {
+        cells.iter().enumerate().for_each(|(index, cell)| {
+            cell.neighbours.iter().for_each(|&neighbour| {
+                let neighbour = &cells[usize::from(neighbour)];
+                assert!(neighbour.neighbours.contains(&RegionId::new_unsafe(index)));
+            })
+        });
+    }
+
+    #[test]
+    fn square_grid() {
+        // 4 columns x 3 rows, each cell is 1.25 x 1.25
+        let cells = build(LayoutKind::SquareGrid, 5., 3.75, Wrapping::None);
+        assert_eq!(cells.len(), 12);
+        assert_symmetric(&cells);
+
+        let neighbour_counts: Vec<_> = cells.iter().map(|cell| cell.neighbours.len()).collect();
+        assert_eq!(neighbour_counts, vec![2, 3, 3, 2, 3, 4, 4, 3, 2, 3, 3, 2]);
+    }
+
+    #[test]
+    fn square_grid_wrapping() {
+        let cells = build(LayoutKind::SquareGrid, 5., 3.75, Wrapping::Horizontal);
+        assert_eq!(cells.len(), 12);
+        assert_symmetric(&cells);
+
+        // the west-most cell of the first row touches the east-most one
+        assert!(cells[0].neighbours.contains(&RegionId::new_unsafe(3)));
+        assert!(cells.iter().all(|cell| cell.neighbours.len() >= 3));
+    }
+
+    #[test]
+    fn hex_grid() {
+        let cells = build(LayoutKind::HexGrid, 10., 7., Wrapping::None);
+        assert_symmetric(&cells);
+        assert!(cells.iter().all(|cell| cell.neighbours.len() <= 6));
+        assert!(cells.iter().any(|cell| cell.neighbours.len() == 6));
+
+        let wrapping_cells = build(LayoutKind::HexGrid, 10., 7., Wrapping::Horizontal);
+        assert_symmetric(&wrapping_cells);
+        assert_eq!(cells.len(), wrapping_cells.len());
+        // middle rows are surrounded on all sides once the map wraps
+        assert!(
+            wrapping_cells
+                .iter()
+                .filter(|cell| cell.neighbours.len() == 6)
+                .count()
+                > cells
+                    .iter()
+                    .filter(|cell| cell.neighbours.len() == 6)
+                    .count()
+        );
+    }
+
+    #[test]
+    fn grid_planet_geometry() {
+        let planet = Planet::new_test_grid(5., Wrapping::Horizontal);
+        let regions = planet.get_regions();
+        assert_eq!(regions.len(), 12);
+
+        let total_area: f64 = regions.iter().map(|region| region.area()).sum();
+        assert!((total_area - 5. * 3.5).abs() < 1e-9);
+
+        // cells are 1.25 wide & 3.5 / 3 tall
+        let cell_height = 3.5 / 3.;
+        let region = &regions[0];
+        assert!((region.perimeter() - 2. * (1.25 + cell_height)).abs() < 1e-9);
+
+        let shared =
+            |a: usize, b: usize| regions[a].get_shared_border_length(RegionId::new_unsafe(b));
+        assert!((shared(0, 1) - cell_height).abs() < 1e-9);
+        assert!((shared(0, 3) - cell_height).abs() < 1e-9); // across the seam
+        assert!((shared(0, 4) - 1.25).abs() < 1e-9);
+        assert_eq!(shared(0, 5), 0.);
+    }
+}
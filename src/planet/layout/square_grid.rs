@@ -0,0 +1,54 @@
+use super::{
+    count_grid_cells, shift_column, CellData, RegionLayout, SquareGridLayout, GRID_CELL_SIZE,
+};
+use crate::planet::{PlanetCoor, RegionId, Wrapping};
+
+impl RegionLayout for SquareGridLayout {
+    /// Rectangles stretched a little to fill the map exactly; neighbours share an edge.
+    fn build_cells(
+        &self,
+        _creation_rng: &mut dyn rand_core::RngCore,
+        model_width: f64,
+        model_height: f64,
+        wrapping: Wrapping,
+    ) -> Vec<CellData> {
+        let num_columns = count_grid_cells(model_width, GRID_CELL_SIZE);
+        let num_rows = count_grid_cells(model_height, GRID_CELL_SIZE);
+        let cell_width = model_width / num_columns as f64;
+        let cell_height = model_height / num_rows as f64;
+
+        let to_id = |column: usize, row: usize| RegionId::new_unsafe(row * num_columns + column);
+
+        (0..num_rows)
+            .flat_map(|row| (0..num_columns).map(move |column| (column, row)))
+            .map(|(column, row)| {
+                let left = column as f64 * cell_width;
+                let top = row as f64 * cell_height;
+                let (right, bottom) = (left + cell_width, top + cell_height);
+
+                let centroid_coor = PlanetCoor::new(left + cell_width / 2., top + cell_height / 2.);
+                let border_vertices = vec![
+                    PlanetCoor::new(left, top),
+                    PlanetCoor::new(left, bottom),
+                    PlanetCoor::new(right, bottom),
+                    PlanetCoor::new(right, top),
+                ];
+
+                let west = shift_column(column, -1, num_columns, wrapping).map(|c| to_id(c, row));
+                let east = shift_column(column, 1, num_columns, wrapping).map(|c| to_id(c, row));
+                let north = row.checked_sub(1).map(|r| to_id(column, r));
+                let south = Some(row + 1)
+                    .filter(|&r| r < num_rows)
+                    .map(|r| to_id(column, r));
+
+                let neighbours = [west, east, north, south].into_iter().flatten().collect();
+
+                CellData {
+                    centroid_coor,
+                    border_vertices,
+                    neighbours,
+                }
+            })
+            .collect()
+    }
+}
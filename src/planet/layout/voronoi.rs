@@ -0,0 +1,159 @@
+use nalgebra::Vector2;
+use poisson_diskus::bridson_rng;
+use voronoice::{BoundingBox, VoronoiBuilder, VoronoiCell};
+
+use super::{CellData, RegionLayout, VoronoiLayout};
+use crate::planet::{PlanetCoor, RegionId, Wrapping};
+
+impl RegionLayout for VoronoiLayout {
+    /// Poisson-disk sampled sites, relaxed with Lloyd's algorithm.
+    fn build_cells(
+        &self,
+        mut creation_rng: &mut dyn rand_core::RngCore,
+        model_width: f64,
+        model_height: f64,
+        wrapping: Wrapping,
+    ) -> Vec<CellData> {
+        let sites: Vec<_> = {
+            let box_size = [model_width, model_height];
+            let rmin = 1.;
+            let num_attempts = 30;
+            // periodic sampling keeps the sites evenly spaced across the seam
+            let use_pbc = wrapping == Wrapping::Horizontal;
+
+            bridson_rng(&box_size, rmin, num_attempts, use_pbc, &mut creation_rng)
+                .expect("The map site wasn't generated")
+                .into_iter()
+                .map(|[x, y]| voronoice::Point { x, y })
+                .collect()
+        };
+
+        match wrapping {
+            Wrapping::None => build_bounded_cells(sites, model_width, model_height),
+            Wrapping::Horizontal => build_wrapping_cells(sites, model_width, model_height),
+        }
+    }
+}
+
+fn to_cell_data(cell: VoronoiCell, num_sites: usize) -> CellData {
+    let centroid_coor = cell.site_position();
+    let centroid_coor = PlanetCoor::new(centroid_coor.x, centroid_coor.y);
+
+    let border_vertices = cell
+        .iter_vertices()
+        .map(|point| PlanetCoor::new(point.x, point.y))
+        .collect();
+
+    // neighbours that are copies across the seam are mapped back to their original
+    let site = cell.site();
+    let neighbours = cell
+        .iter_neighbors()
+        .map(|neighbour| neighbour % num_sites)
+        .filter(|&neighbour| neighbour != site)
+        .map(RegionId::new_unsafe)
+        .collect();
+
+    CellData {
+        centroid_coor,
+        border_vertices,
+        neighbours,
+    }
+}
+
+const LLOYD_RELAXATION_ITERATIONS: usize = 2;
+
+fn build_bounded_cells(
+    sites: Vec<voronoice::Point>,
+    model_width: f64,
+    model_height: f64,
+) -> Vec<CellData> {
+    let num_sites = sites.len();
+
+    // builds a voronoi diagram from the set of sites above, bounded by the map
+    let graph = VoronoiBuilder::default()
+        .set_sites(sites)
+        .set_bounding_box(BoundingBox::new(
+            voronoice::Point {
+                x: model_width / 2.,
+                y: model_height / 2.,
+            },
+            model_width,
+            model_height,
+        ))
+        .set_lloyd_relaxation_iterations(LLOYD_RELAXATION_ITERATIONS)
+        .build()
+        .unwrap();
+
+    graph
+        .iter_cells()
+        .map(|cell| to_cell_data(cell, num_sites))
+        .collect()
+}
+
+/**
+Builds a periodic voronoi diagram by surrounding the sites with copies of themselves shifted by the
+map width to the west and to the east, so cells at the seam are shaped as if the map is continuous.
+Lloyd relaxation is done by hand, because the library would move the copies independently.
+*/
+fn build_wrapping_cells(
+    mut sites: Vec<voronoice::Point>,
+    model_width: f64,
+    model_height: f64,
+) -> Vec<CellData> {
+    let num_sites = sites.len();
+
+    let build = |sites: &[voronoice::Point]| {
+        let shifted = |dx: f64| {
+            sites.iter().map(move |point| voronoice::Point {
+                x: point.x + dx,
+                y: point.y,
+            })
+        };
+
+        // originals go first, so their cells keep the same indices
+        let all_sites = shifted(0.)
+            .chain(shifted(-model_width))
+            .chain(shifted(model_width))
+            .collect();
+
+        VoronoiBuilder::default()
+            .set_sites(all_sites)
+            .set_bounding_box(BoundingBox::new(
+                voronoice::Point {
+                    x: model_width / 2.,
+                    y: model_height / 2.,
+                },
+                model_width * 3.,
+                model_height,
+            ))
+            .build()
+            .unwrap()
+    };
+
+    for _ in 0..LLOYD_RELAXATION_ITERATIONS {
+        let graph = build(&sites);
+        sites = graph
+            .iter_cells()
+            .take(num_sites)
+            .map(|cell| {
+                let (sum, count) = cell
+                    .iter_vertices()
+                    .fold((Vector2::zeros(), 0), |(sum, count), point| {
+                        (sum + Vector2::new(point.x, point.y), count + 1)
+                    });
+                let centroid = sum / count as f64;
+                voronoice::Point {
+                    x: centroid.x.rem_euclid(model_width),
+                    y: centroid.y,
+                }
+            })
+            .collect();
+    }
+
+    let graph = build(&sites);
+    graph
+        .iter_cells()
+        .take(num_sites)
+        .map(|cell| to_cell_data(cell, num_sites))
+        .collect()
+}
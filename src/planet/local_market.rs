@@ -0,0 +1,250 @@
+use enum_map::{enum_map, EnumMap};
+
+use super::{
+    retailer::{get_penetration, get_price_rating, get_rating, BASE_QUALITY},
+    LocalMarket, Planet, Storage,
+};
+use crate::{
+    economy::{Commodity, MarketConfig, Money, Price, Quantity},
+    Time,
+};
+
+// markets are repriced once per period
+const MARKET_PERIOD: Time = 24;
+// prices stay within this factor of the intrinsic price, either way
+const MAX_PRICE_FACTOR: f64 = 10.;
+// how far a trade moves the price, relative to the portion of the supply it takes or adds
+const SLIPPAGE: f64 = 0.25;
+// prices are in whole units of money, which is counted in cents
+const CENTS_PER_UNIT: f64 = 100.;
+
+/// intrinsic price * (demand / supply)^elasticity
+pub fn get_equilibrium_price(
+    intrinsic_price: Price,
+    elasticity: f64,
+    demand: f64,
+    supply: f64,
+) -> Price {
+    if demand <= 0. && supply <= 0. {
+        return intrinsic_price;
+    }
+    let ratio = (demand / supply.max(1.))
+        .powf(elasticity)
+        .clamp(1. / MAX_PRICE_FACTOR, MAX_PRICE_FACTOR);
+    intrinsic_price * ratio
+}
+
+/// Converts an amount in whole units, as prices are, to money.
+pub fn to_money(amount: Price) -> Money {
+    (amount * CENTS_PER_UNIT).round() as Money
+}
+
+/// What a population uses up in a market period.
+pub fn get_demands(population: u32) -> EnumMap<Commodity, f64> {
+    enum_map! {
+        commodity => population as f64 * commodity.get_consumption_per_capita(),
+    }
+}
+
+impl LocalMarket {
+    pub fn get_storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    pub fn get_storage_mut(&mut self) -> &mut Storage {
+        &mut self.storage
+    }
+
+    pub fn get_price(&self, commodity: Commodity) -> Price {
+        self.prices[commodity]
+    }
+
+    /// Amount delivered over the last market period.
+    pub fn get_production(&self, commodity: Commodity) -> Quantity {
+        self.production[commodity]
+    }
+
+    /// Brings goods made in the region to the market, which counts towards its supply.
+    pub fn deliver(&mut self, commodity: Commodity, quantity: Quantity) {
+        self.storage.add(commodity, quantity);
+        self.deliveries[commodity] = self.deliveries[commodity].saturating_add(quantity);
+    }
+
+    /// Stock plus the latest production, which is how deep the market is for traders.
+    fn get_supply(&self, commodity: Commodity) -> f64 {
+        ((self.storage.get(commodity) + self.production[commodity]) as f64).max(1.)
+    }
+
+    /**
+    Prices the market ends up at after a trader buys (positive) or sells (negative) the quantity.
+    The trader pays or gets the average of the prices before & after.
+    */
+    fn get_price_after(&self, commodity: Commodity, quantity: f64) -> Price {
+        // goods sold count towards the supply they are sold into
+        let supply = self.get_supply(commodity) + (-quantity).max(0.);
+        self.prices[commodity] * (1. + 2. * SLIPPAGE * quantity / supply)
+    }
+
+    fn get_trade_value(&self, commodity: Commodity, quantity: f64) -> Money {
+        let average = (self.prices[commodity] + self.get_price_after(commodity, quantity)) / 2.;
+        to_money(average * quantity.abs())
+    }
+
+    /// What buying the quantity would cost, or None if the market doesn't have it.
+    pub fn quote_buy(&self, commodity: Commodity, quantity: Quantity) -> Option<Money> {
+        (self.storage.get(commodity) >= quantity)
+            .then(|| self.get_trade_value(commodity, quantity as f64))
+    }
+
+    /// What selling the quantity would bring in.
+    pub fn quote_sell(&self, commodity: Commodity, quantity: Quantity) -> Money {
+        self.get_trade_value(commodity, -(quantity as f64))
+    }
+
+    /// Takes the goods out of the stock & raises the price, and returns the cost.
+    pub fn buy(&mut self, commodity: Commodity, quantity: Quantity) -> Option<Money> {
+        let cost = self.quote_buy(commodity, quantity)?;
+        self.prices[commodity] = self.get_price_after(commodity, quantity as f64);
+        self.storage.take(commodity, quantity);
+        Some(cost)
+    }
+
+    /// Adds the goods to the stock & lowers the price, and returns the revenue.
+    pub fn sell(&mut self, commodity: Commodity, quantity: Quantity) -> Money {
+        let revenue = self.quote_sell(commodity, quantity);
+        self.prices[commodity] = self.get_price_after(commodity, -(quantity as f64));
+        self.storage.add(commodity, quantity);
+        revenue
+    }
+
+    /// Ordinary goods found all over the region, so only the price sets them apart.
+    pub fn cal_market_penetration(&self, commodity: Commodity, intrinsic_price: Price) -> f64 {
+        if self.storage.get(commodity) == 0 {
+            return 0.;
+        }
+        let price_rating = get_price_rating(intrinsic_price, self.prices[commodity]);
+        get_penetration(1., get_rating(BASE_QUALITY as f64, 0., price_rating))
+    }
+
+    /**
+    Reprices every commodity from its supply, which is the stock plus what is expected to be
+    delivered again over the next period, and its demand at the market. Shoppers then buy what they
+    can out of the stock.
+    */
+    pub fn update_prices(&mut self, demands: &EnumMap<Commodity, f64>, config: &MarketConfig) {
+        self.production = std::mem::take(&mut self.deliveries);
+
+        self.prices = enum_map! {
+            commodity => get_equilibrium_price(
+                config.get_intrinsic_price(commodity),
+                config.get_elasticity(commodity),
+                demands[commodity],
+                (self.storage.get(commodity) + self.production[commodity]) as f64,
+            ),
+        };
+
+        for (commodity, demand) in demands {
+            self.storage.take(commodity, demand.ceil() as Quantity);
+        }
+    }
+}
+
+impl Planet {
+    /// Opens the markets if it's time to, and returns whether they were.
+    pub fn progress_markets(&mut self, time: Time, config: &MarketConfig) -> bool {
+        if !time.is_multiple_of(MARKET_PERIOD) {
+            return false;
+        }
+        for region in &mut self.regions {
+            region.progress_market(config);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_demands, MAX_PRICE_FACTOR};
+    use crate::{
+        economy::{Commodity, MarketConfig},
+        planet::LocalMarket,
+    };
+
+    #[test]
+    fn prices_rise_under_shortage() {
+        let config = MarketConfig::default();
+        let intrinsic = config.get_intrinsic_price(Commodity::Crop);
+        let population = 1000; // eats 1000 crops a period
+
+        let price_with = |stock| {
+            let mut market = LocalMarket::default();
+            market.deliver(Commodity::Crop, stock);
+            market.update_prices(&get_demands(population), &config);
+            market.get_price(Commodity::Crop)
+        };
+
+        let shortage = price_with(200);
+        let balanced = price_with(500); // delivered again next period, so 1000 in supply
+        let glut = price_with(5000);
+        assert!(shortage > balanced && balanced > glut);
+        assert!((balanced - intrinsic).abs() < 1e-9);
+        assert!(shortage > intrinsic);
+        assert!(glut < intrinsic);
+
+        // nothing to buy at all
+        assert_eq!(price_with(0), intrinsic * MAX_PRICE_FACTOR);
+    }
+
+    #[test]
+    fn population_consumes_stock() {
+        let config = MarketConfig::default();
+        let mut market = LocalMarket::default();
+        market.deliver(Commodity::Crop, 300);
+        market.update_prices(&get_demands(100), &config);
+        assert_eq!(market.get_storage().get(Commodity::Crop), 200);
+        assert_eq!(market.get_production(Commodity::Crop), 300);
+
+        // supply is still there, but nothing new came in
+        market.update_prices(&get_demands(100), &config);
+        assert_eq!(market.get_production(Commodity::Crop), 0);
+        assert_eq!(market.get_storage().get(Commodity::Crop), 100);
+        // nobody wants tools in a village, so they sell cheap
+        market.deliver(Commodity::Tool, 10);
+        market.update_prices(&get_demands(100), &config);
+        assert!(market.get_price(Commodity::Tool) < config.get_intrinsic_price(Commodity::Tool));
+    }
+
+    #[test]
+    fn trades_slip_the_price() {
+        let config = MarketConfig::default();
+        let mut market = LocalMarket::default();
+        market.deliver(Commodity::Tool, 100);
+        market.update_prices(&get_demands(0), &config);
+        let price = market.get_price(Commodity::Tool);
+
+        // buying a lot costs more per piece
+        let small = market.quote_buy(Commodity::Tool, 10).unwrap();
+        let large = market.quote_buy(Commodity::Tool, 100).unwrap();
+        assert!(large > small * 10);
+        assert_eq!(market.quote_buy(Commodity::Tool, 300), None);
+
+        // a round trip loses money
+        let cost = market.buy(Commodity::Tool, 50).unwrap();
+        assert!(market.get_price(Commodity::Tool) > price);
+        assert_eq!(market.get_storage().get(Commodity::Tool), 50);
+        let revenue = market.sell(Commodity::Tool, 50);
+        assert!(revenue < cost);
+        assert!(market.get_price(Commodity::Tool) < price);
+    }
+
+    #[test]
+    fn prices_follow_the_config() {
+        let mut config = MarketConfig::default();
+        config.set_intrinsic_price(Commodity::Salt, 10.);
+        config.set_elasticity(Commodity::Salt, 0.);
+
+        let mut market = LocalMarket::default();
+        market.update_prices(&get_demands(1000), &config);
+        assert_eq!(market.get_price(Commodity::Salt), 10.);
+    }
+}
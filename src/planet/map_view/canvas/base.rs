@@ -6,7 +6,7 @@ use crate::{
     terrain::Terrain,
 };
 
-use super::util::{get_base_canvas, get_context, set_transformation};
+use super::util::{get_base_canvas, get_context, set_transformation, trace_region};
 
 pub fn update_base_canvas_with_height(view_model: &ViewModelContext, planet: &Planet) {
     let grid_size = view_model.grid_size;
@@ -28,14 +28,7 @@ pub fn update_base_canvas_with_height(view_model: &ViewModelContext, planet: &Pl
         context.set_stroke_style(&style);
         context.set_fill_style(&style);
 
-        let bvs = &region.border_vertices;
-        let first = bvs[0];
-
-        context.move_to(first.x, first.y);
-        bvs.iter().skip(1).for_each(|coor| {
-            context.line_to(coor.x, coor.y);
-        });
-        context.close_path();
+        trace_region(&context, planet, region);
         context.fill();
         context.stroke();
     }
@@ -84,15 +77,7 @@ pub fn update_base_canvas_with_terrain(view_model: &ViewModelContext, planet: &P
         context.set_stroke_style(style);
         context.set_fill_style(style);
 
-        let bvs = &region.border_vertices;
-        let first = bvs[0];
-
-        context.move_to(first.x, first.y);
-        bvs.iter().skip(1).for_each(|coor| {
-            let coor = coor;
-            context.line_to(coor.x, coor.y);
-        });
-        context.close_path();
+        trace_region(&context, planet, region);
         context.fill();
         context.stroke();
     }
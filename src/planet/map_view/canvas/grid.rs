@@ -2,7 +2,7 @@ use wasm_bindgen::JsValue;
 
 use crate::{app::ViewModelContext, planet::Planet};
 
-use super::util::{get_context, get_grid_canvas, set_transformation};
+use super::util::{get_context, get_grid_canvas, set_transformation, trace_region};
 
 pub fn update_grid_canvas(view_model: &ViewModelContext, planet: &Planet) {
     let grid_size = view_model.grid_size;
@@ -22,14 +22,7 @@ pub fn update_grid_canvas(view_model: &ViewModelContext, planet: &Planet) {
     for region in &planet.regions {
         context.begin_path();
 
-        let bvs = &region.border_vertices;
-        let first = bvs[0];
-
-        context.move_to(first.x, first.y);
-        bvs.iter().skip(1).for_each(|coor| {
-            context.line_to(coor.x, coor.y);
-        });
-        context.close_path();
+        trace_region(&context, planet, region);
         context.stroke();
     }
     context.restore();
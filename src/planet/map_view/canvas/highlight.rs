@@ -1,6 +1,6 @@
 use crate::planet::{Planet, RegionId};
 
-use super::util::{get_context, get_hover_canvas, set_transformation};
+use super::util::{get_context, get_hover_canvas, set_transformation, trace_region};
 
 pub fn update_highlight_canvas(planet: &Planet, region_id: RegionId, grid_size: f64) {
     let model_width = planet.model_width;
@@ -19,15 +19,7 @@ pub fn update_highlight_canvas(planet: &Planet, region_id: RegionId, grid_size:
 
     context.begin_path();
 
-    let bvs = &region.border_vertices;
-    let first = bvs[0];
-
-    context.move_to(first.x, first.y);
-    bvs.iter().skip(1).for_each(|coor| {
-        let coor = coor;
-        context.line_to(coor.x, coor.y);
-    });
-    context.close_path();
+    trace_region(&context, planet, region);
     context.stroke();
 
     context.restore();
@@ -1,38 +1,52 @@
+use web_sys::HtmlCanvasElement;
+
 use crate::{app::ViewModelContext, planet::map_view::MapMode};
 
 use super::{
     base::update_base_canvas,
     grid::update_grid_canvas,
+    port::draw_port_markers,
+    portal::draw_portal_markers,
+    road::draw_roads,
     util::{get_base_canvas, get_context, get_grid_canvas, get_hover_canvas, get_main_canvas},
 };
 
-pub fn redraw_main(is_show_grid: bool) {
+/// Copies the layers onto the main canvas. The layers are shifted west by `scroll_x` (in viewport
+/// pixels), and the part that was scrolled out re-enters from the east.
+pub fn redraw_main(is_show_grid: bool, scroll_x: f64) {
     let canvas = get_main_canvas();
     let context = get_context(&canvas);
+    let vp_width = canvas.width() as f64;
 
     let hover_canvas = get_hover_canvas();
     let base_canvas = get_base_canvas();
 
-    context
-        .draw_image_with_html_canvas_element(&base_canvas, 0., 0.)
-        .unwrap();
+    let draw_layer = |layer: &HtmlCanvasElement| {
+        context
+            .draw_image_with_html_canvas_element(layer, -scroll_x, 0.)
+            .unwrap();
+        if scroll_x != 0. {
+            context
+                .draw_image_with_html_canvas_element(layer, vp_width - scroll_x, 0.)
+                .unwrap();
+        }
+    };
+
+    draw_layer(&base_canvas);
 
     if is_show_grid {
         let grid_canvas = get_grid_canvas();
-        context
-            .draw_image_with_html_canvas_element(&grid_canvas, 0., 0.)
-            .unwrap();
+        draw_layer(&grid_canvas);
     }
 
-    context
-        .draw_image_with_html_canvas_element(&hover_canvas, 0., 0.)
-        .unwrap();
+    draw_layer(&hover_canvas);
 }
 
 pub fn redraw_all(
     view_model: &ViewModelContext,
     map_mode: MapMode,
     is_show_grid: bool,
+    scroll_x: f64,
     should_redraw_map: bool,
 ) {
     let game = view_model.game.borrow();
@@ -44,9 +58,18 @@ pub fn redraw_all(
 
         if should_redraw_map {
             update_base_canvas(view_model, map_mode, planet);
+            draw_roads(planet, view_model.grid_size);
+            draw_port_markers(planet, view_model.grid_size);
+            draw_portal_markers(
+                game.get_portals(),
+                universe_id,
+                planet_id,
+                planet,
+                view_model.grid_size,
+            );
         }
         update_grid_canvas(view_model, planet);
 
-        redraw_main(is_show_grid);
+        redraw_main(is_show_grid, scroll_x);
     }
 }
@@ -2,4 +2,7 @@ pub mod base;
 pub mod grid;
 pub mod highlight;
 pub mod main;
+pub mod port;
+pub mod portal;
+pub mod road;
 mod util;
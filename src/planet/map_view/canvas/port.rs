@@ -0,0 +1,36 @@
+use wasm_bindgen::JsValue;
+
+use crate::planet::Planet;
+
+use super::util::{get_base_canvas, get_context, get_wrap_shifts, set_transformation};
+
+// side of the marker, in model units
+const PORT_MARKER_SIZE: f64 = 0.3;
+
+/// Draws a square on every region with a port, on top of the base layer.
+pub fn draw_port_markers(planet: &Planet, grid_size: f64) {
+    let context = {
+        let canvas = get_base_canvas();
+        get_context(&canvas)
+    };
+
+    let shifts = get_wrap_shifts(planet);
+
+    context.save();
+    set_transformation(&context, grid_size);
+    context.set_fill_style(&JsValue::from("#123"));
+
+    for region_id in planet.get_ports() {
+        let centroid = planet.regions[usize::from(region_id)].get_centroid();
+        let half = PORT_MARKER_SIZE / 2.;
+        for dx in &shifts {
+            context.fill_rect(
+                centroid.x + dx - half,
+                centroid.y - half,
+                PORT_MARKER_SIZE,
+                PORT_MARKER_SIZE,
+            );
+        }
+    }
+    context.restore();
+}
@@ -0,0 +1,51 @@
+use wasm_bindgen::JsValue;
+
+use crate::{
+    planet::{Planet, PlanetId},
+    portal::Portals,
+    universe::UniverseId,
+};
+
+use super::util::{get_base_canvas, get_context, get_wrap_shifts, set_transformation};
+
+// radius of the marker, in model units
+const PORTAL_MARKER_RADIUS: f64 = 0.3;
+
+/// Draws a ring on every region that has a portal, on top of the base layer.
+pub fn draw_portal_markers(
+    portals: &Portals,
+    universe_id: UniverseId,
+    planet_id: PlanetId,
+    planet: &Planet,
+    grid_size: f64,
+) {
+    let context = {
+        let canvas = get_base_canvas();
+        get_context(&canvas)
+    };
+
+    let shifts = get_wrap_shifts(planet);
+
+    context.save();
+    set_transformation(&context, grid_size);
+    context.set_line_width(3. / grid_size);
+    context.set_stroke_style(&JsValue::from("#c6f"));
+
+    for (_, end) in portals.iter_ends_on_planet(universe_id, planet_id) {
+        let centroid = planet.regions[usize::from(end.region_id)].get_centroid();
+        for dx in &shifts {
+            context.begin_path();
+            context
+                .arc(
+                    centroid.x + dx,
+                    centroid.y,
+                    PORTAL_MARKER_RADIUS,
+                    0.,
+                    std::f64::consts::TAU,
+                )
+                .unwrap();
+            context.stroke();
+        }
+    }
+    context.restore();
+}
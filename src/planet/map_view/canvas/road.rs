@@ -0,0 +1,45 @@
+use wasm_bindgen::JsValue;
+
+use crate::planet::{Planet, RoadKind};
+
+use super::util::{get_base_canvas, get_context, get_wrap_shifts, set_transformation};
+
+/// Draws the roads between region centroids on top of the base layer, wider for better roads.
+pub fn draw_roads(planet: &Planet, grid_size: f64) {
+    let context = {
+        let canvas = get_base_canvas();
+        get_context(&canvas)
+    };
+
+    let shifts = get_wrap_shifts(planet);
+
+    context.save();
+    set_transformation(&context, grid_size);
+
+    for ((a, b), road) in planet.iter_roads() {
+        let (color, width) = road_style(road.get_kind());
+        context.set_stroke_style(&JsValue::from(color));
+        context.set_line_width(width / grid_size);
+        context.set_global_alpha(0.4 + 0.6 * road.get_condition());
+
+        let from = planet.regions[usize::from(a)].get_centroid();
+        let to =
+            from + planet.get_displacement(from, planet.regions[usize::from(b)].get_centroid());
+        for dx in &shifts {
+            context.begin_path();
+            context.move_to(from.x + dx, from.y);
+            context.line_to(to.x + dx, to.y);
+            context.stroke();
+        }
+    }
+    context.restore();
+}
+
+fn road_style(kind: RoadKind) -> (&'static str, f64) {
+    match kind {
+        RoadKind::Trail => ("#c8a96e", 1.),
+        RoadKind::Road => ("#d9d9d9", 2.),
+        RoadKind::Highway => ("#ffd24d", 3.),
+        RoadKind::Rail => ("#333333", 3.),
+    }
+}
@@ -1,5 +1,7 @@
 use wasm_bindgen::JsCast;
 
+use crate::planet::{Planet, Region};
+
 type Canvas = web_sys::HtmlCanvasElement;
 
 pub fn get_canvas(element_id: &str) -> Canvas {
@@ -42,3 +44,30 @@ pub fn set_transformation(context: &web_sys::CanvasRenderingContext2d, grid_size
     context.scale(grid_size, grid_size).unwrap();
     context.set_line_width(1. / grid_size)
 }
+
+/**
+Horizontal offsets to draw everything at, in model units. On wrapping planets, things are also
+drawn one map width to the west & east, so those near the seam show up on both sides.
+*/
+pub fn get_wrap_shifts(planet: &Planet) -> Vec<f64> {
+    if planet.is_wrapping() {
+        vec![-planet.model_width, 0., planet.model_width]
+    } else {
+        vec![0.]
+    }
+}
+
+/// Adds the region's border to the current path, once per wrap shift, so cells crossing the seam
+/// show up on both sides.
+pub fn trace_region(context: &web_sys::CanvasRenderingContext2d, planet: &Planet, region: &Region) {
+    let bvs = &region.border_vertices;
+    let first = bvs[0];
+
+    for dx in get_wrap_shifts(planet) {
+        context.move_to(first.x + dx, first.y);
+        bvs.iter().skip(1).for_each(|coor| {
+            context.line_to(coor.x + dx, coor.y);
+        });
+        context.close_path();
+    }
+}
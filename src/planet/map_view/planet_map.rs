@@ -20,6 +20,8 @@ pub fn create_planet_map() -> Html {
 
     let map_mode = use_state(|| MapMode::Terrain);
     let is_show_grid = use_state(|| false);
+    // how far the map is scrolled east, in viewport pixels; only used by wrapping planets
+    let scroll_x = use_state(|| 0.);
     let hovered_region_id = use_state(|| Option::<RegionId>::None);
 
     let change_map_mode = {
@@ -47,16 +49,39 @@ pub fn create_planet_map() -> Html {
     let game = view_model.game.borrow();
     let (universe_id, planet_id) = view_model.get_selected_planet_id();
 
+    // another planet starts unscrolled
+    use_effect_with_deps(
+        {
+            let scroll_x = scroll_x.clone();
+            move |_| {
+                if *scroll_x != 0. {
+                    scroll_x.set(0.);
+                }
+                || {}
+            }
+        },
+        (universe_id, planet_id),
+    );
+
+    let is_wrapping = planet_id.is_some_and(|planet_id| {
+        game.get_universe(universe_id)
+            .get_planet(planet_id)
+            .is_wrapping()
+    });
+    let shown_scroll_x = if is_wrapping { *scroll_x } else { 0. };
+
     use_effect({
         let view_model = view_model.clone();
         let map_mode = *map_mode;
         let is_show_grid = *is_show_grid;
+        let scroll_x = shown_scroll_x;
 
         move || {
             redraw_all(
                 &view_model,
                 map_mode,
                 is_show_grid,
+                scroll_x,
                 *should_redraw_map.borrow(),
             );
             *should_redraw_map.borrow_mut() = false;
@@ -84,6 +109,16 @@ pub fn create_planet_map() -> Html {
             .to_i16()
             .unwrap();
 
+        let scroll_by = {
+            let scroll_x = scroll_x.clone();
+            let vp_width = vp_width as f64;
+            move |delta: f64| {
+                if is_wrapping {
+                    scroll_x.set((*scroll_x + delta).rem_euclid(vp_width));
+                }
+            }
+        };
+
         // turn the dimension values into strings
         let vp_height = vp_height.to_string();
         let vp_width = vp_width.to_string();
@@ -100,6 +135,7 @@ pub fn create_planet_map() -> Html {
 
         let map_mouse_move = {
             let is_show_grid = *is_show_grid;
+            let scroll_x = shown_scroll_x;
             let view_model = view_model.clone();
 
             move |e| {
@@ -108,14 +144,43 @@ pub fn create_planet_map() -> Html {
                 let planet = universe.get_planet(planet_id);
                 let coor_calculator = CoorCalculator::new(model_width, model_height, grid_size);
 
-                let mouse_coor = get_mouse_coor(&e);
+                let mut mouse_coor = get_mouse_coor(&e);
+                mouse_coor.x += scroll_x;
                 let model_coor = coor_calculator.to_model_coor(mouse_coor);
                 let region_id = planet.find_region_id(model_coor);
 
                 hovered_region_id.set(Some(region_id));
                 update_highlight_canvas(planet, region_id, grid_size);
-                redraw_main(is_show_grid);
+                redraw_main(is_show_grid, scroll_x);
+            }
+        };
+
+        let map_wheel = {
+            let scroll_by = scroll_by.clone();
+            move |e: WheelEvent| {
+                if is_wrapping {
+                    e.prevent_default();
+                    scroll_by(e.delta_x() + e.delta_y());
+                }
+            }
+        };
+
+        let scroll_buttons = if is_wrapping {
+            let scroll_step = coor_calculator.to_vp_magnitude(model_width / 8.);
+            let scroll_west = {
+                let scroll_by = scroll_by.clone();
+                move |_| scroll_by(-scroll_step)
+            };
+            let scroll_east = move |_| scroll_by(scroll_step);
+
+            html! {
+                <>
+                    <button onclick={scroll_west}>{"West"}</button>
+                    <button onclick={scroll_east}>{"East"}</button>
+                </>
             }
+        } else {
+            html! {}
         };
 
         html! {
@@ -124,12 +189,14 @@ pub fn create_planet_map() -> Html {
                     <button onclick={change_map_mode(MapMode::Terrain)}>{"Terrain"}</button>
                     <button onclick={change_map_mode(MapMode::Height)}>{"Height"}</button>
                     <button onclick={toggle_grid}>{"Grid"}</button>
+                    {scroll_buttons}
                 </div>
                 <canvas id="map-hover" style="display: none" width={vp_width.clone()} height={vp_height.clone()} />
                 <canvas id="map-base" style="display: none" width={vp_width.clone()} height={vp_height.clone()} />
                 <canvas id="map-grid" style="display: none" width={vp_width.clone()} height={vp_height.clone()} />
                 <canvas id="map" style="cursor: none" width={vp_width.clone()} height={vp_height.clone()}
                     onmousemove={map_mouse_move}
+                    onwheel={map_wheel}
                     onclick={update_selected_region_id} />
             </div>
         }
@@ -1,36 +1,87 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use enum_map::EnumMap;
+use enum_map::{Enum, EnumMap};
 use nalgebra::Point2;
 use rstar::RTree;
 use serde::Serialize;
 
-use self::producer::farm::Farm;
+use self::{
+    layout::LayoutKind,
+    producer::{factory::Factory, farm::Farm, fishery::Fishery},
+};
 use crate::{
     character::CharacterId,
-    economy::{Commodity, Ownership, Quality, Quantity},
+    economy::{Commodity, Ownership, Price, Quality, Quantity},
     pop::Pops,
     terrain::Terrain,
-    GalaxyEntityId,
+    GalaxyEntityId, Time,
 };
 
+mod farming;
+mod landmass;
+mod landmass_id;
+pub mod layout;
+mod local_market;
 pub mod map_view;
+mod pathfinding;
 mod planet;
 mod planet_id;
+mod port;
 mod producer;
+mod production;
 mod region;
+mod region_hierarchy;
 mod region_id;
+mod region_specialty;
+mod retailer;
+mod road;
+mod route_cache;
+mod spot;
+mod storage;
+mod trade_route;
+mod warehouse;
+mod zoning;
+
+#[cfg(test)]
+pub use self::{farming::HARVEST_PERIOD, planet::test_util};
 
 pub type LandSize = u16;
 
-/*
-Zoning determines how much land is allocated for a paricular purpose in a region:
-- farming
-- industry
-- commercial
-- residence
-- special
-*/
+/// Zoning determines how much land is allocated for a paricular purpose in a region.
+#[derive(Enum, Clone, Copy, Debug, PartialEq)]
+pub enum Zone {
+    Farming,
+    Industry,
+    Commercial,
+    Residence,
+    Special, // whatever the specialty of the region needs, e.g. launch pads
+}
+
+/// Land in a region that is zoned for one purpose and held by one owner.
+#[derive(Clone)]
+pub struct Plot {
+    zone: Zone,
+    owner: Ownership,
+    size: LandSize,
+    rezoning: Option<Rezoning>,
+}
+
+/// Works to change the zone of a plot; the plot keeps its old zone until they're done.
+#[derive(Clone, Copy, Debug)]
+pub struct Rezoning {
+    zone: Zone,
+    done_at: Time,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoningError {
+    NotAllowed, // by the specialty of the region
+    NotEnoughLand,
+    NoPlot,
+    AlreadyRezoning,
+    NotEnoughMaterials,
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum RegionSpecialty {
     // natural
@@ -57,11 +108,13 @@ impl Default for RegionSpecialty {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct RetailerStorage {
     avg_quality: u32,
     avg_brand: u32,
     quantity: Quantity,
+    price: Price,
+    sales: Quantity, // over the last market period
 }
 
 // rating = weighted sum of (quality + brand + price rating)
@@ -82,25 +135,21 @@ i.e. permanently stationed stores
 */
 #[derive(Clone)]
 pub struct Retailer {
+    ownership: Ownership,
     manager: Option<CharacterId>,
     // how many physical stores -> coverage
     num_stores: u32,
     storage: EnumMap<Commodity, RetailerStorage>,
 }
 
-impl Retailer {
-    pub fn cal_market_penetration(&self) -> u32 {
-        0
-    }
-}
-
 #[derive(Clone, Default)]
 pub struct Storage(EnumMap<Commodity, Quantity>);
 
 #[derive(Clone)]
 pub struct Warehouse {
-    scale: u32,
-    quality: Quality,
+    ownership: Ownership,
+    scale: u32,       // sets the capacity
+    quality: Quality, // how well goods keep
     storage: Storage,
 }
 
@@ -111,10 +160,27 @@ pub struct Warehouse {
 pub struct LocalMarket {
     scale: u32,
     storage: Storage,
+    prices: EnumMap<Commodity, Price>,
+    deliveries: EnumMap<Commodity, Quantity>, // since the last repricing
+    production: EnumMap<Commodity, Quantity>, // delivered in the last market period
 }
 
 pub type PlanetCoor = Point2<f64>;
 
+/// How the edges of a planet's map connect to each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wrapping {
+    None,       // flat rectangle, all edges are hard walls
+    Horizontal, // east & west edges are connected, like a cylinder
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PlanetConfig {
+    pub layout: LayoutKind,
+    pub wrapping: Wrapping,
+    pub model_width: Option<f64>, // picked randomly if not set
+}
+
 #[derive(Clone)]
 pub enum CrimeKind {
     Theft,
@@ -178,6 +244,8 @@ pub struct Civic {
 // Hill -> Terrace
 // Mountain -> Mine
 //
+// owned_land is the part of the spot bought up by investors, which develops it once it's all owned
+#[derive(Clone, Debug, PartialEq)]
 pub enum Spot {
     Arcology {
         population: u32,
@@ -222,6 +290,7 @@ pub struct Region {
     centroid_coor: PlanetCoor,
     border_vertices: Vec<PlanetCoor>,
     neighbours: HashSet<RegionId>,
+    border_lengths: HashMap<RegionId, f64>, // length of the edge shared with each neighbour
     noise: f64,
     terrain: Terrain,
 
@@ -229,17 +298,191 @@ pub struct Region {
     stability: u32,
 
     local_market: LocalMarket,
+    retailers: Vec<Retailer>,
+    warehouses: Vec<Warehouse>,
+
+    plots: Vec<Plot>,
+    spots: Vec<Spot>,
 
     farms: HashMap<Ownership, Farm>,
+    factories: Vec<Factory>,
+
+    has_port: bool,
+    fisheries: Vec<Fishery>, // at ports, fishing the shallow water around
+    fish_stock: f64,         // of shallow water
 
     pops: Pops,
 
     crime: HashMap<CharacterId, HashMap<CrimeKind, u32>>,
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Serialize)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Serialize)]
 pub struct RegionId(usize);
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LandmassKind {
+    Continent,
+    Island,
+    Ocean,
+    Sea,
+}
+
+/// A connected component of either land or water regions.
+pub struct Landmass {
+    name: String,
+    kind: LandmassKind,
+    regions: Vec<RegionId>,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Serialize)]
+pub struct LandmassId(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RoadKind {
+    Trail,
+    Road,
+    Highway,
+    Rail,
+}
+
+/// A road between the centroids of 2 neighbouring regions.
+#[derive(Clone, Copy, Debug)]
+pub struct Road {
+    kind: RoadKind,
+    wear: Time, // ticks since the road was built or last maintained
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PortError {
+    NotCoastal,
+    NoPort,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FarmError {
+    NotEnoughFarmland, // zoned for farming & held by the owner
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProductionError {
+    NotAllowed, // by the specialty of the region
+    NotEnoughWorkers,
+    MissingInputs,
+    MissingTools,
+}
+
+/// Where a trade route picks up or drops off goods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeEndpoint {
+    Market(RegionId),
+    Warehouse(RegionId, usize), // index of the warehouse in the region
+}
+
+/// Caravans regularly hauling a commodity between 2 regions of a planet.
+#[derive(Clone)]
+pub struct TradeRoute {
+    owner: Ownership, // independent routes are run by merchants, for the price difference
+    commodity: Commodity,
+    from: TradeEndpoint,
+    to: TradeEndpoint,
+    route: Route,
+    versions: (u64, u64), // of the planet & the roads when the route was found
+    capacity: Quantity,   // per shipment
+    is_open: bool,        // closed routes still finish the shipments on the way
+    shipments: Vec<Shipment>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Shipment {
+    quantity: Quantity,
+    arrives_at: Time,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeRouteError {
+    SameRegion,
+    NoRoute,
+    NoWarehouse,
+    NotOwner, // of the warehouse
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarehouseError {
+    NoWarehouse,
+    SameWarehouse,
+    DifferentOwners,
+    NotEnoughGoods,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpotError {
+    NoSpot,
+    CannotDevelop,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoadError {
+    NotNeighbours,
+    Impassable, // one of the regions can't be walked on
+    NoRoad,
+    FullyUpgraded,
+}
+
+/// How a mover gets around, which decides the regions it can cross.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mobility {
+    Foot,
+    CoastalShip, // early ships that stay in shallow water
+    OceanShip,
+}
+
+/// Path between 2 regions, including both ends.
+#[derive(Clone, Debug)]
+pub struct Route {
+    regions: Vec<RegionId>,
+    travel_time: f64, // in ticks
+}
+
+/// Speed multipliers for crossing from a region to a neighbour, e.g. along roads.
+#[derive(Default)]
+pub struct RoadBonus {
+    speeds: HashMap<(RegionId, RegionId), f64>, // stored in both directions
+    max_speed: f64,
+    version: u64, // bumped on every change
+}
+
+/// Fastest times from every region to a target, and the next step to take towards it.
+pub struct DistanceField {
+    times: Vec<f64>, // infinite when the target can't be reached
+    next_steps: Vec<Option<RegionId>>,
+}
+
+/// A small connected group of walkable regions.
+struct RegionCluster {
+    center: PlanetCoor,          // centroid of the first region
+    foot_cost: f64,              // average over the regions
+    neighbours: BTreeSet<usize>, // ordered, so searches over the clusters are repeatable
+}
+
+/**
+Coarse view of the walkable regions. Regions are grouped into clusters, which are grouped into
+components that can't be walked out of.
+*/
+pub struct RegionHierarchy {
+    clusters: Vec<RegionCluster>,
+    region_clusters: Vec<Option<usize>>, // None for regions that can't be walked on
+    cluster_components: Vec<usize>,
+}
+
+/// Routing data of a planet that is expensive to build, dropped when the terrain or roads change.
+#[derive(Default)]
+pub struct RouteCache {
+    versions: (u64, u64),   // of the planet & the roads when the data was built
+    targets: Vec<RegionId>, // whose distance fields are kept built
+    fields: HashMap<RegionId, DistanceField>,
+    hierarchy: Option<RegionHierarchy>,
+}
+
 struct RegionIndexData {
     // index of the region in the planet
     index: usize,
@@ -251,9 +494,17 @@ pub struct Planet {
     name: String,
     regions: Vec<Region>,
     region_index: RTree<RegionIndexData>,
+    landmasses: Vec<Landmass>,
+    region_landmasses: Vec<LandmassId>, // indexed by region id
     model_width: f64,
     model_height: f64,
+    wrapping: Wrapping,
+    route_version: u64, // bumped whenever the terrain changes
+    roads: HashMap<(RegionId, RegionId), Road>, // keyed by the lower region id first
+    road_bonus: RoadBonus,
+    trade_routes: Vec<TradeRoute>,
+    station: Storage, // goods in orbit, waiting to land
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct PlanetId(usize);
@@ -0,0 +1,323 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use ordered_float::OrderedFloat;
+
+use super::{Mobility, Planet, RegionId, RoadBonus, Route};
+use crate::Time;
+
+// distance walked on plains per tick, in model units
+pub(super) const WALKING_SPEED: f64 = 0.05;
+
+impl Route {
+    pub fn get_regions(&self) -> &[RegionId] {
+        &self.regions
+    }
+
+    pub fn get_travel_time(&self) -> Time {
+        self.travel_time.ceil() as Time
+    }
+}
+
+impl RoadBonus {
+    /// Speeds below 1 are raised to 1, as a road never slows travellers down.
+    pub fn insert(&mut self, a: RegionId, b: RegionId, speed: f64) {
+        let speed = speed.max(1.);
+        self.speeds.insert((a, b), speed);
+        self.speeds.insert((b, a), speed);
+        self.max_speed = self.max_speed.max(speed);
+        self.version += 1;
+    }
+
+    pub fn get_speed(&self, from: RegionId, to: RegionId) -> f64 {
+        self.speeds.get(&(from, to)).copied().unwrap_or(1.)
+    }
+
+    pub fn get_max_speed(&self) -> f64 {
+        self.max_speed.max(1.)
+    }
+
+    pub fn get_version(&self) -> u64 {
+        self.version
+    }
+}
+
+impl Planet {
+    /**
+    How many times longer it takes to cross the region than walking on plains, or None if it can't
+    be crossed. Ships can only go on land to dock at a port.
+    */
+    pub fn get_crossing_cost(&self, region_id: RegionId, mobility: Mobility) -> Option<f64> {
+        let region = &self.regions[usize::from(region_id)];
+        match mobility {
+            Mobility::Foot => region.terrain.get_foot_cost(),
+            Mobility::CoastalShip | Mobility::OceanShip if region.has_port => Some(1.),
+            Mobility::CoastalShip => region.terrain.get_sailing_cost(false),
+            Mobility::OceanShip => region.terrain.get_sailing_cost(true),
+        }
+    }
+
+    /// Ticks it takes to walk from a region to its neighbour.
+    pub fn get_step_time(
+        &self,
+        from: RegionId,
+        to: RegionId,
+        roads: Option<&RoadBonus>,
+    ) -> Option<f64> {
+        self.get_step_time_by(Mobility::Foot, from, to, roads)
+    }
+
+    /**
+    Ticks it takes to go from a region to its neighbour, before vehicle speed-ups: the distance
+    between the centroids, with each half of the way slowed down by the region it is in. None if
+    either region can't be crossed.
+    */
+    pub fn get_step_time_by(
+        &self,
+        mobility: Mobility,
+        from: RegionId,
+        to: RegionId,
+        roads: Option<&RoadBonus>,
+    ) -> Option<f64> {
+        let from_region = &self.regions[usize::from(from)];
+        let to_region = &self.regions[usize::from(to)];
+        let terrain_cost =
+            (self.get_crossing_cost(from, mobility)? + self.get_crossing_cost(to, mobility)?) / 2.;
+        let road_speed = match mobility {
+            Mobility::Foot => roads.map_or(1., |roads| roads.get_speed(from, to)),
+            Mobility::CoastalShip | Mobility::OceanShip => 1.,
+        };
+        let distance = self.get_distance(from_region.centroid_coor, to_region.centroid_coor);
+
+        Some(distance / WALKING_SPEED * terrain_cost / road_speed)
+    }
+
+    /**
+    Finds the fastest route on foot with A*. The heuristic is the straight-line walking time at the
+    best possible speed (plains, fastest road), so the route is optimal.
+    */
+    pub fn find_route(
+        &self,
+        from: RegionId,
+        to: RegionId,
+        roads: Option<&RoadBonus>,
+    ) -> Option<Route> {
+        self.find_route_by(Mobility::Foot, from, to, roads)
+    }
+
+    /// Fastest route for the mobility, e.g. a sea route between ports for ships.
+    pub fn find_route_by(
+        &self,
+        mobility: Mobility,
+        from: RegionId,
+        to: RegionId,
+        roads: Option<&RoadBonus>,
+    ) -> Option<Route> {
+        self.find_route_with(mobility, from, to, roads, 1., |_, _| 1.)
+    }
+
+    /**
+    Fastest route for a vehicle whose speed changes along the way, e.g. a car that is only fast on
+    good roads. Steps are sped up by `get_speed`, which never goes above `max_speed`.
+    */
+    pub fn find_route_with(
+        &self,
+        mobility: Mobility,
+        from: RegionId,
+        to: RegionId,
+        roads: Option<&RoadBonus>,
+        max_speed: f64,
+        get_speed: impl Fn(RegionId, RegionId) -> f64,
+    ) -> Option<Route> {
+        let max_speed = WALKING_SPEED * roads.map_or(1., RoadBonus::get_max_speed) * max_speed;
+        let get_step_time = |a, b| {
+            self.get_step_time_by(mobility, a, b, roads)
+                .map(|step_time| step_time / get_speed(a, b))
+        };
+        self.find_route_within(mobility, from, to, max_speed, get_step_time, |_| true)
+    }
+
+    /**
+    A* that only goes through the allowed regions. `max_speed` is the fastest any step can be
+    crossed at, in model units per tick.
+    */
+    pub(super) fn find_route_within(
+        &self,
+        mobility: Mobility,
+        from: RegionId,
+        to: RegionId,
+        max_speed: f64,
+        get_step_time: impl Fn(RegionId, RegionId) -> Option<f64>,
+        is_allowed: impl Fn(RegionId) -> bool,
+    ) -> Option<Route> {
+        let goal = self.regions[usize::from(to)].centroid_coor;
+        let heuristic = |region_id: RegionId| {
+            self.get_distance(self.regions[usize::from(region_id)].centroid_coor, goal) / max_speed
+        };
+
+        self.get_crossing_cost(from, mobility)?;
+
+        let mut best_times = vec![f64::INFINITY; self.regions.len()];
+        best_times[usize::from(from)] = 0.;
+        let mut came_from = HashMap::new();
+        let mut open = BinaryHeap::from([(Reverse(OrderedFloat(heuristic(from))), from)]);
+
+        while let Some((Reverse(OrderedFloat(estimate)), current)) = open.pop() {
+            let time = best_times[usize::from(current)];
+            if current == to {
+                return Some(Route {
+                    regions: trace_back(&came_from, to),
+                    travel_time: time,
+                });
+            }
+            // skip the outdated entries of regions that were reached faster later on
+            if estimate > time + heuristic(current) {
+                continue;
+            }
+            // ships only dock at ports on their way, they don't sail on through land
+            let is_docked = mobility != Mobility::Foot
+                && current != from
+                && self.regions[usize::from(current)].terrain.is_land();
+            if is_docked {
+                continue;
+            }
+
+            for &neighbour in &self.regions[usize::from(current)].neighbours {
+                if !is_allowed(neighbour) {
+                    continue;
+                }
+                let step_time = match get_step_time(current, neighbour) {
+                    Some(step_time) => step_time,
+                    None => continue,
+                };
+                let new_time = time + step_time;
+
+                // ties go to the lower region id, so the same route is found every time
+                let best_time = best_times[usize::from(neighbour)];
+                let is_tie_won = new_time == best_time
+                    && came_from
+                        .get(&neighbour)
+                        .is_some_and(|&previous| current < previous);
+                if is_tie_won {
+                    came_from.insert(neighbour, current);
+                } else if new_time < best_time {
+                    best_times[usize::from(neighbour)] = new_time;
+                    came_from.insert(neighbour, current);
+                    open.push((
+                        Reverse(OrderedFloat(new_time + heuristic(neighbour))),
+                        neighbour,
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn trace_back(came_from: &HashMap<RegionId, RegionId>, to: RegionId) -> Vec<RegionId> {
+    let mut ret = vec![to];
+    let mut current = to;
+    while let Some(&previous) = came_from.get(&current) {
+        ret.push(previous);
+        current = previous;
+    }
+    ret.reverse();
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RoadBonus, WALKING_SPEED};
+    use crate::planet::{test_util::ids, Planet, RegionId, Wrapping};
+
+    #[test]
+    fn goes_around_mountains() {
+        #[rustfmt::skip]
+        let planet = Planet::new_test_map(&[
+            "...",
+            ".M.",
+            "...",
+        ], Wrapping::None);
+
+        let route = planet
+            .find_route(RegionId::new_unsafe(3), RegionId::new_unsafe(5), None)
+            .unwrap();
+        assert_eq!(route.get_regions().len(), 5);
+        assert!(!route.get_regions().contains(&RegionId::new_unsafe(4)));
+
+        // 4 steps of 1.25 on plains
+        let expected = 4. * 1.25 / WALKING_SPEED;
+        assert!((route.travel_time - expected).abs() < 1e-9);
+        assert_eq!(route.get_travel_time(), expected.ceil() as u64);
+    }
+
+    #[test]
+    fn deep_ocean_is_impassable() {
+        #[rustfmt::skip]
+        let planet = Planet::new_test_map(&[
+            ".O.",
+            ".O.",
+            ".O.",
+        ], Wrapping::None);
+
+        assert!(planet
+            .find_route(RegionId::new_unsafe(0), RegionId::new_unsafe(2), None)
+            .is_none());
+        assert!(planet
+            .find_route(RegionId::new_unsafe(0), RegionId::new_unsafe(1), None)
+            .is_none());
+
+        // but a wrapping planet can be walked around
+        let planet = Planet::new_test_map(&[".O.", ".O.", ".O."], Wrapping::Horizontal);
+        let route = planet
+            .find_route(RegionId::new_unsafe(0), RegionId::new_unsafe(2), None)
+            .unwrap();
+        assert_eq!(route.get_regions(), ids(&[0, 2]));
+    }
+
+    #[test]
+    fn roads_are_preferred() {
+        #[rustfmt::skip]
+        let planet = Planet::new_test_map(&[
+            "....",
+            "....",
+            "....",
+        ], Wrapping::None);
+
+        let mut roads = RoadBonus::default();
+        for (a, b) in [(4, 0), (0, 1), (1, 2), (2, 3), (3, 7)] {
+            roads.insert(RegionId::new_unsafe(a), RegionId::new_unsafe(b), 4.);
+        }
+
+        let from = RegionId::new_unsafe(4);
+        let to = RegionId::new_unsafe(7);
+        let walk = planet.find_route(from, to, None).unwrap();
+        assert_eq!(walk.get_regions(), ids(&[4, 5, 6, 7]));
+
+        let drive = planet.find_route(from, to, Some(&roads)).unwrap();
+        assert_eq!(drive.get_regions(), ids(&[4, 0, 1, 2, 3, 7]));
+        assert!(drive.travel_time < walk.travel_time);
+    }
+
+    #[test]
+    fn hills_cost_more_than_plains() {
+        #[rustfmt::skip]
+        let planet = Planet::new_test_map(&[
+            "...",
+            "hhh",
+            "MMM",
+        ], Wrapping::None);
+
+        let time = |a, b| {
+            planet
+                .find_route(RegionId::new_unsafe(a), RegionId::new_unsafe(b), None)
+                .unwrap()
+                .travel_time
+        };
+        assert!(time(0, 2) < time(3, 5));
+        assert!(time(3, 5) < time(6, 8));
+    }
+}
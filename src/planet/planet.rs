@@ -1,15 +1,20 @@
+use std::collections::HashMap;
+
+use nalgebra::Vector2;
 use noise::{
     utils::{NoiseMapBuilder, PlaneMapBuilder},
     Fbm, Seedable,
 };
 use ordered_float::OrderedFloat;
-use poisson_diskus::bridson;
-use rand::Rng;
+use rand::{prelude::SliceRandom, Rng};
 use rstar::{PointDistance, RTree, RTreeObject, AABB};
-use voronoice::{BoundingBox, VoronoiBuilder};
 
-use super::{Planet, RegionId, RegionIndexData};
+use super::{
+    landmass::detect_landmasses, Landmass, LandmassId, Planet, PlanetConfig, RegionId,
+    RegionIndexData, Storage, Wrapping,
+};
 use crate::{
+    naming::{Culture, NameGenerator},
     planet::{PlanetCoor, Region, RegionSpecialty},
     terrain::Terrain,
     GalaxyEntityId,
@@ -38,53 +43,53 @@ impl Planet {
         &self.regions
     }
 
-    pub fn new(creation_rng: &mut dyn rand_core::RngCore, id: GalaxyEntityId) -> Self {
-        let model_width = creation_rng.gen_range(20.0..50.0);
-        let model_height = model_width * 0.7;
+    pub fn get_region_mut(&mut self, region_id: RegionId) -> &mut Region {
+        &mut self.regions[usize::from(region_id)]
+    }
 
-        let sites: Vec<_> = {
-            let box_size = [model_width, model_height];
-            // let rmin = 1.;
-            let rmin = 1.;
-            let num_attempts = 30;
-            let use_pbc = false;
-
-            bridson(&box_size, rmin, num_attempts, use_pbc)
-                .expect("The map site wasn't generated")
-                .into_iter()
-                .map(|[x, y]| voronoice::Point { x, y })
-                .collect()
-        };
+    /// Goods that came in from other planets & are waiting in orbit to land.
+    pub fn get_station(&self) -> &Storage {
+        &self.station
+    }
 
-        // builds a voronoi diagram from the set of sites above, bounded by a square of size 4
-        let graph = VoronoiBuilder::default()
-            .set_sites(sites)
-            .set_bounding_box(BoundingBox::new(
-                voronoice::Point {
-                    x: model_width / 2.,
-                    y: model_height / 2.,
-                },
-                model_width,
-                model_height,
-            ))
-            .set_lloyd_relaxation_iterations(2)
-            .build()
-            .unwrap();
+    pub fn get_station_mut(&mut self) -> &mut Storage {
+        &mut self.station
+    }
 
-        let num_sites = graph.sites().len();
+    pub fn new(
+        creation_rng: &mut dyn rand_core::RngCore,
+        id: GalaxyEntityId,
+        config: &PlanetConfig,
+        names: &NameGenerator,
+    ) -> Self {
+        let model_width = config
+            .model_width
+            .unwrap_or_else(|| creation_rng.gen_range(MIN_MODEL_WIDTH..MAX_MODEL_WIDTH));
+        let model_height = model_width * 0.7;
+        let wrapping = config.wrapping;
+        let is_wrapping = wrapping == Wrapping::Horizontal;
+
+        let cells = config.layout.get_layout().build_cells(
+            creation_rng,
+            model_width,
+            model_height,
+            wrapping,
+        );
+
+        let num_sites = cells.len();
 
         // build the height map with library
         let fbm = Fbm::new().set_seed(creation_rng.gen());
         let base_noise_map = PlaneMapBuilder::new(&fbm)
             .set_size(model_width as usize, model_height as usize)
-            .set_is_seamless(false)
+            .set_is_seamless(is_wrapping)
             .build();
 
         // extract the library model
-        let noise_map: Vec<_> = graph
-            .iter_cells()
+        let noise_map: Vec<_> = cells
+            .iter()
             .map(|cell| {
-                let pos = cell.site_position();
+                let pos = cell.centroid_coor();
                 // map coordinates for model sized boudning rectangle to the noise one
                 base_noise_map.get_value(pos.x as usize, pos.y as usize)
             })
@@ -130,38 +135,31 @@ impl Planet {
 
         let regions: Vec<_> = noise_map
             .iter()
-            .zip(graph.iter_cells())
-            .map(|(&noise, cell)| {
-                let centroid_coor = cell.site_position();
-                let centroid_coor: PlanetCoor = PlanetCoor::new(centroid_coor.x, centroid_coor.y);
-
-                let border_vertices = cell
-                    .iter_vertices()
-                    .cloned()
-                    .into_iter()
-                    .map(|point| PlanetCoor::new(point.x, point.y))
-                    .collect();
-
-                let neighbours = cell.iter_neighbors().map(RegionId::new_unsafe).collect();
-
-                let terrain = noise_to_terrain(noise);
-
-                Region {
-                    centroid_coor,
-                    border_vertices,
-                    neighbours,
-                    noise,
-                    terrain,
-                    specialty: RegionSpecialty::Uncivilized,
-                    stability: 0,
-                    local_market: Default::default(),
-                    farms: Default::default(),
-                    pops: Default::default(),
-                    crime: Default::default(),
-                }
-            })
+            .zip(cells)
+            .map(|(&noise, cell)| Region::new(cell, noise, noise_to_terrain(noise)))
             .collect();
 
+        Self::assemble(
+            creation_rng,
+            id,
+            names,
+            regions,
+            model_width,
+            model_height,
+            wrapping,
+        )
+    }
+
+    /// Indexes the regions, then finds the landmasses, borders & spaceports.
+    fn assemble(
+        creation_rng: &mut dyn rand_core::RngCore,
+        id: GalaxyEntityId,
+        names: &NameGenerator,
+        regions: Vec<Region>,
+        model_width: f64,
+        model_height: f64,
+        wrapping: Wrapping,
+    ) -> Self {
         let region_index = RTree::bulk_load(
             regions
                 .iter()
@@ -173,27 +171,208 @@ impl Planet {
                 .collect(),
         );
 
-        let name = format!("Earth {}", creation_rng.next_u32());
+        let culture = Culture::random(creation_rng);
+        let (landmasses, region_landmasses) =
+            detect_landmasses(&regions, creation_rng, names, culture);
 
-        Self {
+        let name = names.planet(creation_rng, culture);
+
+        let mut planet = Self {
             id,
             name,
             regions,
             region_index,
+            landmasses,
+            region_landmasses,
             model_width,
             model_height,
+            wrapping,
+            route_version: 0,
+            roads: Default::default(),
+            road_bonus: Default::default(),
+            trade_routes: Default::default(),
+            station: Default::default(),
+        };
+        planet.measure_shared_borders();
+        planet.build_spaceports(creation_rng);
+        planet.build_spaceport_road();
+        planet.build_initial_ports(creation_rng);
+        planet.grow_wilderness(creation_rng);
+        planet
+    }
+
+    /**
+    Changes the terrain of a region, e.g. by terraforming, which invalidates the cached routes.
+    Landmasses are not detected again.
+    */
+    pub fn set_terrain(&mut self, region_id: RegionId, terrain: Terrain) {
+        self.regions[usize::from(region_id)].terrain = terrain;
+        self.route_version += 1;
+    }
+
+    pub fn get_route_version(&self) -> u64 {
+        self.route_version
+    }
+
+    /// Every planet starts with a rocket pad and a space elevator on random land regions.
+    fn build_spaceports(&mut self, creation_rng: &mut dyn rand_core::RngCore) {
+        let mut land_indices = self.get_land_indices();
+        land_indices.shuffle(creation_rng);
+
+        let specialties = [RegionSpecialty::RocketPad, RegionSpecialty::SpaceElevator];
+        for (region_id, specialty) in land_indices.into_iter().zip(specialties) {
+            self.regions[usize::from(region_id)].specialty = specialty;
+        }
+    }
+
+    /// Links the spaceports with a trail, if they can be walked between.
+    fn build_spaceport_road(&mut self) {
+        if let [a, b, ..] = self.get_spaceports()[..] {
+            if let Some(route) = self.find_route(a, b, None) {
+                self.build_road_along(route.get_regions())
+                    .expect("routes should only go through walkable neighbours");
+            }
+        }
+    }
+
+    pub fn get_spaceports(&self) -> Vec<RegionId> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| region.specialty.is_spaceport())
+            .map(|(index, _)| RegionId::new_unsafe(index))
+            .collect()
+    }
+
+    /**
+    Finds the neighbour across each edge of every region. A voronoi edge is the perpendicular
+    bisector of the 2 sites, so the neighbour's centroid is the reflection of the region's own
+    centroid across the edge. Edges on the map boundary have no neighbour and are skipped.
+    */
+    fn measure_shared_borders(&mut self) {
+        let border_lengths: Vec<HashMap<RegionId, f64>> = self
+            .regions
+            .iter()
+            .map(|region| {
+                let centroid = region.centroid_coor;
+                let mut ret = HashMap::new();
+
+                for (a, b) in region.iter_edges() {
+                    let edge = b - a;
+                    let length = edge.norm();
+                    if length < EDGE_EPSILON {
+                        continue;
+                    }
+
+                    let t = (centroid - a).dot(&edge) / edge.norm_squared();
+                    let foot = a + edge * t;
+                    let reflection = foot + (foot - centroid);
+
+                    let across = region
+                        .neighbours
+                        .iter()
+                        .map(|&neighbour_id| {
+                            let neighbour = &self.regions[usize::from(neighbour_id)];
+                            let offset = self.get_displacement(centroid, neighbour.centroid_coor);
+                            (
+                                neighbour_id,
+                                nalgebra::distance(&(centroid + offset), &reflection),
+                            )
+                        })
+                        .min_by_key(|&(_, distance)| OrderedFloat(distance));
+
+                    if let Some((neighbour_id, distance)) = across {
+                        if distance < EDGE_EPSILON * length.max(1.) {
+                            *ret.entry(neighbour_id).or_insert(0.) += length;
+                        }
+                    }
+                }
+                ret
+            })
+            .collect();
+
+        self.regions
+            .iter_mut()
+            .zip(border_lengths)
+            .for_each(|(region, border_lengths)| region.border_lengths = border_lengths);
+    }
+
+    pub fn get_wrapping(&self) -> Wrapping {
+        self.wrapping
+    }
+
+    pub fn is_wrapping(&self) -> bool {
+        self.wrapping == Wrapping::Horizontal
+    }
+
+    /// Moves a coordinate that went past the east-west seam back onto the map.
+    pub fn wrap_coor(&self, coor: PlanetCoor) -> PlanetCoor {
+        match self.wrapping {
+            Wrapping::None => coor,
+            Wrapping::Horizontal => PlanetCoor::new(coor.x.rem_euclid(self.model_width), coor.y),
+        }
+    }
+
+    /// The shortest vector from one coordinate to another, which may cross the seam.
+    pub fn get_displacement(&self, from: PlanetCoor, to: PlanetCoor) -> Vector2<f64> {
+        let mut ret = to - from;
+        if self.is_wrapping() {
+            let half_width = self.model_width / 2.;
+            if ret.x > half_width {
+                ret.x -= self.model_width;
+            } else if ret.x < -half_width {
+                ret.x += self.model_width;
+            }
         }
+        ret
+    }
+
+    pub fn get_distance(&self, from: PlanetCoor, to: PlanetCoor) -> f64 {
+        self.get_displacement(from, to).norm()
     }
 
     pub fn find_region_id(&self, coor: PlanetCoor) -> RegionId {
-        let index = self
-            .region_index
-            .nearest_neighbor(&[coor.x, coor.y])
-            .expect("should find at least 1 region")
-            .index;
+        let nearest = |x: f64| {
+            let point = [x, coor.y];
+            let data = self
+                .region_index
+                .nearest_neighbor(&point)
+                .expect("should find at least 1 region");
+            (data.index, data.distance_2(&point))
+        };
+
+        let index = match self.wrapping {
+            Wrapping::None => nearest(coor.x).0,
+            Wrapping::Horizontal => {
+                // the nearest centroid might be on the other side of the seam
+                let x = coor.x.rem_euclid(self.model_width);
+                [x - self.model_width, x, x + self.model_width]
+                    .into_iter()
+                    .map(nearest)
+                    .min_by_key(|&(_, distance)| OrderedFloat(distance))
+                    .expect("should find at least 1 region")
+                    .0
+            }
+        };
         RegionId::new_unsafe(index)
     }
 
+    pub fn landmasses(&self) -> &[Landmass] {
+        &self.landmasses
+    }
+
+    pub fn get_landmass(&self, landmass_id: LandmassId) -> &Landmass {
+        &self.landmasses[usize::from(landmass_id)]
+    }
+
+    pub fn get_landmass_id(&self, region_id: RegionId) -> LandmassId {
+        self.region_landmasses[usize::from(region_id)]
+    }
+
+    pub fn find_landmass(&self, region_id: RegionId) -> &Landmass {
+        self.get_landmass(self.get_landmass_id(region_id))
+    }
+
     pub fn get_land_indices(&self) -> Vec<RegionId> {
         self.regions
             .iter()
@@ -204,6 +383,13 @@ impl Planet {
     }
 }
 
+// bounds of the random planet width, in model units
+const MIN_MODEL_WIDTH: f64 = 20.;
+pub(super) const MAX_MODEL_WIDTH: f64 = 50.;
+
+// tolerance for floating point errors when matching edges
+const EDGE_EPSILON: f64 = 1e-6;
+
 fn get_min_max(data: &[f64]) -> (f64, f64) {
     if data.is_empty() {
         unreachable!("noise array should have at least 1 value");
@@ -220,3 +406,239 @@ fn get_min_max(data: &[f64]) -> (f64, f64) {
         }
     })
 }
+
+#[cfg(test)]
+impl Planet {
+    /// Small planet on a square grid with deterministic generation, for tests.
+    pub fn new_test_grid(model_width: f64, wrapping: Wrapping) -> Self {
+        use rand::{prelude::StdRng, SeedableRng};
+
+        let config = PlanetConfig {
+            layout: super::layout::LayoutKind::SquareGrid,
+            wrapping,
+            model_width: Some(model_width),
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        Self::new(
+            &mut rng,
+            GalaxyEntityId::default(),
+            &config,
+            &NameGenerator::default(),
+        )
+    }
+
+    /**
+    Planet on a square grid with the terrain drawn by the rows: `.` plains, `h` hills, `M`
+    mountains, `~` shallow water and `O` deep ocean. Region ids go row by row.
+    */
+    pub fn new_test_map(rows: &[&str], wrapping: Wrapping) -> Self {
+        use rand::{prelude::StdRng, SeedableRng};
+
+        use super::layout::{LayoutKind, GRID_CELL_SIZE};
+
+        let terrains: Vec<_> = rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .map(|c| match c {
+                '.' => Terrain::Plain,
+                'h' => Terrain::Hill,
+                'M' => Terrain::Mountain,
+                '~' => Terrain::ShallowWater,
+                'O' => Terrain::DeepOcean,
+                _ => panic!("unknown terrain {}", c),
+            })
+            .collect();
+
+        let model_width = rows[0].len() as f64 * GRID_CELL_SIZE;
+        let model_height = rows.len() as f64 * GRID_CELL_SIZE;
+        let mut rng = StdRng::seed_from_u64(0);
+        let cells = LayoutKind::SquareGrid.get_layout().build_cells(
+            &mut rng,
+            model_width,
+            model_height,
+            wrapping,
+        );
+        assert_eq!(
+            cells.len(),
+            terrains.len(),
+            "the map should be at least 3x3"
+        );
+
+        let regions = cells
+            .into_iter()
+            .zip(terrains)
+            .map(|(cell, terrain)| Region::new(cell, 0., terrain))
+            .collect();
+
+        Self::assemble(
+            &mut rng,
+            GalaxyEntityId::default(),
+            &NameGenerator::default(),
+            regions,
+            model_width,
+            model_height,
+            wrapping,
+        )
+    }
+}
+
+/// Shared fixtures for the tests, built on `Planet::new_test_map`.
+#[cfg(test)]
+pub mod test_util {
+    use crate::{
+        economy::Ownership,
+        planet::{Planet, Region, RegionId, RegionSpecialty, Spot, Wrapping, Zone},
+        terrain::Terrain,
+    };
+
+    pub fn id(index: usize) -> RegionId {
+        RegionId::new_unsafe(index)
+    }
+
+    pub fn ids(indices: &[usize]) -> Vec<RegionId> {
+        indices.iter().copied().map(id).collect()
+    }
+
+    /// Test map without the roads, ports, fisheries & wilderness placed at creation.
+    pub fn bare_map(rows: &[&str], wrapping: Wrapping) -> Planet {
+        let mut planet = Planet::new_test_map(rows, wrapping);
+        planet.roads.clear();
+        planet.road_bonus = Default::default();
+        for region in &mut planet.regions {
+            region.has_port = false;
+            region.fisheries.clear();
+            region.spots.clear();
+        }
+        planet
+    }
+
+    const PLAINS: [&str; 3] = ["...", "...", "..."];
+
+    /// Bare map, with the regions at the indices set up by `setup`.
+    fn map_with(rows: &[&str], indices: &[usize], setup: impl Fn(&mut Region)) -> Planet {
+        let mut planet = bare_map(rows, Wrapping::None);
+        for &index in indices {
+            setup(&mut planet.regions[index]);
+        }
+        planet
+    }
+
+    /// The middle region, 4, has the spots.
+    pub fn planet_with_spots(spots: Vec<Spot>) -> Planet {
+        map_with(&PLAINS, &[4], |region| region.spots = spots.clone())
+    }
+
+    /// A town of 1000 in the middle region, 4.
+    pub fn planet_with_town() -> Planet {
+        planet_with_spots(vec![Spot::Settlement {
+            population: 1000,
+            owned_land: 0,
+        }])
+    }
+
+    /// The middle region, 4, is rural.
+    pub fn rural_planet() -> Planet {
+        map_with(&PLAINS, &[4], |region| {
+            region.specialty = RegionSpecialty::Rural
+        })
+    }
+
+    /// Independent farms of 100 land & 10 workers in regions 3 and 4, on plains & hills.
+    pub fn farming_planet() -> Planet {
+        map_with(&["...", ".h.", "..."], &[3, 4], |region| {
+            region.specialty = RegionSpecialty::Rural;
+            region
+                .allocate_land(Zone::Farming, Ownership::Independent, 100)
+                .unwrap();
+            region
+                .establish_farm(Ownership::Independent, 100)
+                .unwrap()
+                .hire(10);
+        })
+    }
+
+    /// Turns the region into rural plains with a village of 100, and farms up to 100 of its land.
+    pub fn add_village_farm(region: &mut Region, ownership: Ownership) {
+        region.terrain = Terrain::Plain;
+        region.specialty = RegionSpecialty::Rural;
+        region.spots = vec![Spot::Settlement {
+            population: 100,
+            owned_land: 0,
+        }];
+        let land = region.get_free_land().min(100);
+        region
+            .allocate_land(Zone::Farming, ownership.clone(), land)
+            .unwrap();
+        region.establish_farm(ownership, land).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::layout::GRID_CELL_SIZE;
+    use crate::planet::{Planet, PlanetCoor, RegionId, Wrapping};
+
+    const MAP: [&str; 3] = ["....", "....", "...."];
+
+    #[test]
+    fn coordinates_wrap_across_the_seam() {
+        let planet = Planet::new_test_map(&MAP, Wrapping::Horizontal);
+        let width = 4. * GRID_CELL_SIZE;
+        let west = PlanetCoor::new(0.5, 1.);
+        let east = PlanetCoor::new(width - 0.5, 1.);
+
+        assert_eq!(planet.wrap_coor(PlanetCoor::new(-0.5, 1.)), east);
+        assert_eq!(planet.wrap_coor(PlanetCoor::new(width + 0.5, 1.)), west);
+        assert_eq!(planet.get_displacement(west, east).x, -1.);
+        assert_eq!(planet.get_displacement(east, west).x, 1.);
+        assert!((planet.get_distance(west, east) - 1.).abs() < 1e-9);
+
+        let flat = Planet::new_test_map(&MAP, Wrapping::None);
+        assert_eq!(flat.wrap_coor(PlanetCoor::new(-0.5, 1.)).x, -0.5);
+        assert!((flat.get_distance(west, east) - (width - 1.)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn regions_are_found_across_the_seam() {
+        let planet = Planet::new_test_map(&MAP, Wrapping::Horizontal);
+        let width = 4. * GRID_CELL_SIZE;
+        let find = |x: f64| usize::from(planet.find_region_id(PlanetCoor::new(x, 0.5)));
+
+        assert_eq!(find(0.1), 0);
+        assert_eq!(find(width - 0.1), 3);
+        // past the seam, the nearest centroid is on the other side of the map
+        assert_eq!(find(-0.1), 3);
+        assert_eq!(find(width + 0.1), 0);
+
+        let flat = Planet::new_test_map(&MAP, Wrapping::None);
+        let find = |x: f64| usize::from(flat.find_region_id(PlanetCoor::new(x, 0.5)));
+        assert_eq!(find(-0.1), 0);
+        assert_eq!(find(width + 0.1), 3);
+    }
+
+    #[test]
+    fn edge_regions_neighbour_across_the_seam() {
+        let planet = Planet::new_test_map(&MAP, Wrapping::Horizontal);
+        let flat = Planet::new_test_map(&MAP, Wrapping::None);
+        let neighbours = |planet: &Planet, a: usize, b: usize| {
+            planet.regions[a]
+                .neighbours
+                .contains(&RegionId::new_unsafe(b))
+        };
+
+        for (west, east) in [(0, 3), (4, 7), (8, 11)] {
+            assert!(neighbours(&planet, west, east));
+            assert!(neighbours(&planet, east, west));
+            assert!(!neighbours(&flat, west, east));
+        }
+        assert!(planet
+            .regions
+            .iter()
+            .all(|region| region.neighbours.len() >= 3));
+        assert!(
+            (planet.regions[0].get_shared_border_length(RegionId::new_unsafe(3)) - GRID_CELL_SIZE)
+                .abs()
+                < 1e-9
+        );
+    }
+}
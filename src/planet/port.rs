@@ -0,0 +1,228 @@
+use rand::prelude::SliceRandom;
+
+use super::{producer::fishery::Fishery, region::FISH_CAPACITY, Planet, PortError, RegionId};
+use crate::{
+    economy::{Commodity, Ownership},
+    terrain::Terrain,
+    Time,
+};
+
+// fisheries go out once per period
+const FISHING_PERIOD: Time = 24;
+// portion of the fish stock that is born each period, when the stock is far from the capacity
+const FISH_GROWTH_RATE: f64 = 0.05;
+// one port for this many coastal regions
+const COASTAL_REGIONS_PER_PORT: usize = 12;
+
+impl Planet {
+    /// Land regions next to water.
+    pub fn is_coastal(&self, region_id: RegionId) -> bool {
+        let region = &self.regions[usize::from(region_id)];
+        region.terrain.is_land()
+            && region
+                .neighbours
+                .iter()
+                .any(|&neighbour| !self.regions[usize::from(neighbour)].terrain.is_land())
+    }
+
+    pub fn build_port(&mut self, region_id: RegionId) -> Result<(), PortError> {
+        if !self.is_coastal(region_id) {
+            return Err(PortError::NotCoastal);
+        }
+        self.regions[usize::from(region_id)].has_port = true;
+        self.route_version += 1;
+        Ok(())
+    }
+
+    pub fn get_ports(&self) -> Vec<RegionId> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| region.has_port)
+            .map(|(index, _)| RegionId::new_unsafe(index))
+            .collect()
+    }
+
+    /// Shallow water around the port, where its fisheries go.
+    pub fn get_fishing_grounds(&self, port: RegionId) -> Vec<RegionId> {
+        self.regions[usize::from(port)]
+            .neighbours
+            .iter()
+            .copied()
+            .filter(|&neighbour| {
+                self.regions[usize::from(neighbour)].terrain == Terrain::ShallowWater
+            })
+            .collect()
+    }
+
+    pub fn build_fishery(&mut self, port: RegionId, fishery: Fishery) -> Result<(), PortError> {
+        let region = &mut self.regions[usize::from(port)];
+        if !region.has_port {
+            return Err(PortError::NoPort);
+        }
+        region.fisheries.push(fishery);
+        Ok(())
+    }
+
+    /// Gives some coastal regions a port with a small fishery.
+    pub(super) fn build_initial_ports(&mut self, creation_rng: &mut dyn rand_core::RngCore) {
+        let mut coastal: Vec<_> = (0..self.regions.len())
+            .map(RegionId::new_unsafe)
+            .filter(|&region_id| self.is_coastal(region_id))
+            .collect();
+        coastal.shuffle(creation_rng);
+
+        let num_ports = (coastal.len() / COASTAL_REGIONS_PER_PORT).max(1);
+        for region_id in coastal.into_iter().take(num_ports) {
+            self.build_port(region_id)
+                .expect("coastal regions should be able to have a port");
+            self.build_fishery(region_id, Fishery::new(Ownership::Independent, 2))
+                .expect("the port was just built");
+        }
+    }
+
+    /**
+    Every fishing period, the fish stocks grow logistically, then each fishery takes its catch out
+    of the grounds around its port and brings it to its owner's warehouses or the local market as meat.
+    */
+    pub fn progress_fisheries(&mut self, time: Time) {
+        if !time.is_multiple_of(FISHING_PERIOD) {
+            return;
+        }
+
+        for region in self.regions.iter_mut() {
+            if region.terrain == Terrain::ShallowWater {
+                let stock = region.fish_stock;
+                region.fish_stock += FISH_GROWTH_RATE * stock * (1. - stock / FISH_CAPACITY);
+            }
+        }
+
+        for port_index in 0..self.regions.len() {
+            if self.regions[port_index].fisheries.is_empty() {
+                continue;
+            }
+
+            let grounds = self.get_fishing_grounds(RegionId::new_unsafe(port_index));
+            let capacity: f64 = self.regions[port_index]
+                .fisheries
+                .iter()
+                .map(Fishery::get_capacity)
+                .sum();
+
+            let mut catch = 0.;
+            for ground in grounds {
+                let stock = &mut self.regions[usize::from(ground)].fish_stock;
+                let taken = stock.min(capacity - catch);
+                *stock -= taken;
+                catch += taken;
+            }
+
+            // shared by the fisheries by their capacity
+            let port = &mut self.regions[port_index];
+            let shares: Vec<_> = port
+                .fisheries
+                .iter_mut()
+                .map(|fishery| {
+                    let share = catch * fishery.get_capacity() / capacity;
+                    (fishery.get_ownership().clone(), fishery.land(share))
+                })
+                .collect();
+            for (ownership, meat) in shares {
+                port.deliver(&ownership, Commodity::Meat, meat);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fishery, PortError, FISHING_PERIOD};
+    use crate::{
+        economy::{Commodity, Ownership},
+        planet::{
+            producer::fishery::CATCH_PER_BOAT,
+            test_util::{bare_map, id},
+            Mobility, Planet, Wrapping,
+        },
+    };
+
+    #[test]
+    fn sea_routes() {
+        #[rustfmt::skip]
+        let mut planet = bare_map(&[
+            ".~O~.",
+            ".~O~.",
+            ".~~~.",
+        ], Wrapping::None);
+        assert_eq!(planet.build_port(id(6)), Err(PortError::NotCoastal));
+        for port in [0, 4, 5, 9] {
+            planet.build_port(id(port)).unwrap();
+        }
+
+        let regions = |mobility| {
+            planet
+                .find_route_by(mobility, id(0), id(4), None)
+                .map(|route| route.get_regions().to_vec())
+        };
+        let coastal = regions(Mobility::CoastalShip).unwrap();
+        assert!(!coastal.contains(&id(2)) && !coastal.contains(&id(7)));
+        // ships don't sail through the other ports
+        assert!(!coastal.contains(&id(5)) && !coastal.contains(&id(9)));
+        assert_eq!(
+            regions(Mobility::OceanShip).unwrap(),
+            vec![id(0), id(1), id(2), id(3), id(4)]
+        );
+
+        // no sea route from a region without port
+        assert!(planet
+            .find_route_by(Mobility::OceanShip, id(10), id(4), None)
+            .is_none());
+    }
+
+    #[test]
+    fn fisheries_catch_meat() {
+        #[rustfmt::skip]
+        let mut planet = bare_map(&[
+            "...",
+            "~~~",
+            "OOO",
+        ], Wrapping::None);
+        let port = id(1);
+        planet.build_port(port).unwrap();
+        assert_eq!(
+            planet.build_fishery(id(0), Fishery::new(Ownership::Independent, 1)),
+            Err(PortError::NoPort)
+        );
+        planet
+            .build_fishery(port, Fishery::new(Ownership::Independent, 3))
+            .unwrap();
+        assert_eq!(planet.get_fishing_grounds(port), vec![id(4)]);
+
+        let stock = planet.regions[4].get_fish_stock();
+        let market = |planet: &Planet| {
+            planet.regions[1]
+                .local_market
+                .get_storage()
+                .get(Commodity::Meat)
+        };
+
+        planet.progress_fisheries(0);
+        let meat = market(&planet);
+        assert_eq!(meat as f64, 3. * CATCH_PER_BOAT);
+        assert_eq!(planet.regions[4].get_fish_stock(), stock - meat as f64);
+
+        // the boats only go out once per period
+        planet.progress_fisheries(1);
+        assert_eq!(market(&planet), meat);
+
+        // catches too small to bring in add up
+        planet
+            .build_fishery(port, Fishery::new(Ownership::Independent, 3))
+            .unwrap();
+        for period in 1..=2 {
+            planet.regions[4].fish_stock = 1.;
+            planet.progress_fisheries(period * FISHING_PERIOD);
+        }
+        assert_eq!(market(&planet), meat + 2);
+    }
+}
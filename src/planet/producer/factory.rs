@@ -0,0 +1,78 @@
+use crate::{
+    economy::{Commodity, Ownership, Quantity, RecipeId},
+    planet::{ProductionError, Storage},
+    pop::PopCount,
+    Time,
+};
+
+/// Runs a recipe over & over, one cycle at a time.
+#[derive(Clone)]
+pub struct Factory {
+    ownership: Ownership,
+    recipe_id: RecipeId,
+    workers: PopCount,
+    elapsed: Option<Time>, // into the current cycle; None while waiting for inputs
+}
+
+impl Factory {
+    pub fn new(ownership: Ownership, recipe_id: RecipeId, workers: PopCount) -> Self {
+        Self {
+            ownership,
+            recipe_id,
+            workers,
+            elapsed: None,
+        }
+    }
+
+    pub fn get_ownership(&self) -> &Ownership {
+        &self.ownership
+    }
+
+    pub fn get_recipe_id(&self) -> RecipeId {
+        self.recipe_id
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.elapsed.is_some()
+    }
+
+    /**
+    Works on the current cycle for a tick. A cycle starts by taking the inputs out of the storage,
+    as long as the tools are there too, and returns the outputs once it's done.
+    */
+    pub fn progress(
+        &mut self,
+        storage: &mut Storage,
+    ) -> Result<Option<&'static [(Commodity, Quantity)]>, ProductionError> {
+        let recipe = self.recipe_id.get_recipe();
+
+        if self.elapsed.is_none() {
+            if self.workers < recipe.get_labor() {
+                return Err(ProductionError::NotEnoughWorkers);
+            }
+            let has_all = |goods: &[(Commodity, Quantity)]| {
+                goods
+                    .iter()
+                    .all(|&(commodity, quantity)| storage.get(commodity) >= quantity)
+            };
+            if !has_all(recipe.get_tools()) {
+                return Err(ProductionError::MissingTools);
+            }
+            if !has_all(recipe.get_inputs()) {
+                return Err(ProductionError::MissingInputs);
+            }
+            for &(commodity, quantity) in recipe.get_inputs() {
+                storage.take(commodity, quantity);
+            }
+            self.elapsed = Some(0);
+        }
+
+        let elapsed = self.elapsed.get_or_insert(0);
+        *elapsed += 1;
+        if *elapsed < recipe.get_time() {
+            return Ok(None);
+        }
+        self.elapsed = None;
+        Ok(Some(recipe.get_outputs()))
+    }
+}
@@ -1,17 +1,114 @@
-use std::collections::HashSet;
-
 use crate::{
-    character::CharacterId,
-    economy::{Ownership, Wage},
-    planet::LandSize,
+    economy::{Commodity, Money, Ownership, Quantity, Wage},
+    planet::{LandSize, Storage},
     pop::PopCount,
+    terrain::Terrain,
 };
 
 #[derive(Clone)]
 pub struct Farm {
-    workers: HashSet<CharacterId>,
     wage_rate: Wage,
     employed: PopCount,
     ownership: Ownership,
     owned_land: LandSize,
 }
+
+// crops a plot of plains yields each season, when fully worked
+const CROP_PER_LAND: f64 = 30.;
+// meat a plot of hills yields each season, when fully worked
+const MEAT_PER_LAND: f64 = 5.;
+// land a worker can tend
+const LAND_PER_WORKER: f64 = 10.;
+// land that can be fed by a unit of fertilizer each season, or worked with one tool
+const LAND_PER_FERTILIZER: f64 = 20.;
+const LAND_PER_TOOL: f64 = 50.;
+// extra crops with all the spots of the region turned into farmland
+const FARMLAND_BONUS: f64 = 1.;
+// extra yield with all the land fertilized, or with tools for every worker
+const FERTILIZER_BONUS: f64 = 0.5;
+const TOOL_BONUS: f64 = 0.25;
+
+impl Farm {
+    pub fn new(ownership: Ownership, owned_land: LandSize, wage_rate: Wage) -> Self {
+        Self {
+            wage_rate,
+            employed: 0,
+            ownership,
+            owned_land,
+        }
+    }
+
+    pub fn get_ownership(&self) -> &Ownership {
+        &self.ownership
+    }
+
+    pub fn get_owned_land(&self) -> LandSize {
+        self.owned_land
+    }
+
+    pub fn add_land(&mut self, land: LandSize) {
+        self.owned_land += land;
+    }
+
+    pub fn get_employed(&self) -> PopCount {
+        self.employed
+    }
+
+    pub fn get_workers_needed(&self) -> PopCount {
+        (self.owned_land as f64 / LAND_PER_WORKER).ceil() as PopCount
+    }
+
+    /// Takes on workers, up to what the land needs, in place of those of the last season.
+    pub fn hire(&mut self, pops: PopCount) {
+        self.employed = pops.min(self.get_workers_needed());
+    }
+
+    /// What the workers are paid for the season.
+    pub fn get_wages(&self) -> Money {
+        self.employed as Money * self.wage_rate.get_seasonal_pay()
+    }
+
+    /**
+    Harvests the season's crops & meat. Crops depend on the fertility of the terrain & on how much
+    of the region is farmland by its crop yield, and meat on the pastures. The farm spreads what fertilizer it needs from the storage, and works with the
+    tools in there without using them up. Both are pointless without the workers to use them.
+    */
+    pub fn harvest(
+        &self,
+        terrain: Terrain,
+        crop_yield: f64,
+        storage: &mut Storage,
+    ) -> (Quantity, Quantity) {
+        let land = self.owned_land as f64;
+        let labor = match self.get_workers_needed() {
+            0 => 0.,
+            needed => self.employed as f64 / needed as f64,
+        };
+
+        let fertilizer_needed = (land / LAND_PER_FERTILIZER).ceil() as Quantity;
+        let fertilizer = storage.take(Commodity::Fertilizer, fertilizer_needed);
+        let tools_needed = (land / LAND_PER_TOOL).ceil();
+        let bonus = |used: f64, needed: f64, max_bonus: f64| {
+            if needed > 0. {
+                1. + max_bonus * (used / needed).min(1.)
+            } else {
+                1.
+            }
+        };
+        let crop_multiplier = bonus(
+            fertilizer as f64,
+            fertilizer_needed as f64,
+            FERTILIZER_BONUS,
+        ) * bonus(
+            storage.get(Commodity::Tool) as f64,
+            tools_needed,
+            TOOL_BONUS,
+        );
+
+        let crop_multiplier = crop_multiplier * (1. + FARMLAND_BONUS * crop_yield);
+
+        let crop = land * CROP_PER_LAND * terrain.get_fertility() * crop_multiplier * labor;
+        let meat = land * MEAT_PER_LAND * terrain.get_pasture_quality() * labor;
+        (crop as Quantity, meat as Quantity)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::economy::{Ownership, Quantity};
+
+// fish caught by a boat every fishing period, when there is enough fish around
+pub const CATCH_PER_BOAT: f64 = 10.;
+
+#[derive(Clone)]
+pub struct Fishery {
+    ownership: Ownership,
+    boats: u32,
+    catch: f64, // the part of the catch too small to bring in yet
+}
+
+impl Fishery {
+    pub fn new(ownership: Ownership, boats: u32) -> Self {
+        Self {
+            ownership,
+            boats,
+            catch: 0.,
+        }
+    }
+
+    pub fn get_ownership(&self) -> &Ownership {
+        &self.ownership
+    }
+
+    pub fn get_boats(&self) -> u32 {
+        self.boats
+    }
+
+    /// Fish the boats can bring back in a period.
+    pub fn get_capacity(&self) -> f64 {
+        self.boats as f64 * CATCH_PER_BOAT
+    }
+
+    /// Brings in the whole fish of the catch, and keeps the rest for the next time.
+    pub fn land(&mut self, catch: f64) -> Quantity {
+        self.catch += catch;
+        let landed = self.catch.floor();
+        self.catch -= landed;
+        landed as Quantity
+    }
+}
@@ -1 +1,3 @@
+pub mod factory;
 pub mod farm;
+pub mod fishery;
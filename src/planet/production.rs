@@ -0,0 +1,169 @@
+use super::{
+    producer::factory::Factory, warehouse::with_supplies, Planet, ProductionError, Region,
+};
+
+impl Region {
+    pub fn get_factories(&self) -> &[Factory] {
+        &self.factories
+    }
+
+    /// Sets up a factory if its recipe can be run here, and returns its index.
+    pub fn build_factory(&mut self, factory: Factory) -> Result<usize, ProductionError> {
+        if !factory
+            .get_recipe_id()
+            .get_recipe()
+            .is_allowed_in(self.specialty)
+        {
+            return Err(ProductionError::NotAllowed);
+        }
+        self.factories.push(factory);
+        Ok(self.factories.len() - 1)
+    }
+}
+
+impl Planet {
+    /**
+    Runs the factories for a tick. Company factories take their inputs out of the company's
+    warehouses in the region, and the others out of the local market. Factories short of inputs
+    stay idle until they're there.
+    */
+    pub fn progress_factories(&mut self) {
+        for region in &mut self.regions {
+            let mut deliveries = Vec::new();
+            for factory in &mut region.factories {
+                let ownership = factory.get_ownership().clone();
+                let progress = with_supplies(
+                    &mut region.warehouses,
+                    &mut region.local_market,
+                    &ownership,
+                    |storage| factory.progress(storage),
+                );
+                if let Ok(Some(outputs)) = progress {
+                    deliveries.push((ownership, outputs));
+                }
+            }
+            for (ownership, outputs) in deliveries {
+                for &(commodity, quantity) in outputs {
+                    region.deliver(&ownership, commodity, quantity);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        economy::{recipe::tests::get_recipes, Commodity, CompanyId, Ownership, RecipeId},
+        planet::{
+            producer::factory::Factory, retailer::BASE_QUALITY, Planet, ProductionError,
+            RegionSpecialty, Warehouse, Wrapping,
+        },
+    };
+
+    fn find_recipe(name: &str) -> RecipeId {
+        get_recipes()
+            .find(|(_, recipe)| recipe.get_name() == name)
+            .map(|(recipe_id, _)| recipe_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn factories_run_recipes() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let region = &mut planet.regions[4];
+        let smithing = find_recipe("smithing");
+        let factory = Factory::new(Ownership::Independent, smithing, 10);
+
+        assert_eq!(
+            region.build_factory(factory.clone()),
+            Err(ProductionError::NotAllowed)
+        );
+        region.specialty = RegionSpecialty::Urban;
+        region.build_factory(factory).unwrap();
+
+        // waits for the inputs
+        planet.progress_factories();
+        assert!(!planet.regions[4].get_factories()[0].is_running());
+
+        let storage = planet.regions[4].local_market.get_storage_mut();
+        storage.add(Commodity::Metal, 3);
+        storage.add(Commodity::Wood, 1);
+        let time = smithing.get_recipe().get_time();
+        for _ in 0..time - 1 {
+            planet.progress_factories();
+        }
+        let market = &planet.regions[4].local_market;
+        assert_eq!(market.get_storage().get(Commodity::Metal), 1);
+        assert_eq!(market.get_storage().get(Commodity::Tool), 0);
+
+        planet.progress_factories();
+        let market = &planet.regions[4].local_market;
+        assert_eq!(market.get_storage().get(Commodity::Tool), 2);
+        // not enough for another cycle
+        planet.progress_factories();
+        assert!(!planet.regions[4].get_factories()[0].is_running());
+    }
+
+    #[test]
+    fn factories_need_workers_and_tools() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        planet.regions[4].specialty = RegionSpecialty::Industry;
+        let plate_rolling = find_recipe("plate rolling");
+        let storage = planet.regions[4].local_market.get_storage_mut();
+        storage.add(Commodity::Alloy, 10);
+
+        let mut factory = Factory::new(Ownership::Independent, plate_rolling, 1);
+        assert_eq!(
+            factory.progress(storage),
+            Err(ProductionError::NotEnoughWorkers)
+        );
+        let mut factory = Factory::new(Ownership::Independent, plate_rolling, 10);
+        assert_eq!(
+            factory.progress(storage),
+            Err(ProductionError::MissingTools)
+        );
+        storage.add(Commodity::Tool, 1);
+        assert_eq!(factory.progress(storage), Ok(None));
+        assert_eq!(storage.get(Commodity::Tool), 1);
+    }
+
+    #[test]
+    fn company_factories_use_their_warehouses() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let acme = Ownership::Company(CompanyId::new_unsafe(0));
+        let region = &mut planet.regions[4];
+        region.specialty = RegionSpecialty::Urban;
+        let smithing = find_recipe("smithing");
+        region
+            .build_factory(Factory::new(acme.clone(), smithing, 10))
+            .unwrap();
+        region
+            .local_market
+            .get_storage_mut()
+            .add(Commodity::Metal, 3);
+        region
+            .local_market
+            .get_storage_mut()
+            .add(Commodity::Wood, 1);
+
+        // the market's goods aren't theirs
+        planet.progress_factories();
+        assert!(!planet.regions[4].get_factories()[0].is_running());
+
+        let region = &mut planet.regions[4];
+        region.add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        region.add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        region.warehouses[0].store(Commodity::Metal, 1);
+        region.warehouses[1].store(Commodity::Metal, 2);
+        region.warehouses[1].store(Commodity::Wood, 1);
+        for _ in 0..smithing.get_recipe().get_time() {
+            planet.progress_factories();
+        }
+        let region = &planet.regions[4];
+        assert_eq!(region.get_stored(&acme, Commodity::Metal), 1);
+        assert_eq!(region.get_stored(&acme, Commodity::Wood), 0);
+        assert_eq!(region.get_stored(&acme, Commodity::Tool), 2);
+        assert_eq!(region.local_market.get_storage().get(Commodity::Metal), 3);
+    }
+}
@@ -1,11 +1,128 @@
-use super::{Region, RegionSpecialty};
+use super::{
+    layout::CellData, LandSize, LocalMarket, PlanetCoor, Region, RegionId, RegionSpecialty,
+};
+use crate::terrain::Terrain;
+
+// fish a shallow water region can hold
+pub(super) const FISH_CAPACITY: f64 = 5000.;
+
+// amount of land in a region with an area of 1 (squared model unit) of plains
+const LAND_PER_AREA: f64 = 1000.;
 
 impl Region {
-    pub fn get_base_building_capacity(&self) -> u32 {
-        10000
+    pub(super) fn new(cell: CellData, noise: f64, terrain: Terrain) -> Self {
+        let (centroid_coor, border_vertices, neighbours) = cell.into_parts();
+
+        Self {
+            centroid_coor,
+            border_vertices,
+            neighbours,
+            border_lengths: Default::default(),
+            noise,
+            terrain,
+            specialty: RegionSpecialty::Uncivilized,
+            stability: 0,
+            local_market: Default::default(),
+            retailers: Default::default(),
+            warehouses: Default::default(),
+            plots: Default::default(),
+            spots: Default::default(),
+            farms: Default::default(),
+            factories: Default::default(),
+            has_port: false,
+            fisheries: Default::default(),
+            fish_stock: match terrain {
+                Terrain::ShallowWater => FISH_CAPACITY,
+                _ => 0.,
+            },
+            pops: Default::default(),
+            crime: Default::default(),
+        }
+    }
+
+    /// Land that can be built on, which depends on the size & the terrain of the region.
+    pub fn get_land_capacity(&self) -> LandSize {
+        let land = self.area() * LAND_PER_AREA * self.terrain.get_usable_land_ratio();
+        land.min(LandSize::MAX as f64) as LandSize
+    }
+
+    pub fn get_local_market(&self) -> &LocalMarket {
+        &self.local_market
+    }
+
+    pub fn get_local_market_mut(&mut self) -> &mut LocalMarket {
+        &mut self.local_market
     }
 
     pub fn get_specialty(&self) -> RegionSpecialty {
         self.specialty
     }
+
+    pub fn has_port(&self) -> bool {
+        self.has_port
+    }
+
+    pub fn get_fish_stock(&self) -> f64 {
+        self.fish_stock
+    }
+
+    pub fn get_centroid(&self) -> PlanetCoor {
+        self.centroid_coor
+    }
+
+    /// Iterates the border edges as pairs of vertices, including the edge that closes the polygon.
+    pub fn iter_edges(&self) -> impl Iterator<Item = (PlanetCoor, PlanetCoor)> + '_ {
+        let bvs = &self.border_vertices;
+        bvs.iter()
+            .zip(bvs.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    /// Area of the border polygon, by the shoelace formula.
+    pub fn area(&self) -> f64 {
+        let twice_area: f64 = self.iter_edges().map(|(a, b)| a.x * b.y - b.x * a.y).sum();
+        twice_area.abs() / 2.
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        self.iter_edges()
+            .map(|(a, b)| nalgebra::distance(&a, &b))
+            .sum()
+    }
+
+    /// Length of the border shared with a neighbour; 0 if the regions aren't adjacent.
+    pub fn get_shared_border_length(&self, neighbour: RegionId) -> f64 {
+        self.border_lengths
+            .get(&neighbour)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::planet::{layout::GRID_CELL_SIZE, Planet, RegionId, Wrapping};
+
+    #[test]
+    fn square_cell_geometry() {
+        #[rustfmt::skip]
+        let planet = Planet::new_test_map(&[
+            "...",
+            ".h.",
+            "...",
+        ], Wrapping::None);
+        let center = &planet.get_regions()[4];
+
+        assert!((center.area() - GRID_CELL_SIZE * GRID_CELL_SIZE).abs() < 1e-9);
+        assert!((center.perimeter() - 4. * GRID_CELL_SIZE).abs() < 1e-9);
+        for neighbour in [1, 3, 5, 7] {
+            let shared = center.get_shared_border_length(RegionId::new_unsafe(neighbour));
+            assert!((shared - GRID_CELL_SIZE).abs() < 1e-9);
+        }
+        assert_eq!(center.get_shared_border_length(RegionId::new_unsafe(0)), 0.);
+
+        // the same area holds less usable land on hills
+        let plains = &planet.get_regions()[1];
+        assert!(center.get_land_capacity() < plains.get_land_capacity());
+    }
 }
@@ -0,0 +1,232 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, VecDeque},
+};
+
+use ordered_float::OrderedFloat;
+
+use super::{
+    pathfinding::WALKING_SPEED, Mobility, Planet, RegionCluster, RegionHierarchy, RegionId,
+    RoadBonus, Route,
+};
+
+// width of the squares the regions are binned into before being split into connected clusters
+const CLUSTER_SIZE: f64 = 6.;
+
+impl RegionHierarchy {
+    /**
+    Bins the walkable regions into squares, and splits each square into its connected parts, so a
+    cluster can always be crossed without leaving it.
+    */
+    pub fn new(planet: &Planet) -> Self {
+        let regions = &planet.regions;
+        let is_walkable = |index: usize| regions[index].terrain.get_foot_cost().is_some();
+        let bin = |index: usize| {
+            let coor = regions[index].centroid_coor;
+            (
+                (coor.x / CLUSTER_SIZE).floor() as i64,
+                (coor.y / CLUSTER_SIZE).floor() as i64,
+            )
+        };
+
+        let mut clusters: Vec<RegionCluster> = Vec::new();
+        let mut region_clusters = vec![None; regions.len()];
+
+        for start in 0..regions.len() {
+            if !is_walkable(start) || region_clusters[start].is_some() {
+                continue;
+            }
+
+            let cluster_index = clusters.len();
+            let start_bin = bin(start);
+            let mut members = Vec::new();
+            let mut queue = VecDeque::from([start]);
+            region_clusters[start] = Some(cluster_index);
+
+            while let Some(index) = queue.pop_front() {
+                members.push(RegionId::new_unsafe(index));
+                for &neighbour in &regions[index].neighbours {
+                    let neighbour = usize::from(neighbour);
+                    if region_clusters[neighbour].is_none()
+                        && is_walkable(neighbour)
+                        && bin(neighbour) == start_bin
+                    {
+                        region_clusters[neighbour] = Some(cluster_index);
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+
+            // summed in a fixed order, as neighbours are visited in no particular one
+            members.sort();
+            let foot_cost = members
+                .iter()
+                .filter_map(|&region_id| regions[usize::from(region_id)].terrain.get_foot_cost())
+                .sum::<f64>()
+                / members.len() as f64;
+
+            clusters.push(RegionCluster {
+                center: regions[start].centroid_coor,
+                foot_cost,
+                neighbours: BTreeSet::new(),
+            });
+        }
+
+        for (index, region) in regions.iter().enumerate() {
+            if let Some(cluster_index) = region_clusters[index] {
+                for &neighbour in &region.neighbours {
+                    match region_clusters[usize::from(neighbour)] {
+                        Some(other) if other != cluster_index => {
+                            clusters[cluster_index].neighbours.insert(other);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let cluster_components = find_components(&clusters);
+
+        Self {
+            clusters,
+            region_clusters,
+            cluster_components,
+        }
+    }
+
+    /// Whether the regions can be walked between at all.
+    pub fn is_connected(&self, from: RegionId, to: RegionId) -> bool {
+        match (
+            self.region_clusters[usize::from(from)],
+            self.region_clusters[usize::from(to)],
+        ) {
+            (Some(a), Some(b)) => self.cluster_components[a] == self.cluster_components[b],
+            _ => false,
+        }
+    }
+
+    /**
+    Finds a route by first finding the chain of clusters to go through, then searching regions in
+    those clusters and the ones around them only. Much faster than searching the whole planet, but
+    the route may be a little slower than the best one.
+    */
+    pub fn find_route(
+        &self,
+        planet: &Planet,
+        from: RegionId,
+        to: RegionId,
+        roads: Option<&RoadBonus>,
+    ) -> Option<Route> {
+        if !self.is_connected(from, to) {
+            return None;
+        }
+
+        let mut corridor = vec![false; self.clusters.len()];
+        for index in self.find_cluster_path(planet, from, to, roads)? {
+            corridor[index] = true;
+            for &neighbour in &self.clusters[index].neighbours {
+                corridor[neighbour] = true;
+            }
+        }
+
+        let max_speed = WALKING_SPEED * roads.map_or(1., RoadBonus::get_max_speed);
+        planet.find_route_within(
+            Mobility::Foot,
+            from,
+            to,
+            max_speed,
+            |a, b| planet.get_step_time(a, b, roads),
+            |region_id| {
+                self.region_clusters[usize::from(region_id)].is_some_and(|index| corridor[index])
+            },
+        )
+    }
+
+    /**
+    A* over the clusters, with the walking time between their centers as the cost. Ties go to the
+    lower cluster index, so the same path is found every time.
+    */
+    fn find_cluster_path(
+        &self,
+        planet: &Planet,
+        from: RegionId,
+        to: RegionId,
+        roads: Option<&RoadBonus>,
+    ) -> Option<Vec<usize>> {
+        let start = self.region_clusters[usize::from(from)]?;
+        let goal = self.region_clusters[usize::from(to)]?;
+        let max_speed = WALKING_SPEED * roads.map_or(1., RoadBonus::get_max_speed);
+        let goal_center = self.clusters[goal].center;
+        let heuristic = |index: usize| {
+            planet.get_distance(self.clusters[index].center, goal_center) / max_speed
+        };
+
+        let mut best_times = vec![f64::INFINITY; self.clusters.len()];
+        best_times[start] = 0.;
+        let mut came_from = vec![None; self.clusters.len()];
+        let mut open = BinaryHeap::from([(Reverse(OrderedFloat(heuristic(start))), start)]);
+
+        while let Some((Reverse(OrderedFloat(estimate)), current)) = open.pop() {
+            let time = best_times[current];
+            if current == goal {
+                let mut ret = vec![goal];
+                while let Some(previous) = came_from[*ret.last().unwrap()] {
+                    ret.push(previous);
+                }
+                return Some(ret);
+            }
+            if estimate > time + heuristic(current) {
+                continue;
+            }
+
+            let cluster = &self.clusters[current];
+            for &neighbour in &cluster.neighbours {
+                let other = &self.clusters[neighbour];
+                let distance = planet.get_distance(cluster.center, other.center);
+                let new_time =
+                    time + distance / WALKING_SPEED * (cluster.foot_cost + other.foot_cost) / 2.;
+
+                let best_time = best_times[neighbour];
+                let is_tie_won = new_time == best_time
+                    && came_from[neighbour].is_some_and(|previous| current < previous);
+                if is_tie_won {
+                    came_from[neighbour] = Some(current);
+                } else if new_time < best_time {
+                    best_times[neighbour] = new_time;
+                    came_from[neighbour] = Some(current);
+                    open.push((
+                        Reverse(OrderedFloat(new_time + heuristic(neighbour))),
+                        neighbour,
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Labels the connected components of the cluster graph.
+fn find_components(clusters: &[RegionCluster]) -> Vec<usize> {
+    let mut ret: Vec<Option<usize>> = vec![None; clusters.len()];
+    let mut num_components = 0;
+
+    for start in 0..clusters.len() {
+        if ret[start].is_some() {
+            continue;
+        }
+        ret[start] = Some(num_components);
+        let mut queue = VecDeque::from([start]);
+        while let Some(index) = queue.pop_front() {
+            for &neighbour in &clusters[index].neighbours {
+                if ret[neighbour].is_none() {
+                    ret[neighbour] = Some(num_components);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        num_components += 1;
+    }
+
+    ret.into_iter().flatten().collect()
+}
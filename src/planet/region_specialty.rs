@@ -0,0 +1,45 @@
+use super::RegionSpecialty;
+use crate::{economy::Quantity, Time};
+
+impl RegionSpecialty {
+    /// Number of passengers that can leave the planet from the region per tick.
+    pub fn get_launch_capacity(&self) -> u32 {
+        match self {
+            RegionSpecialty::RocketPad => 8,
+            RegionSpecialty::SpaceElevator => 64,
+            _ => 0,
+        }
+    }
+
+    /// Ticks it takes to get from the ground to orbit, or back.
+    pub fn get_launch_time(&self) -> Time {
+        match self {
+            RegionSpecialty::RocketPad => 12,
+            RegionSpecialty::SpaceElevator => 48, // the climbers are slow but carry a lot
+            _ => 0,
+        }
+    }
+
+    /// Goods that can be launched to or landed from orbit through the region per tick.
+    pub fn get_freight_throughput(&self) -> Quantity {
+        match self {
+            RegionSpecialty::RocketPad => 20,
+            RegionSpecialty::SpaceElevator => 500,
+            _ => 0,
+        }
+    }
+
+    /// Fuel burnt to get the goods to orbit.
+    pub fn get_launch_fuel(&self, quantity: Quantity) -> Quantity {
+        let per_piece = match self {
+            RegionSpecialty::RocketPad => 0.5,
+            RegionSpecialty::SpaceElevator => 0.02, // only to power the climbers
+            _ => 0.,
+        };
+        (quantity as f64 * per_piece).ceil() as Quantity
+    }
+
+    pub fn is_spaceport(&self) -> bool {
+        self.get_launch_capacity() > 0
+    }
+}
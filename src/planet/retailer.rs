@@ -0,0 +1,272 @@
+use enum_map::EnumMap;
+
+use super::{local_market::to_money, Region, Retailer, RetailerStorage};
+use crate::{
+    character::CharacterId,
+    economy::{Commodity, MarketConfig, Money, Ownership, Price, Quality, Quantity},
+};
+
+// weights of each part of a rating
+const QUALITY_WEIGHT: f64 = 1.;
+const BRAND_WEIGHT: f64 = 0.5;
+const PRICE_WEIGHT: f64 = 2.;
+// quality of ordinary goods, like the ones sold at the local market
+pub(super) const BASE_QUALITY: Quality = 100;
+// stores it takes to reach half of the shoppers in a region
+const STORES_FOR_HALF_COVERAGE: f64 = 10.;
+
+/**
+"intrinsic price" / retail price - 1, kept within -1 & 1 so a giveaway can't take the whole market.
+Goods that haven't been priced yet are rated as if they sold at the intrinsic price.
+*/
+pub(super) fn get_price_rating(intrinsic_price: Price, price: Price) -> f64 {
+    if price <= 0. {
+        return 0.;
+    }
+    (intrinsic_price / price - 1.).clamp(-1., 1.)
+}
+
+/// Weighted sum of quality, brand & price ratings; 0 for ordinary goods at the intrinsic price.
+pub(super) fn get_rating(quality: f64, brand: f64, price_rating: f64) -> f64 {
+    let base = BASE_QUALITY as f64;
+    QUALITY_WEIGHT * (quality / base - 1.)
+        + BRAND_WEIGHT * brand / base
+        + PRICE_WEIGHT * price_rating
+}
+
+/// Market penetration of a seller that reaches the portion of shoppers with goods of the rating.
+pub(super) fn get_penetration(coverage: f64, rating: f64) -> f64 {
+    coverage * rating.exp()
+}
+
+impl RetailerStorage {
+    pub fn get_quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    pub fn get_price(&self) -> Price {
+        self.price
+    }
+
+    pub fn get_sales(&self) -> Quantity {
+        self.sales
+    }
+
+    /// What the shoppers paid for the sales.
+    pub fn get_revenue(&self) -> Money {
+        to_money(self.sales as Price * self.price)
+    }
+
+    pub fn get_avg_quality(&self) -> Quality {
+        self.avg_quality
+    }
+
+    pub fn get_avg_brand(&self) -> u32 {
+        self.avg_brand
+    }
+}
+
+impl Retailer {
+    pub fn new(ownership: Ownership, manager: Option<CharacterId>, num_stores: u32) -> Self {
+        Self {
+            ownership,
+            manager,
+            num_stores,
+            storage: EnumMap::default(),
+        }
+    }
+
+    pub fn get_ownership(&self) -> &Ownership {
+        &self.ownership
+    }
+
+    pub fn get_storage(&self, commodity: Commodity) -> &RetailerStorage {
+        &self.storage[commodity]
+    }
+
+    pub fn iter_storage(&self) -> impl Iterator<Item = (Commodity, &RetailerStorage)> {
+        self.storage.iter()
+    }
+
+    /// Stocks the shelves, averaging the quality & brand with the goods already there.
+    pub fn stock(
+        &mut self,
+        commodity: Commodity,
+        quantity: Quantity,
+        quality: Quality,
+        brand: u32,
+    ) {
+        let storage = &mut self.storage[commodity];
+        let total = storage.quantity + quantity;
+        if total == 0 {
+            return;
+        }
+        let average = |current: u32, new: u32| {
+            ((current as u64 * storage.quantity as u64 + new as u64 * quantity as u64)
+                / total as u64) as u32
+        };
+        storage.avg_quality = average(storage.avg_quality, quality);
+        storage.avg_brand = average(storage.avg_brand, brand);
+        storage.quantity = total;
+    }
+
+    pub fn set_price(&mut self, commodity: Commodity, price: Price) {
+        self.storage[commodity].price = price;
+    }
+
+    /// Portion of the shoppers of the region who can get to one of the stores.
+    pub fn get_coverage(&self) -> f64 {
+        let stores = self.num_stores as f64;
+        stores / (stores + STORES_FOR_HALF_COVERAGE)
+    }
+
+    /// How much the retailer draws shoppers of the commodity; 0 if it has none to sell.
+    pub fn cal_market_penetration(&self, commodity: Commodity, intrinsic_price: Price) -> f64 {
+        let storage = &self.storage[commodity];
+        if storage.quantity == 0 {
+            return 0.;
+        }
+        let rating = get_rating(
+            storage.avg_quality as f64,
+            storage.avg_brand as f64,
+            get_price_rating(intrinsic_price, storage.price),
+        );
+        get_penetration(self.get_coverage(), rating)
+    }
+
+    /// Sells up to the demand out of the stock, and returns how much was sold.
+    fn sell(&mut self, commodity: Commodity, demand: f64) -> Quantity {
+        let storage = &mut self.storage[commodity];
+        let sold = (demand.round() as Quantity).min(storage.quantity);
+        storage.quantity -= sold;
+        storage.sales = sold;
+        sold
+    }
+}
+
+impl Region {
+    pub fn get_retailers(&self) -> &[Retailer] {
+        &self.retailers
+    }
+
+    /// Opens a retailer in the region, and returns its index, which stays its own for good.
+    pub fn add_retailer(&mut self, retailer: Retailer) -> usize {
+        self.retailers.push(retailer);
+        self.retailers.len() - 1
+    }
+
+    /**
+    Shares of the demand for the commodity that go to the local market & to each retailer, by
+    their portion of the total market penetration of the region.
+    */
+    pub fn get_market_shares(
+        &self,
+        commodity: Commodity,
+        config: &MarketConfig,
+    ) -> (f64, Vec<f64>) {
+        let intrinsic_price = config.get_intrinsic_price(commodity);
+        let market = self
+            .local_market
+            .cal_market_penetration(commodity, intrinsic_price);
+        let retailers: Vec<_> = self
+            .retailers
+            .iter()
+            .map(|retailer| retailer.cal_market_penetration(commodity, intrinsic_price))
+            .collect();
+
+        let total = market + retailers.iter().sum::<f64>();
+        if total <= 0. {
+            return (1., vec![0.; retailers.len()]);
+        }
+        (
+            market / total,
+            retailers
+                .iter()
+                .map(|penetration| penetration / total)
+                .collect(),
+        )
+    }
+
+    /// Retailers sell their shares of the demand, and the local market is repriced on the rest.
+    pub(super) fn progress_market(&mut self, config: &MarketConfig) {
+        let mut demands = super::local_market::get_demands(self.get_spot_population());
+        for (commodity, demand) in demands.iter_mut() {
+            let (market_share, retailer_shares) = self.get_market_shares(commodity, config);
+            for (retailer, share) in self.retailers.iter_mut().zip(retailer_shares) {
+                retailer.sell(commodity, *demand * share);
+            }
+            *demand *= market_share;
+        }
+        self.local_market.update_prices(&demands, config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BASE_QUALITY;
+    use crate::{
+        economy::{Commodity, MarketConfig, Ownership},
+        planet::{test_util::planet_with_town, Retailer},
+    };
+
+    #[test]
+    fn better_retailers_take_more_of_the_market() {
+        let mut planet = planet_with_town();
+        let config = MarketConfig::default();
+        let price = config.get_intrinsic_price(Commodity::Apparel);
+        let region = &mut planet.regions[4];
+
+        // nobody but the local market sells apparel yet
+        assert_eq!(region.get_market_shares(Commodity::Apparel, &config).0, 1.);
+
+        let mut plain = Retailer::new(Ownership::Independent, None, 10);
+        plain.stock(Commodity::Apparel, 100, BASE_QUALITY, 0);
+        plain.set_price(Commodity::Apparel, price);
+        let mut fancy = Retailer::new(Ownership::Independent, None, 10);
+        fancy.stock(Commodity::Apparel, 100, BASE_QUALITY * 2, 50);
+        fancy.set_price(Commodity::Apparel, price);
+        let mut cheap = Retailer::new(Ownership::Independent, None, 10);
+        cheap.stock(Commodity::Apparel, 100, BASE_QUALITY, 0);
+        cheap.set_price(Commodity::Apparel, price * 0.8);
+        region.add_retailer(plain);
+        region.add_retailer(fancy);
+        region.add_retailer(cheap);
+
+        let (market_share, shares) = region.get_market_shares(Commodity::Apparel, &config);
+        assert!(shares[1] > shares[0]);
+        assert!(shares[2] > shares[0]);
+        let total = market_share + shares.iter().sum::<f64>();
+        assert!((total - 1.).abs() < 1e-9);
+
+        // more stores reach more shoppers
+        let mut chain = Retailer::new(Ownership::Independent, None, 100);
+        chain.stock(Commodity::Apparel, 100, BASE_QUALITY, 0);
+        chain.set_price(Commodity::Apparel, price);
+        region.add_retailer(chain);
+        let (_, shares) = region.get_market_shares(Commodity::Apparel, &config);
+        assert!(shares[3] > shares[0]);
+    }
+
+    #[test]
+    fn retailers_sell_their_share() {
+        let mut planet = planet_with_town();
+        let config = MarketConfig::default();
+        let price = config.get_intrinsic_price(Commodity::Apparel);
+
+        let mut retailer = Retailer::new(Ownership::Independent, None, 50);
+        retailer.stock(Commodity::Apparel, 100, BASE_QUALITY * 2, 100);
+        retailer.set_price(Commodity::Apparel, price);
+        let region = &mut planet.regions[4];
+        region.add_retailer(retailer);
+        region.local_market.deliver(Commodity::Apparel, 100);
+
+        planet.progress_markets(0, &config);
+        let region = &planet.regions[4];
+        let storage = region.get_retailers()[0].get_storage(Commodity::Apparel);
+        // 1000 people want 50 pieces, mostly from the well-known retailer
+        assert!(storage.get_sales() > 25);
+        assert_eq!(storage.get_quantity(), 100 - storage.get_sales());
+        let market_sales = 100 - region.local_market.get_storage().get(Commodity::Apparel);
+        assert!((market_sales + storage.get_sales()).abs_diff(50) <= 1);
+    }
+}
@@ -0,0 +1,254 @@
+use super::{Planet, PlanetCoor, RegionId, Road, RoadBonus, RoadError, RoadKind};
+use crate::Time;
+
+impl RoadKind {
+    /// Multiplier of the walking speed along the road.
+    pub fn get_speed(&self) -> f64 {
+        match self {
+            RoadKind::Trail => 1.5,
+            RoadKind::Road => 2.5,
+            RoadKind::Highway => 4.,
+            RoadKind::Rail => 8.,
+        }
+    }
+
+    /// Ticks without maintenance before the road falls to the previous kind.
+    pub fn get_lifetime(&self) -> Time {
+        match self {
+            RoadKind::Trail => 2000,
+            RoadKind::Road => 5000,
+            RoadKind::Highway => 8000,
+            RoadKind::Rail => 12000,
+        }
+    }
+
+    pub fn get_upgrade(&self) -> Option<RoadKind> {
+        match self {
+            RoadKind::Trail => Some(RoadKind::Road),
+            RoadKind::Road => Some(RoadKind::Highway),
+            RoadKind::Highway => Some(RoadKind::Rail),
+            RoadKind::Rail => None,
+        }
+    }
+
+    pub fn get_downgrade(&self) -> Option<RoadKind> {
+        match self {
+            RoadKind::Trail => None,
+            RoadKind::Road => Some(RoadKind::Trail),
+            RoadKind::Highway => Some(RoadKind::Road),
+            RoadKind::Rail => Some(RoadKind::Highway),
+        }
+    }
+}
+
+impl Road {
+    pub fn get_kind(&self) -> RoadKind {
+        self.kind
+    }
+
+    /// Portion of the lifetime that is left, from 1 when new to 0 when about to decay.
+    pub fn get_condition(&self) -> f64 {
+        1. - self.wear as f64 / self.kind.get_lifetime() as f64
+    }
+}
+
+fn road_key(a: RegionId, b: RegionId) -> (RegionId, RegionId) {
+    (a.min(b), a.max(b))
+}
+
+impl Planet {
+    pub fn get_road(&self, a: RegionId, b: RegionId) -> Option<&Road> {
+        self.roads.get(&road_key(a, b))
+    }
+
+    pub fn iter_roads(&self) -> impl Iterator<Item = ((RegionId, RegionId), &Road)> {
+        self.roads.iter().map(|(&key, road)| (key, road))
+    }
+
+    /// Speeds of the roads, to be used for route finding.
+    pub fn get_road_bonus(&self) -> &RoadBonus {
+        &self.road_bonus
+    }
+
+    /// Builds a trail between neighbouring regions that can both be walked on.
+    pub fn build_road(&mut self, a: RegionId, b: RegionId) -> Result<&Road, RoadError> {
+        if !self.regions[usize::from(a)].neighbours.contains(&b) {
+            return Err(RoadError::NotNeighbours);
+        }
+        let is_walkable = |id: RegionId| {
+            self.regions[usize::from(id)]
+                .terrain
+                .get_foot_cost()
+                .is_some()
+        };
+        if !is_walkable(a) || !is_walkable(b) {
+            return Err(RoadError::Impassable);
+        }
+
+        let road = *self.roads.entry(road_key(a, b)).or_insert(Road {
+            kind: RoadKind::Trail,
+            wear: 0,
+        });
+        self.road_bonus.insert(a, b, road.kind.get_speed());
+        Ok(&self.roads[&road_key(a, b)])
+    }
+
+    /// Builds trails along the regions of a route.
+    pub fn build_road_along(&mut self, regions: &[RegionId]) -> Result<(), RoadError> {
+        for pair in regions.windows(2) {
+            self.build_road(pair[0], pair[1])?;
+        }
+        Ok(())
+    }
+
+    /// Turns the road into the next kind, as good as new.
+    pub fn upgrade_road(&mut self, a: RegionId, b: RegionId) -> Result<&Road, RoadError> {
+        let road = self
+            .roads
+            .get_mut(&road_key(a, b))
+            .ok_or(RoadError::NoRoad)?;
+        road.kind = road.kind.get_upgrade().ok_or(RoadError::FullyUpgraded)?;
+        road.wear = 0;
+        let speed = road.kind.get_speed();
+
+        self.road_bonus.insert(a, b, speed);
+        Ok(&self.roads[&road_key(a, b)])
+    }
+
+    pub fn maintain_road(&mut self, a: RegionId, b: RegionId) -> Result<(), RoadError> {
+        let road = self
+            .roads
+            .get_mut(&road_key(a, b))
+            .ok_or(RoadError::NoRoad)?;
+        road.wear = 0;
+        Ok(())
+    }
+
+    /// Wears the roads down by a tick. Worn out roads fall to the previous kind, and trails vanish.
+    pub fn progress_roads(&mut self) {
+        let mut decayed = Vec::new();
+        for (&key, road) in self.roads.iter_mut() {
+            road.wear += 1;
+            if road.wear >= road.kind.get_lifetime() {
+                decayed.push(key);
+            }
+        }
+
+        for (a, b) in decayed {
+            let road = self.roads[&(a, b)];
+            match road.kind.get_downgrade() {
+                Some(kind) => {
+                    self.roads.insert((a, b), Road { kind, wear: 0 });
+                    self.road_bonus.insert(a, b, kind.get_speed());
+                }
+                None => {
+                    self.roads.remove(&(a, b));
+                    self.road_bonus.insert(a, b, 1.);
+                }
+            }
+        }
+    }
+
+    /// Point on the road from a region's centroid to its neighbour's, where progress goes 0 to 1.
+    pub fn get_road_coor(&self, from: RegionId, to: RegionId, progress: f64) -> PlanetCoor {
+        let from = self.regions[usize::from(from)].centroid_coor;
+        let to = self.regions[usize::from(to)].centroid_coor;
+        self.wrap_coor(from + self.get_displacement(from, to) * progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RoadError, RoadKind};
+    use crate::planet::{
+        test_util::{bare_map, id},
+        Wrapping,
+    };
+
+    #[test]
+    fn build_and_upgrade() {
+        #[rustfmt::skip]
+        let mut planet = bare_map(&[
+            "..O",
+            "...",
+            "...",
+        ], Wrapping::None);
+
+        assert_eq!(
+            planet.build_road(id(0), id(2)).err(),
+            Some(RoadError::NotNeighbours)
+        );
+        assert_eq!(
+            planet.build_road(id(1), id(2)).err(),
+            Some(RoadError::Impassable)
+        );
+        assert_eq!(
+            planet.upgrade_road(id(0), id(1)).err(),
+            Some(RoadError::NoRoad)
+        );
+
+        assert_eq!(
+            planet.build_road(id(1), id(0)).unwrap().get_kind(),
+            RoadKind::Trail
+        );
+        for kind in [RoadKind::Road, RoadKind::Highway, RoadKind::Rail] {
+            assert_eq!(planet.upgrade_road(id(0), id(1)).unwrap().get_kind(), kind);
+        }
+        assert_eq!(
+            planet.upgrade_road(id(1), id(0)).err(),
+            Some(RoadError::FullyUpgraded)
+        );
+        assert_eq!(
+            planet.get_road_bonus().get_speed(id(1), id(0)),
+            RoadKind::Rail.get_speed()
+        );
+    }
+
+    #[test]
+    fn roads_decay_without_maintenance() {
+        let mut planet = bare_map(&["...", "...", "..."], Wrapping::None);
+        planet.build_road(id(0), id(1)).unwrap();
+        planet.upgrade_road(id(0), id(1)).unwrap();
+
+        for _ in 0..RoadKind::Road.get_lifetime() - 1 {
+            planet.progress_roads();
+        }
+        planet.maintain_road(id(0), id(1)).unwrap();
+        for _ in 0..RoadKind::Road.get_lifetime() {
+            planet.progress_roads();
+        }
+        let road = planet.get_road(id(0), id(1)).unwrap();
+        assert_eq!(road.get_kind(), RoadKind::Trail);
+        assert_eq!(road.get_condition(), 1.);
+
+        for _ in 0..RoadKind::Trail.get_lifetime() {
+            planet.progress_roads();
+        }
+        assert!(planet.get_road(id(0), id(1)).is_none());
+        assert_eq!(planet.get_road_bonus().get_speed(id(0), id(1)), 1.);
+    }
+
+    #[test]
+    fn roads_speed_up_routes() {
+        let mut planet = bare_map(&["...", "...", "..."], Wrapping::None);
+        let before = planet
+            .find_route(id(0), id(2), Some(planet.get_road_bonus()))
+            .unwrap();
+        planet.build_road_along(before.get_regions()).unwrap();
+        let after = planet
+            .find_route(id(0), id(2), Some(planet.get_road_bonus()))
+            .unwrap();
+        assert!(after.get_travel_time() < before.get_travel_time());
+    }
+
+    #[test]
+    fn road_coor_crosses_the_seam() {
+        let mut planet = bare_map(&["...", "...", "..."], Wrapping::Horizontal);
+        planet.build_road(id(0), id(2)).unwrap();
+
+        // halfway between the west-most & east-most columns is the west edge of the map
+        let coor = planet.get_road_coor(id(0), id(2), 0.5);
+        assert!(coor.x.abs() < 1e-9);
+        assert!((coor.y - planet.regions[0].get_centroid().y).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,342 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use ordered_float::OrderedFloat;
+
+use super::{DistanceField, Planet, RegionHierarchy, RegionId, RoadBonus, Route, RouteCache};
+
+// the biggest towns of a planet kept with distance fields, on top of the spaceports
+const MARKET_TARGETS: usize = 10;
+
+impl DistanceField {
+    /// Dijkstra outwards from the target. Steps cost the same both ways, so the times found are
+    /// also the times to reach the target.
+    pub fn new(planet: &Planet, target: RegionId, roads: Option<&RoadBonus>) -> Self {
+        let num_regions = planet.regions.len();
+        let mut times = vec![f64::INFINITY; num_regions];
+        let mut next_steps = vec![None; num_regions];
+
+        if planet.regions[usize::from(target)]
+            .terrain
+            .get_foot_cost()
+            .is_some()
+        {
+            times[usize::from(target)] = 0.;
+        }
+        let mut open = BinaryHeap::from([(Reverse(OrderedFloat(0.)), target)]);
+
+        while let Some((Reverse(OrderedFloat(time)), current)) = open.pop() {
+            if time > times[usize::from(current)] {
+                continue;
+            }
+
+            for &neighbour in &planet.regions[usize::from(current)].neighbours {
+                let step_time = match planet.get_step_time(neighbour, current, roads) {
+                    Some(step_time) => step_time,
+                    None => continue,
+                };
+                let new_time = time + step_time;
+                if new_time < times[usize::from(neighbour)] {
+                    times[usize::from(neighbour)] = new_time;
+                    next_steps[usize::from(neighbour)] = Some(current);
+                    open.push((Reverse(OrderedFloat(new_time)), neighbour));
+                }
+            }
+        }
+
+        Self { times, next_steps }
+    }
+
+    /// Ticks to walk to the target, or None if it can't be reached.
+    pub fn get_time(&self, from: RegionId) -> Option<f64> {
+        Some(self.times[usize::from(from)]).filter(|time| time.is_finite())
+    }
+
+    /// The neighbour to move to next, or None at the target or if it can't be reached.
+    pub fn get_next_step(&self, from: RegionId) -> Option<RegionId> {
+        self.next_steps[usize::from(from)]
+    }
+
+    pub fn get_route(&self, from: RegionId) -> Option<Route> {
+        let travel_time = self.get_time(from)?;
+        let mut regions = vec![from];
+        while let Some(next) = self.get_next_step(*regions.last().unwrap()) {
+            regions.push(next);
+        }
+
+        Some(Route {
+            regions,
+            travel_time,
+        })
+    }
+}
+
+impl Planet {
+    /// Where most movers head to: the spaceports, the capital and the other big market towns.
+    fn get_route_targets(&self) -> Vec<RegionId> {
+        let mut targets = self.get_spaceports();
+        for town in self.get_towns().into_iter().take(MARKET_TARGETS) {
+            if !targets.contains(&town) {
+                targets.push(town);
+            }
+        }
+        targets
+    }
+}
+
+impl RouteCache {
+    /**
+    Drops everything if the planet or the roads changed since the data was built. The fields to the
+    prepared targets are built again right away, the others once they are asked for.
+    */
+    fn validate(&mut self, planet: &Planet, roads: Option<&RoadBonus>) {
+        let versions = (
+            planet.get_route_version(),
+            roads.map_or(0, RoadBonus::get_version),
+        );
+        if versions != self.versions {
+            // clusters only depend on which regions are walkable, i.e. on the terrain
+            if versions.0 != self.versions.0 {
+                self.hierarchy = None;
+            }
+            self.fields = self
+                .targets
+                .iter()
+                .map(|&target| (target, DistanceField::new(planet, target, roads)))
+                .collect();
+            self.versions = versions;
+        }
+    }
+
+    /// Distance field to the target, built on first use.
+    pub fn get_field(
+        &mut self,
+        planet: &Planet,
+        roads: Option<&RoadBonus>,
+        target: RegionId,
+    ) -> &DistanceField {
+        self.validate(planet, roads);
+        self.fields
+            .entry(target)
+            .or_insert_with(|| DistanceField::new(planet, target, roads))
+    }
+
+    /**
+    Keeps the distance fields to where most movers head to built, even as the terrain & roads
+    change. Prepared again as the towns grow.
+    */
+    pub fn prepare(&mut self, planet: &Planet, roads: Option<&RoadBonus>) {
+        self.targets = planet.get_route_targets();
+        for target in self.targets.clone() {
+            self.get_field(planet, roads, target);
+        }
+    }
+
+    pub fn get_hierarchy(
+        &mut self,
+        planet: &Planet,
+        roads: Option<&RoadBonus>,
+    ) -> &RegionHierarchy {
+        self.validate(planet, roads);
+        self.hierarchy
+            .get_or_insert_with(|| RegionHierarchy::new(planet))
+    }
+
+    /**
+    Route to the target, read from its distance field when it has been built, or else found
+    through the region hierarchy.
+    */
+    pub fn find_route(
+        &mut self,
+        planet: &Planet,
+        roads: Option<&RoadBonus>,
+        from: RegionId,
+        to: RegionId,
+    ) -> Option<Route> {
+        self.validate(planet, roads);
+        if let Some(field) = self.fields.get(&to) {
+            return field.get_route(from);
+        }
+        self.get_hierarchy(planet, roads)
+            .find_route(planet, from, to, roads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use rand::{prelude::StdRng, seq::SliceRandom, SeedableRng};
+
+    use super::{DistanceField, RouteCache};
+    use crate::{
+        naming::NameGenerator,
+        planet::{
+            layout::LayoutKind, planet::MAX_MODEL_WIDTH, test_util::planet_with_town, Planet,
+            PlanetConfig, RegionId, RoadBonus, Wrapping,
+        },
+        terrain::Terrain,
+        GalaxyEntityId,
+    };
+
+    /// Hex grid, as voronoi sites aren't reproducible from the seed.
+    fn big_planet() -> Planet {
+        let config = PlanetConfig {
+            layout: LayoutKind::HexGrid,
+            wrapping: Wrapping::Horizontal,
+            model_width: Some(MAX_MODEL_WIDTH),
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        Planet::new(
+            &mut rng,
+            GalaxyEntityId::default(),
+            &config,
+            &NameGenerator::default(),
+        )
+    }
+
+    #[test]
+    fn field_matches_a_star() {
+        #[rustfmt::skip]
+        let planet = Planet::new_test_map(&[
+            "..hM.",
+            ".OOh.",
+            "..~..",
+        ], Wrapping::None);
+        let target = RegionId::new_unsafe(4);
+        let field = DistanceField::new(&planet, target, None);
+
+        for index in 0..planet.regions.len() {
+            let from = RegionId::new_unsafe(index);
+            let route = planet.find_route(from, target, None);
+            assert_eq!(field.get_time(from).is_some(), route.is_some());
+
+            if let (Some(route), Some(field_route)) = (route, field.get_route(from)) {
+                assert!((route.travel_time - field_route.travel_time).abs() < 1e-9);
+                assert_eq!(field_route.get_regions().first(), Some(&from));
+                assert_eq!(field_route.get_regions().last(), Some(&target));
+            }
+        }
+    }
+
+    #[test]
+    fn invalidated_by_terrain_and_roads() {
+        #[rustfmt::skip]
+        let mut planet = Planet::new_test_map(&[
+            "...",
+            "...",
+            "...",
+        ], Wrapping::None);
+        let (from, to) = (RegionId::new_unsafe(0), RegionId::new_unsafe(2));
+        let mut roads = RoadBonus::default();
+        let mut cache = RouteCache::default();
+
+        let time = |cache: &mut RouteCache, planet: &Planet, roads: &RoadBonus| {
+            cache
+                .get_field(planet, Some(roads), to)
+                .get_time(from)
+                .unwrap()
+        };
+
+        let plain = time(&mut cache, &planet, &roads);
+        planet.set_terrain(RegionId::new_unsafe(1), Terrain::Mountain);
+        let detour = time(&mut cache, &planet, &roads);
+        assert!(detour > plain);
+
+        roads.insert(RegionId::new_unsafe(0), RegionId::new_unsafe(1), 10.);
+        roads.insert(RegionId::new_unsafe(1), RegionId::new_unsafe(2), 10.);
+        assert!(time(&mut cache, &planet, &roads) < detour);
+    }
+
+    #[test]
+    fn prepared_fields_are_rebuilt() {
+        let mut planet = planet_with_town();
+        let mut cache = RouteCache::default();
+        cache.prepare(&planet, Some(planet.get_road_bonus()));
+        let town = RegionId::new_unsafe(4);
+        assert!(cache.fields.contains_key(&town));
+
+        planet.set_terrain(RegionId::new_unsafe(1), Terrain::Mountain);
+        let roads = planet.get_road_bonus();
+        cache.validate(&planet, Some(roads));
+        let field = &cache.fields[&town];
+        let from = RegionId::new_unsafe(0);
+        let exact = planet.find_route(from, town, Some(roads)).unwrap();
+        assert!((field.get_time(from).unwrap() - exact.travel_time).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hierarchy_finds_routes() {
+        let planet = big_planet();
+        let mut cache = RouteCache::default();
+        let hierarchy = cache.get_hierarchy(&planet, None);
+        assert!(hierarchy.clusters.len() < planet.regions.len());
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let land = planet.get_land_indices();
+        for _ in 0..50 {
+            let from = *land.choose(&mut rng).unwrap();
+            let to = *land.choose(&mut rng).unwrap();
+
+            let exact = planet.find_route(from, to, None);
+            let rough = hierarchy.find_route(&planet, from, to, None);
+            assert_eq!(exact.is_some(), rough.is_some());
+            if let (Some(exact), Some(rough)) = (exact, rough) {
+                assert!(rough.travel_time >= exact.travel_time - 1e-9);
+                assert!(rough.travel_time <= exact.travel_time * 1.5);
+            }
+        }
+    }
+
+    /// Run with `cargo test --release bench_route_finding -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_route_finding() {
+        let planet = big_planet();
+        let land = planet.get_land_indices();
+        let mut rng = StdRng::seed_from_u64(1);
+        let pairs: Vec<_> = (0..1000)
+            .map(|_| {
+                (
+                    *land.choose(&mut rng).unwrap(),
+                    *land.choose(&mut rng).unwrap(),
+                )
+            })
+            .collect();
+
+        let start = Instant::now();
+        pairs.iter().for_each(|&(from, to)| {
+            planet.find_route(from, to, None);
+        });
+        let a_star = start.elapsed();
+
+        // through the hierarchy, as nothing has been prepared
+        let mut cache = RouteCache::default();
+        let start = Instant::now();
+        pairs.iter().for_each(|&(from, to)| {
+            cache.find_route(&planet, None, from, to);
+        });
+        let hierarchical = start.elapsed();
+
+        // many movers heading to the same few places
+        let targets: Vec<_> = land.iter().take(10).copied().collect();
+        let start = Instant::now();
+        for &target in &targets {
+            cache.get_field(&planet, None, target);
+        }
+        pairs.iter().enumerate().for_each(|(index, &(from, _))| {
+            let target = targets[index % targets.len()];
+            cache.find_route(&planet, None, from, target);
+        });
+        let fields = start.elapsed();
+
+        println!(
+            "{} regions, {} routes: A* {:?}, hierarchical {:?}, distance fields to {} targets {:?}",
+            planet.regions.len(),
+            pairs.len(),
+            a_star,
+            hierarchical,
+            targets.len(),
+            fields
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use std::cmp::Reverse;
+
+use rand::Rng;
+
+use super::{Planet, Region, RegionId, RegionSpecialty, Spot, SpotError};
+use crate::{terrain::Terrain, Time};
+
+// land covered by a spot
+const SPOT_LAND: u32 = 100;
+// spots change once per period
+const SPOT_PERIOD: Time = 24;
+// chance for a spot on plains to start as a forest instead of grassland
+const FOREST_CHANCE: f64 = 0.4;
+// portions of the people & the animals born each period, when they are far from the capacity
+const POP_GROWTH_RATE: f64 = 0.02;
+const WILDLIFE_GROWTH_RATE: f64 = 0.1;
+// people who move into a newly developed farmland, terrace or mine
+const SETTLERS: u32 = 5;
+// forest animals driven away each period per person, by logging for wood
+const LOGGING_PER_PERSON: f64 = 0.05;
+// forests with less wildlife than this portion of the capacity are left as grassland
+const DEFORESTED_RATIO: f64 = 0.1;
+
+impl Spot {
+    /// Untouched nature of the terrain, as full of animals as it can be.
+    fn new_wild(terrain: Terrain, is_forest: bool) -> Option<Self> {
+        let mut spot = match terrain {
+            Terrain::Plain if is_forest => Spot::Forest {
+                wildlife_population: 0,
+                owned_land: 0,
+            },
+            Terrain::Plain => Spot::Grassland {
+                wildlife_population: 0,
+                owned_land: 0,
+            },
+            Terrain::Hill => Spot::Hill {
+                wildlife_population: 0,
+                owned_land: 0,
+            },
+            Terrain::Mountain => Spot::Mountain {
+                wildlife_population: 0,
+                owned_land: 0,
+            },
+            Terrain::ShallowWater | Terrain::DeepOcean => return None,
+        };
+        let capacity = spot.get_wildlife_capacity();
+        if let Some(wildlife) = spot.wildlife_mut() {
+            *wildlife = capacity;
+        }
+        Some(spot)
+    }
+
+    pub fn get_population(&self) -> u32 {
+        match self {
+            Spot::Arcology { population }
+            | Spot::Settlement { population, .. }
+            | Spot::Farmland { population, .. }
+            | Spot::Mine { population, .. }
+            | Spot::Terrace { population, .. } => *population,
+            _ => 0,
+        }
+    }
+
+    pub fn get_wildlife(&self) -> u32 {
+        match self {
+            Spot::Grassland {
+                wildlife_population,
+                ..
+            }
+            | Spot::Forest {
+                wildlife_population,
+                ..
+            }
+            | Spot::Hill {
+                wildlife_population,
+                ..
+            }
+            | Spot::Mountain {
+                wildlife_population,
+                ..
+            } => *wildlife_population,
+            _ => 0,
+        }
+    }
+
+    /// People who can live in the spot.
+    pub fn get_housing(&self) -> u32 {
+        match self {
+            Spot::Arcology { .. } => 2000,
+            Spot::Settlement { .. } => 200,
+            Spot::Farmland { .. } => 20,
+            Spot::Terrace { .. } => 15,
+            Spot::Mine { .. } => 10,
+            _ => 0,
+        }
+    }
+
+    pub fn get_wildlife_capacity(&self) -> u32 {
+        match self {
+            Spot::Forest { .. } => 200,
+            Spot::Hill { .. } => 80,
+            Spot::Grassland { .. } => 50,
+            Spot::Mountain { .. } => 40,
+            _ => 0,
+        }
+    }
+
+    /// How well the spot grows crops, from 0 for the wild to 1 for farmland.
+    pub fn get_crop_yield(&self) -> f64 {
+        match self {
+            Spot::Farmland { .. } => 1.,
+            Spot::Terrace { .. } => 0.6,
+            _ => 0.,
+        }
+    }
+
+    /// What the spot turns into once investors own all of its land, or None if it can't develop.
+    fn develop(&self, specialty: RegionSpecialty) -> Option<Spot> {
+        let developed = match *self {
+            Spot::Grassland { .. } => Spot::Farmland {
+                population: SETTLERS,
+                owned_land: 0,
+            },
+            // clearing a forest only leaves some of the animals around
+            Spot::Forest {
+                wildlife_population,
+                ..
+            } => Spot::Grassland {
+                wildlife_population: wildlife_population.min(
+                    Spot::Grassland {
+                        wildlife_population: 0,
+                        owned_land: 0,
+                    }
+                    .get_wildlife_capacity(),
+                ),
+                owned_land: 0,
+            },
+            Spot::Farmland { population, .. } => Spot::Settlement {
+                population,
+                owned_land: 0,
+            },
+            Spot::Settlement { population, .. } => Spot::Arcology { population },
+            Spot::Hill { .. } | Spot::Mountain { .. } if specialty == RegionSpecialty::Mining => {
+                Spot::Mine {
+                    population: SETTLERS,
+                    owned_land: 0,
+                }
+            }
+            Spot::Hill { .. } => Spot::Terrace {
+                population: SETTLERS,
+                owned_land: 0,
+            },
+            Spot::Mountain { .. } | Spot::Mine { .. } | Spot::Terrace { .. } => return None,
+            Spot::Arcology { .. } => return None,
+        };
+        Some(developed)
+    }
+
+    fn population_mut(&mut self) -> Option<&mut u32> {
+        match self {
+            Spot::Arcology { population }
+            | Spot::Settlement { population, .. }
+            | Spot::Farmland { population, .. }
+            | Spot::Mine { population, .. }
+            | Spot::Terrace { population, .. } => Some(population),
+            _ => None,
+        }
+    }
+
+    fn wildlife_mut(&mut self) -> Option<&mut u32> {
+        match self {
+            Spot::Grassland {
+                wildlife_population,
+                ..
+            }
+            | Spot::Forest {
+                wildlife_population,
+                ..
+            }
+            | Spot::Hill {
+                wildlife_population,
+                ..
+            }
+            | Spot::Mountain {
+                wildlife_population,
+                ..
+            } => Some(wildlife_population),
+            _ => None,
+        }
+    }
+
+    fn owned_land_mut(&mut self) -> Option<&mut u32> {
+        match self {
+            Spot::Arcology { .. } => None,
+            Spot::Settlement { owned_land, .. }
+            | Spot::Farmland { owned_land, .. }
+            | Spot::Mine { owned_land, .. }
+            | Spot::Grassland { owned_land, .. }
+            | Spot::Forest { owned_land, .. }
+            | Spot::Hill { owned_land, .. }
+            | Spot::Terrace { owned_land, .. }
+            | Spot::Mountain { owned_land, .. } => Some(owned_land),
+        }
+    }
+}
+
+/// Logistic growth that always moves by at least 1 until the capacity is reached. Crowds beyond
+/// the capacity are left as they are.
+fn grow(count: u32, capacity: u32, rate: f64) -> u32 {
+    if count >= capacity {
+        return count;
+    }
+    let growth = rate * count as f64 * (1. - count as f64 / capacity as f64);
+    (count + growth.ceil() as u32).min(capacity)
+}
+
+impl Region {
+    pub fn get_spots(&self) -> &[Spot] {
+        &self.spots
+    }
+
+    pub fn get_housing_capacity(&self) -> u32 {
+        self.spots.iter().map(Spot::get_housing).sum()
+    }
+
+    pub fn get_spot_population(&self) -> u32 {
+        self.spots.iter().map(Spot::get_population).sum()
+    }
+
+    pub fn get_wildlife(&self) -> u32 {
+        self.spots.iter().map(Spot::get_wildlife).sum()
+    }
+
+    /// How much of the region is worked into farmland & terraces, by the yield of its spots.
+    pub fn get_crop_yield(&self) -> f64 {
+        if self.spots.is_empty() {
+            return 0.;
+        }
+        let total: f64 = self.spots.iter().map(Spot::get_crop_yield).sum();
+        total / self.spots.len() as f64
+    }
+
+    /// Buys up land in a spot, which develops it once all of its land is owned.
+    pub fn invest_in_spot(&mut self, spot_index: usize, land: u32) -> Result<(), SpotError> {
+        let specialty = self.specialty;
+        let spot = self.spots.get_mut(spot_index).ok_or(SpotError::NoSpot)?;
+        let developed = spot.develop(specialty).ok_or(SpotError::CannotDevelop)?;
+
+        let owned_land = spot
+            .owned_land_mut()
+            .expect("spots that can develop should have land to own");
+        *owned_land = (*owned_land + land).min(SPOT_LAND);
+        if *owned_land == SPOT_LAND {
+            *spot = developed;
+        }
+        Ok(())
+    }
+
+    /**
+    People grow towards the housing of the region, and move into the spots with room to spare,
+    while animals grow towards what their spots can hold. Crowded farmland grows into a
+    settlement, and forests get logged by the people of the region until they are grassland.
+    */
+    fn progress_spots(&mut self) {
+        let population = self.get_spot_population();
+        let mut born = grow(population, self.get_housing_capacity(), POP_GROWTH_RATE) - population;
+        let mut logging = (population as f64 * LOGGING_PER_PERSON).round() as u32;
+
+        for spot in &mut self.spots {
+            let housing = spot.get_housing();
+            if let Some(population) = spot.population_mut() {
+                let settled = born.min(housing.saturating_sub(*population));
+                *population += settled;
+                born -= settled;
+            }
+            if let Spot::Farmland { population, .. } = *spot {
+                if population >= housing {
+                    *spot = Spot::Settlement {
+                        population,
+                        owned_land: 0,
+                    };
+                }
+            }
+
+            let capacity = spot.get_wildlife_capacity();
+            if let Some(wildlife) = spot.wildlife_mut() {
+                *wildlife = grow(*wildlife, capacity, WILDLIFE_GROWTH_RATE);
+            }
+            if let Spot::Forest {
+                wildlife_population,
+                owned_land,
+            } = spot
+            {
+                let cut = logging.min(*wildlife_population);
+                *wildlife_population -= cut;
+                logging -= cut;
+                if (*wildlife_population as f64) < capacity as f64 * DEFORESTED_RATIO {
+                    *spot = Spot::Grassland {
+                        wildlife_population: *wildlife_population,
+                        owned_land: *owned_land,
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl Planet {
+    /// Regions where people live & shop, the most populous first, so the capital leads.
+    pub fn get_towns(&self) -> Vec<RegionId> {
+        let mut towns: Vec<_> = (0..self.regions.len())
+            .filter(|&index| self.regions[index].get_spot_population() > 0)
+            .collect();
+        towns.sort_by_key(|&index| Reverse(self.regions[index].get_spot_population()));
+        towns.into_iter().map(RegionId::new_unsafe).collect()
+    }
+
+    /// Covers the land with spots of wilderness.
+    pub(super) fn grow_wilderness(&mut self, creation_rng: &mut dyn rand_core::RngCore) {
+        for region in &mut self.regions {
+            let num_spots = (region.get_land_capacity() as u32 / SPOT_LAND).max(1);
+            region.spots = (0..num_spots)
+                .filter_map(|_| {
+                    Spot::new_wild(region.terrain, creation_rng.gen_bool(FOREST_CHANCE))
+                })
+                .collect();
+        }
+    }
+
+    pub fn progress_spots(&mut self, time: Time) {
+        if !time.is_multiple_of(SPOT_PERIOD) {
+            return;
+        }
+        for region in &mut self.regions {
+            region.progress_spots();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SETTLERS, SPOT_LAND, SPOT_PERIOD};
+    use crate::planet::{
+        test_util::planet_with_spots, Planet, RegionSpecialty, Spot, SpotError, Wrapping,
+    };
+
+    #[test]
+    fn wilderness_covers_land() {
+        let planet = Planet::new_test_map(&["...", "hM~", "..."], Wrapping::None);
+        let regions = planet.get_regions();
+        assert!(regions[0].get_spots().len() > 1);
+        assert!(regions[0]
+            .get_spots()
+            .iter()
+            .all(|spot| matches!(spot, Spot::Grassland { .. } | Spot::Forest { .. })));
+        assert!(matches!(regions[3].get_spots()[0], Spot::Hill { .. }));
+        assert!(regions[5].get_spots().is_empty());
+        assert!(regions[0].get_wildlife() > 0);
+        assert_eq!(regions[0].get_housing_capacity(), 0);
+    }
+
+    #[test]
+    fn investment_develops_spots() {
+        let mut planet = planet_with_spots(vec![
+            Spot::Grassland {
+                wildlife_population: 50,
+                owned_land: 0,
+            },
+            Spot::Hill {
+                wildlife_population: 80,
+                owned_land: 0,
+            },
+            Spot::Arcology { population: 100 },
+        ]);
+        let region = &mut planet.regions[4];
+
+        region.invest_in_spot(0, SPOT_LAND / 2).unwrap();
+        assert!(matches!(region.get_spots()[0], Spot::Grassland { .. }));
+        region.invest_in_spot(0, SPOT_LAND).unwrap();
+        assert_eq!(
+            region.get_spots()[0],
+            Spot::Farmland {
+                population: SETTLERS,
+                owned_land: 0
+            }
+        );
+        assert!(region.get_crop_yield() > 0.);
+
+        // hills are mined out in mining regions, and terraced elsewhere
+        region.specialty = RegionSpecialty::Mining;
+        region.invest_in_spot(1, SPOT_LAND).unwrap();
+        assert!(matches!(region.get_spots()[1], Spot::Mine { .. }));
+
+        assert_eq!(
+            region.invest_in_spot(2, SPOT_LAND),
+            Err(SpotError::CannotDevelop)
+        );
+        assert_eq!(region.invest_in_spot(3, 1), Err(SpotError::NoSpot));
+    }
+
+    #[test]
+    fn people_grow_and_cut_forests() {
+        let mut planet = planet_with_spots(vec![
+            Spot::Farmland {
+                population: 10,
+                owned_land: 0,
+            },
+            Spot::Settlement {
+                population: 190,
+                owned_land: 0,
+            },
+            Spot::Forest {
+                wildlife_population: 200,
+                owned_land: 0,
+            },
+        ]);
+
+        for period in 0..1000 {
+            planet.progress_spots(period * SPOT_PERIOD);
+        }
+
+        let region = &planet.regions[4];
+        let spots = region.get_spots();
+        // the crowded farmland became part of the town, which is now full
+        assert!(matches!(spots[0], Spot::Settlement { .. }));
+        assert_eq!(spots[1].get_population(), spots[1].get_housing());
+        assert_eq!(region.get_spot_population(), region.get_housing_capacity());
+        assert!(matches!(spots[2], Spot::Grassland { .. }));
+    }
+}
@@ -0,0 +1,30 @@
+use super::Storage;
+use crate::economy::{Commodity, Quantity};
+
+impl Storage {
+    pub fn get(&self, commodity: Commodity) -> Quantity {
+        self.0[commodity]
+    }
+
+    pub fn add(&mut self, commodity: Commodity, quantity: Quantity) {
+        self.0[commodity] = self.0[commodity].saturating_add(quantity);
+    }
+
+    /// Amount of goods of every kind put together.
+    pub fn get_total(&self) -> Quantity {
+        self.0.values().sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Commodity, Quantity)> + '_ {
+        self.0
+            .iter()
+            .map(|(commodity, &quantity)| (commodity, quantity))
+    }
+
+    /// Takes up to the quantity out, and returns how much was taken.
+    pub fn take(&mut self, commodity: Commodity, quantity: Quantity) -> Quantity {
+        let taken = quantity.min(self.0[commodity]);
+        self.0[commodity] -= taken;
+        taken
+    }
+}
@@ -0,0 +1,554 @@
+use enum_map::EnumMap;
+
+use super::{
+    local_market::to_money, pathfinding::WALKING_SPEED, Planet, RegionId, Route, Shipment,
+    TradeEndpoint, TradeRoute, TradeRouteError,
+};
+use crate::{
+    economy::{Commodity, Money, Ownership, Price, Quantity},
+    Time,
+};
+
+// shipments leave once per period
+const TRADE_PERIOD: Time = 24;
+// merchants look for new deals once per period
+const MERCHANT_PERIOD: Time = 24 * 7;
+// cost of carrying a piece of goods for a tick, which covers both the distance & the terrain
+const TRANSPORT_COST: Price = 0.001;
+// goods a merchant caravan carries per shipment
+const MERCHANT_CAPACITY: Quantity = 100;
+// merchant routes open on a planet at once
+const MAX_MERCHANT_ROUTES: usize = 50;
+
+impl TradeEndpoint {
+    pub fn get_region_id(&self) -> RegionId {
+        match *self {
+            TradeEndpoint::Market(region_id) | TradeEndpoint::Warehouse(region_id, _) => region_id,
+        }
+    }
+}
+
+impl Shipment {
+    pub fn get_quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    pub fn get_arrives_at(&self) -> Time {
+        self.arrives_at
+    }
+}
+
+impl TradeRoute {
+    pub fn get_owner(&self) -> &Ownership {
+        &self.owner
+    }
+
+    pub fn get_commodity(&self) -> Commodity {
+        self.commodity
+    }
+
+    pub fn get_from(&self) -> TradeEndpoint {
+        self.from
+    }
+
+    pub fn get_to(&self) -> TradeEndpoint {
+        self.to
+    }
+
+    pub fn get_route(&self) -> &Route {
+        &self.route
+    }
+
+    pub fn get_capacity(&self) -> Quantity {
+        self.capacity
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn get_shipments(&self) -> &[Shipment] {
+        &self.shipments
+    }
+
+    /// Cost of carrying a piece of goods the whole way.
+    pub fn get_unit_cost(&self) -> Price {
+        self.route.get_travel_time() as Price * TRANSPORT_COST
+    }
+
+    fn is_same_line(
+        &self,
+        owner: &Ownership,
+        commodity: Commodity,
+        from: TradeEndpoint,
+        to: TradeEndpoint,
+    ) -> bool {
+        &self.owner == owner && self.commodity == commodity && self.from == from && self.to == to
+    }
+}
+
+impl Planet {
+    pub fn get_trade_routes(&self) -> &[TradeRoute] {
+        &self.trade_routes
+    }
+
+    /**
+    Starts shipping a commodity along the fastest route between 2 regions, and returns the index
+    of the trade route. Warehouses at either end have to belong to the owner of the route.
+    */
+    pub fn open_trade_route(
+        &mut self,
+        owner: Ownership,
+        commodity: Commodity,
+        from: TradeEndpoint,
+        to: TradeEndpoint,
+        capacity: Quantity,
+    ) -> Result<usize, TradeRouteError> {
+        if from.get_region_id() == to.get_region_id() {
+            return Err(TradeRouteError::SameRegion);
+        }
+        for endpoint in [from, to] {
+            if let TradeEndpoint::Warehouse(region_id, index) = endpoint {
+                let warehouse = self.regions[usize::from(region_id)]
+                    .warehouses
+                    .get(index)
+                    .ok_or(TradeRouteError::NoWarehouse)?;
+                if warehouse.ownership != owner {
+                    return Err(TradeRouteError::NotOwner);
+                }
+            }
+        }
+
+        let route = self
+            .find_route(
+                from.get_region_id(),
+                to.get_region_id(),
+                Some(&self.road_bonus),
+            )
+            .ok_or(TradeRouteError::NoRoute)?;
+        let versions = self.get_route_versions();
+
+        // reopens a line that was run before, along the way as it is now
+        if let Some(index) = self
+            .trade_routes
+            .iter()
+            .position(|trade_route| trade_route.is_same_line(&owner, commodity, from, to))
+        {
+            let trade_route = &mut self.trade_routes[index];
+            trade_route.route = route;
+            trade_route.versions = versions;
+            trade_route.capacity = capacity;
+            trade_route.is_open = true;
+            return Ok(index);
+        }
+
+        self.trade_routes.push(TradeRoute {
+            owner,
+            commodity,
+            from,
+            to,
+            route,
+            versions,
+            capacity,
+            is_open: true,
+            shipments: Vec::new(),
+        });
+        Ok(self.trade_routes.len() - 1)
+    }
+
+    fn get_route_versions(&self) -> (u64, u64) {
+        (self.get_route_version(), self.road_bonus.get_version())
+    }
+
+    /**
+    Finds the way again if the terrain or the roads changed since the route was found, and tells
+    whether the ends are still connected. Shipments already on the way keep their arrival times.
+    */
+    fn reroute(&mut self, index: usize) -> bool {
+        let versions = self.get_route_versions();
+        let trade_route = &self.trade_routes[index];
+        if trade_route.versions == versions {
+            return true;
+        }
+        let (from, to) = (
+            trade_route.from.get_region_id(),
+            trade_route.to.get_region_id(),
+        );
+        match self.find_route(from, to, Some(&self.road_bonus)) {
+            Some(route) => {
+                let trade_route = &mut self.trade_routes[index];
+                trade_route.route = route;
+                trade_route.versions = versions;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stops sending shipments; those on the way still arrive.
+    pub fn close_trade_route(&mut self, index: usize) {
+        if let Some(trade_route) = self.trade_routes.get_mut(index) {
+            trade_route.is_open = false;
+        }
+    }
+
+    fn get_price_at(&self, endpoint: TradeEndpoint, commodity: Commodity) -> Price {
+        self.regions[usize::from(endpoint.get_region_id())]
+            .local_market
+            .get_price(commodity)
+    }
+
+    /// Whether the price difference pays for carrying the goods, for merchants.
+    fn is_profitable(&self, trade_route: &TradeRoute) -> bool {
+        let spread = self.get_price_at(trade_route.to, trade_route.commodity)
+            - self.get_price_at(trade_route.from, trade_route.commodity);
+        spread > trade_route.get_unit_cost()
+    }
+
+    fn load(
+        &mut self,
+        endpoint: TradeEndpoint,
+        commodity: Commodity,
+        quantity: Quantity,
+    ) -> Quantity {
+        match endpoint {
+            TradeEndpoint::Market(region_id) => self.regions[usize::from(region_id)]
+                .local_market
+                .get_storage_mut()
+                .take(commodity, quantity),
+            TradeEndpoint::Warehouse(region_id, index) => {
+                self.regions[usize::from(region_id)].warehouses[index].take(commodity, quantity)
+            }
+        }
+    }
+
+    /// Drops goods off; what doesn't fit in a warehouse goes to the owner's others or the market.
+    fn unload(
+        &mut self,
+        endpoint: TradeEndpoint,
+        owner: &Ownership,
+        commodity: Commodity,
+        quantity: Quantity,
+    ) {
+        let region = &mut self.regions[usize::from(endpoint.get_region_id())];
+        let remaining = match endpoint {
+            TradeEndpoint::Market(_) => quantity,
+            TradeEndpoint::Warehouse(_, index) => {
+                quantity - region.warehouses[index].store(commodity, quantity)
+            }
+        };
+        if remaining > 0 {
+            region.deliver(owner, commodity, remaining);
+        }
+    }
+
+    /**
+    Merchants open routes for the widest price gaps of every commodity, from a market with stock to
+    a populated one, and close the routes that no longer pay.
+    */
+    fn plan_merchant_routes(&mut self) {
+        for index in 0..self.trade_routes.len() {
+            let trade_route = &self.trade_routes[index];
+            if trade_route.owner == Ownership::Independent && !self.is_profitable(trade_route) {
+                self.trade_routes[index].is_open = false;
+            }
+        }
+
+        let towns = self.get_towns();
+        let mut deals = Vec::new();
+        for (commodity, ()) in EnumMap::<Commodity, ()>::default() {
+            let best = (0..self.regions.len())
+                .map(RegionId::new_unsafe)
+                .filter(|&from| {
+                    self.regions[usize::from(from)]
+                        .local_market
+                        .get_storage()
+                        .get(commodity)
+                        > 0
+                })
+                .flat_map(|from| towns.iter().map(move |&to| (from, to)))
+                .filter(|(from, to)| from != to)
+                .map(|(from, to)| {
+                    let from_region = &self.regions[usize::from(from)];
+                    let to_region = &self.regions[usize::from(to)];
+                    // the straight line is as cheap as carrying can be
+                    let distance =
+                        self.get_distance(from_region.centroid_coor, to_region.centroid_coor);
+                    let margin = to_region.local_market.get_price(commodity)
+                        - from_region.local_market.get_price(commodity)
+                        - distance / WALKING_SPEED * TRANSPORT_COST;
+                    (from, to, margin)
+                })
+                .filter(|&(_, _, margin)| margin > 0.)
+                .max_by(|a, b| a.2.total_cmp(&b.2));
+            if let Some((from, to, margin)) = best {
+                deals.push((commodity, from, to, margin));
+            }
+        }
+        deals.sort_by(|a, b| b.3.total_cmp(&a.3));
+
+        for (commodity, from, to, _) in deals {
+            let open = self
+                .trade_routes
+                .iter()
+                .filter(|trade_route| {
+                    trade_route.owner == Ownership::Independent && trade_route.is_open
+                })
+                .count();
+            if open >= MAX_MERCHANT_ROUTES {
+                break;
+            }
+            let opened = self.open_trade_route(
+                Ownership::Independent,
+                commodity,
+                TradeEndpoint::Market(from),
+                TradeEndpoint::Market(to),
+                MERCHANT_CAPACITY,
+            );
+            // the route may turn out too long to pay
+            if let Ok(index) = opened {
+                if !self.is_profitable(&self.trade_routes[index]) {
+                    self.trade_routes[index].is_open = false;
+                }
+            }
+        }
+    }
+
+    /**
+    Delivers the shipments that have arrived, lets the merchants plan their routes & sends off new
+    shipments when it's time to. Returns what the companies owe for the shipments sent, with the
+    index of their trade route.
+    */
+    pub fn progress_trade_routes(&mut self, time: Time) -> Vec<(usize, Ownership, Money)> {
+        for index in 0..self.trade_routes.len() {
+            let trade_route = &mut self.trade_routes[index];
+            let (arrived, on_the_way) = trade_route
+                .shipments
+                .iter()
+                .partition(|shipment| shipment.arrives_at <= time);
+            trade_route.shipments = on_the_way;
+            let arrived: Vec<Shipment> = arrived;
+            let (to, owner, commodity) = (
+                trade_route.to,
+                trade_route.owner.clone(),
+                trade_route.commodity,
+            );
+            for shipment in arrived {
+                self.unload(to, &owner, commodity, shipment.quantity);
+            }
+        }
+
+        if time.is_multiple_of(MERCHANT_PERIOD) {
+            self.plan_merchant_routes();
+        }
+
+        let mut costs = Vec::new();
+        if !time.is_multiple_of(TRADE_PERIOD) {
+            return costs;
+        }
+        for index in 0..self.trade_routes.len() {
+            // nothing leaves while the way is cut
+            if !self.trade_routes[index].is_open || !self.reroute(index) {
+                continue;
+            }
+            let trade_route = &self.trade_routes[index];
+            if trade_route.owner == Ownership::Independent && !self.is_profitable(trade_route) {
+                continue;
+            }
+            let (from, commodity, capacity) = (
+                trade_route.from,
+                trade_route.commodity,
+                trade_route.capacity,
+            );
+            let quantity = self.load(from, commodity, capacity);
+            if quantity == 0 {
+                continue;
+            }
+
+            let trade_route = &mut self.trade_routes[index];
+            trade_route.shipments.push(Shipment {
+                quantity,
+                arrives_at: time + trade_route.route.get_travel_time(),
+            });
+            if let Ownership::Company(_) = trade_route.owner {
+                let cost = to_money(quantity as Price * trade_route.get_unit_cost());
+                costs.push((index, trade_route.owner.clone(), cost));
+            }
+        }
+        costs
+    }
+
+    /// Unloads the last shipment sent on the route back where it came from, e.g. when it can't be
+    /// paid for.
+    pub fn hold_shipment(&mut self, index: usize) {
+        let trade_route = &mut self.trade_routes[index];
+        let Some(shipment) = trade_route.shipments.pop() else {
+            return;
+        };
+        let (from, owner, commodity) = (
+            trade_route.from,
+            trade_route.owner.clone(),
+            trade_route.commodity,
+        );
+        self.unload(from, &owner, commodity, shipment.quantity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MERCHANT_PERIOD, TRADE_PERIOD};
+    use crate::{
+        economy::{Commodity, CompanyId, MarketConfig, Ownership},
+        planet::{
+            retailer::BASE_QUALITY,
+            test_util::{bare_map, id},
+            Planet, RegionId, Spot, TradeEndpoint, TradeRouteError, Warehouse, Wrapping,
+        },
+        terrain::Terrain,
+    };
+
+    #[test]
+    fn company_routes_haul_between_warehouses() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let acme = Ownership::Company(CompanyId::new_unsafe(0));
+        let farm = planet.regions[0].add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        let depot = planet.regions[8].add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        planet.regions[0].warehouses[farm].store(Commodity::Tool, 150);
+        let from = TradeEndpoint::Warehouse(RegionId::new_unsafe(0), farm);
+        let to = TradeEndpoint::Warehouse(RegionId::new_unsafe(8), depot);
+
+        assert_eq!(
+            planet.open_trade_route(Ownership::Independent, Commodity::Tool, from, to, 100),
+            Err(TradeRouteError::NotOwner)
+        );
+        assert_eq!(
+            planet.open_trade_route(acme.clone(), Commodity::Tool, from, from, 100),
+            Err(TradeRouteError::SameRegion)
+        );
+        let index = planet
+            .open_trade_route(acme.clone(), Commodity::Tool, from, to, 100)
+            .unwrap();
+
+        let costs = planet.progress_trade_routes(TRADE_PERIOD);
+        assert_eq!(costs.len(), 1);
+        assert_eq!(costs[0].0, index);
+        assert!(costs[0].2 > 0);
+        let travel_time = planet.get_trade_routes()[index]
+            .get_route()
+            .get_travel_time();
+        assert!(travel_time > 0);
+        let stock = |planet: &Planet| {
+            planet.regions[8].get_warehouses()[depot]
+                .get_storage()
+                .get(Commodity::Tool)
+        };
+        assert_eq!(stock(&planet), 0);
+        for time in TRADE_PERIOD + 1..=TRADE_PERIOD + travel_time {
+            planet.progress_trade_routes(time);
+        }
+        assert!(stock(&planet) >= 100);
+
+        // closing a route stops new shipments
+        planet.close_trade_route(index);
+        let before = planet.regions[0].get_warehouses()[farm]
+            .get_storage()
+            .get(Commodity::Tool);
+        planet.progress_trade_routes(TRADE_PERIOD * 100);
+        let after = planet.regions[0].get_warehouses()[farm]
+            .get_storage()
+            .get(Commodity::Tool);
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn routes_follow_changes_in_the_land() {
+        let mut planet = bare_map(&["...", "...", "..."], Wrapping::None);
+        let acme = Ownership::Company(CompanyId::new_unsafe(0));
+        let (from, to) = (TradeEndpoint::Market(id(0)), TradeEndpoint::Market(id(2)));
+        let index = planet
+            .open_trade_route(acme.clone(), Commodity::Tool, from, to, 100)
+            .unwrap();
+        let travel_time = |planet: &Planet| {
+            planet.get_trade_routes()[index]
+                .get_route()
+                .get_travel_time()
+        };
+        let plain = travel_time(&planet);
+
+        // the way is found again before the next shipment
+        planet.set_terrain(id(1), Terrain::Mountain);
+        planet.progress_trade_routes(TRADE_PERIOD);
+        let detour = travel_time(&planet);
+        assert!(detour > plain);
+
+        // and when a closed line is reopened
+        planet.close_trade_route(index);
+        planet.set_terrain(id(1), Terrain::Plain);
+        planet
+            .open_trade_route(acme, Commodity::Tool, from, to, 100)
+            .unwrap();
+        assert_eq!(travel_time(&planet), plain);
+    }
+
+    #[test]
+    fn held_shipments_stay_home() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let acme = Ownership::Company(CompanyId::new_unsafe(0));
+        let farm = planet.regions[0].add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        planet.regions[0].warehouses[farm].store(Commodity::Tool, 150);
+        let from = TradeEndpoint::Warehouse(RegionId::new_unsafe(0), farm);
+        let to = TradeEndpoint::Market(RegionId::new_unsafe(8));
+        let index = planet
+            .open_trade_route(acme, Commodity::Tool, from, to, 100)
+            .unwrap();
+
+        planet.progress_trade_routes(TRADE_PERIOD);
+        assert_eq!(planet.get_trade_routes()[index].get_shipments().len(), 1);
+        planet.hold_shipment(index);
+        assert!(planet.get_trade_routes()[index].get_shipments().is_empty());
+        let stock = planet.regions[0].get_warehouses()[farm]
+            .get_storage()
+            .get(Commodity::Tool);
+        assert_eq!(stock, 150);
+    }
+
+    #[test]
+    fn merchants_even_out_prices() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let config = MarketConfig::default();
+        // a farming village with plenty of crops, and a hungry town
+        for (index, population) in [(0, 100), (8, 150)] {
+            planet.regions[index].spots = vec![Spot::Settlement {
+                population,
+                owned_land: 0,
+            }];
+        }
+        planet.regions[0]
+            .local_market
+            .deliver(Commodity::Crop, 5000);
+        planet.regions[8].local_market.deliver(Commodity::Crop, 10);
+        planet.progress_markets(0, &config);
+        let gap = |planet: &Planet| {
+            planet.regions[8].local_market.get_price(Commodity::Crop)
+                - planet.regions[0].local_market.get_price(Commodity::Crop)
+        };
+        let initial_gap = gap(&planet);
+        assert!(initial_gap > 0.);
+
+        for time in 1..=MERCHANT_PERIOD * 4 {
+            planet.progress_trade_routes(time);
+            planet.progress_markets(time, &config);
+        }
+        assert!(planet
+            .get_trade_routes()
+            .iter()
+            .any(|trade_route| trade_route.get_owner() == &Ownership::Independent));
+        assert!(gap(&planet) < initial_gap);
+        assert!(
+            planet.regions[8]
+                .local_market
+                .get_production(Commodity::Crop)
+                > 0
+        );
+    }
+}
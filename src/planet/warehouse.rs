@@ -0,0 +1,382 @@
+use super::{
+    retailer::BASE_QUALITY, LocalMarket, Planet, Region, RegionId, Storage, TradeEndpoint,
+    Warehouse, WarehouseError,
+};
+use crate::{
+    economy::{Commodity, Money, Ownership, Quality, Quantity},
+    Time,
+};
+
+// goods spoil & storage is paid for once per period
+const WAREHOUSE_PERIOD: Time = 24;
+// goods a warehouse of scale 1 can hold
+const CAPACITY_PER_SCALE: Quantity = 1000;
+// paid per scale every period for a warehouse of ordinary quality
+const UPKEEP_PER_SCALE: Money = 50; // 0.50
+
+impl Warehouse {
+    pub fn new(ownership: Ownership, scale: u32, quality: Quality) -> Self {
+        Self {
+            ownership,
+            scale,
+            quality,
+            storage: Storage::default(),
+        }
+    }
+
+    pub fn get_ownership(&self) -> &Ownership {
+        &self.ownership
+    }
+
+    pub fn get_scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn get_quality(&self) -> Quality {
+        self.quality
+    }
+
+    pub fn get_storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    pub fn get_capacity(&self) -> Quantity {
+        self.scale * CAPACITY_PER_SCALE
+    }
+
+    pub fn get_free_space(&self) -> Quantity {
+        self.get_capacity().saturating_sub(self.storage.get_total())
+    }
+
+    /// Cost of keeping the warehouse running for a period, higher for better warehouses.
+    pub fn get_upkeep(&self) -> Money {
+        UPKEEP_PER_SCALE * self.scale as Money * self.quality as Money / BASE_QUALITY as Money
+    }
+
+    /// Portion of the commodity that spoils every period; ordinary warehouses at the base rate.
+    pub fn get_decay_rate(&self, commodity: Commodity) -> f64 {
+        let base = BASE_QUALITY as f64;
+        commodity.get_decay_rate() * 2. * base / (base + self.quality as f64)
+    }
+
+    /// Stores as much as fits, and returns how much was stored.
+    pub fn store(&mut self, commodity: Commodity, quantity: Quantity) -> Quantity {
+        let stored = quantity.min(self.get_free_space());
+        self.storage.add(commodity, stored);
+        stored
+    }
+
+    /// Takes up to the quantity out, and returns how much was taken.
+    pub fn take(&mut self, commodity: Commodity, quantity: Quantity) -> Quantity {
+        self.storage.take(commodity, quantity)
+    }
+
+    /// Throws out what spoiled over a period; at least 1 of any perishable goods.
+    fn decay(&mut self) {
+        let spoiled: Vec<_> = self
+            .storage
+            .iter()
+            .filter(|&(_, quantity)| quantity > 0)
+            .map(|(commodity, quantity)| {
+                let rate = self.get_decay_rate(commodity);
+                (commodity, (quantity as f64 * rate).ceil() as Quantity)
+            })
+            .collect();
+        for (commodity, quantity) in spoiled {
+            self.storage.take(commodity, quantity);
+        }
+    }
+}
+
+/**
+Lets a producer work with its supplies: the owner's warehouses in the region for companies, and
+the local market for independent producers. What the producer uses up is taken out of the
+warehouses.
+*/
+pub(super) fn with_supplies<R>(
+    warehouses: &mut [Warehouse],
+    local_market: &mut LocalMarket,
+    ownership: &Ownership,
+    produce: impl FnOnce(&mut Storage) -> R,
+) -> R {
+    if let Ownership::Independent = ownership {
+        return produce(local_market.get_storage_mut());
+    }
+
+    let mut supplies = Storage::default();
+    for warehouse in warehouses
+        .iter()
+        .filter(|warehouse| &warehouse.ownership == ownership)
+    {
+        for (commodity, quantity) in warehouse.storage.iter() {
+            supplies.add(commodity, quantity);
+        }
+    }
+    let before = supplies.clone();
+    let ret = produce(&mut supplies);
+
+    for (commodity, quantity) in before.iter() {
+        let mut used = quantity.saturating_sub(supplies.get(commodity));
+        for warehouse in warehouses.iter_mut() {
+            if used > 0 && &warehouse.ownership == ownership {
+                used -= warehouse.take(commodity, used);
+            }
+        }
+    }
+    ret
+}
+
+impl Region {
+    pub fn get_warehouses(&self) -> &[Warehouse] {
+        &self.warehouses
+    }
+
+    /// Opens a warehouse in the region, and returns its index.
+    pub fn add_warehouse(&mut self, warehouse: Warehouse) -> usize {
+        self.warehouses.push(warehouse);
+        self.warehouses.len() - 1
+    }
+
+    /// Goods of the commodity the owner keeps in their warehouses in the region.
+    pub fn get_stored(&self, ownership: &Ownership, commodity: Commodity) -> Quantity {
+        self.warehouses
+            .iter()
+            .filter(|warehouse| &warehouse.ownership == ownership)
+            .map(|warehouse| warehouse.storage.get(commodity))
+            .sum()
+    }
+
+    /// Takes up to the quantity out of the owner's warehouses in the region, and returns how much
+    /// was taken.
+    pub fn take_stored(
+        &mut self,
+        ownership: &Ownership,
+        commodity: Commodity,
+        quantity: Quantity,
+    ) -> Quantity {
+        let mut taken = 0;
+        for warehouse in &mut self.warehouses {
+            if &warehouse.ownership == ownership {
+                taken += warehouse.take(commodity, quantity - taken);
+            }
+        }
+        taken
+    }
+
+    /**
+    Moves goods between 2 warehouses of the same owner, as much as fits in the destination.
+    Returns how much was moved.
+    */
+    pub fn transfer_between_warehouses(
+        &mut self,
+        from: usize,
+        to: usize,
+        commodity: Commodity,
+        quantity: Quantity,
+    ) -> Result<Quantity, WarehouseError> {
+        if from == to {
+            return Err(WarehouseError::SameWarehouse);
+        }
+        let (source, destination) = match (self.warehouses.get(from), self.warehouses.get(to)) {
+            (Some(source), Some(destination)) => (source, destination),
+            _ => return Err(WarehouseError::NoWarehouse),
+        };
+        if source.ownership != destination.ownership {
+            return Err(WarehouseError::DifferentOwners);
+        }
+        if source.storage.get(commodity) < quantity {
+            return Err(WarehouseError::NotEnoughGoods);
+        }
+
+        let moved = quantity.min(destination.get_free_space());
+        self.warehouses[from].take(commodity, moved);
+        self.warehouses[to].store(commodity, moved);
+        Ok(moved)
+    }
+
+    /**
+    Brings goods made in the region to the owner's warehouses here, and whatever doesn't fit to the
+    local market. Independent producers always sell at the market.
+    */
+    pub fn deliver(&mut self, ownership: &Ownership, commodity: Commodity, quantity: Quantity) {
+        let mut remaining = quantity;
+        if let Ownership::Company(_) = ownership {
+            for warehouse in &mut self.warehouses {
+                if &warehouse.ownership == ownership {
+                    remaining -= warehouse.store(commodity, remaining);
+                }
+            }
+        }
+        if remaining > 0 {
+            self.local_market.deliver(commodity, remaining);
+        }
+    }
+}
+
+impl Planet {
+    /// Lets goods spoil if it's time to, and returns whether the storage has to be paid for.
+    pub fn progress_warehouses(&mut self, time: Time) -> bool {
+        if !time.is_multiple_of(WAREHOUSE_PERIOD) {
+            return false;
+        }
+        for region in &mut self.regions {
+            for warehouse in &mut region.warehouses {
+                warehouse.decay();
+            }
+        }
+        true
+    }
+
+    /**
+    Shuts a warehouse down, e.g. when its upkeep goes unpaid: its goods are sold off at the local
+    market, and the trade routes using it are closed, with the shipments on the way dropped off at
+    the market instead.
+    */
+    pub fn close_warehouse(&mut self, region_id: RegionId, index: usize) {
+        let region = &mut self.regions[usize::from(region_id)];
+        let warehouse = region.warehouses.remove(index);
+        for (commodity, quantity) in warehouse.storage.iter() {
+            if quantity > 0 {
+                region.local_market.deliver(commodity, quantity);
+            }
+        }
+
+        for trade_route in &mut self.trade_routes {
+            for endpoint in [&mut trade_route.from, &mut trade_route.to] {
+                match *endpoint {
+                    TradeEndpoint::Warehouse(other_region_id, other)
+                        if other_region_id == region_id && other == index =>
+                    {
+                        *endpoint = TradeEndpoint::Market(region_id);
+                        trade_route.is_open = false;
+                    }
+                    // the warehouses after it move down a place
+                    TradeEndpoint::Warehouse(other_region_id, other)
+                        if other_region_id == region_id && other > index =>
+                    {
+                        *endpoint = TradeEndpoint::Warehouse(region_id, other - 1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CAPACITY_PER_SCALE;
+    use crate::{
+        economy::{Commodity, CompanyId, Ownership},
+        planet::{
+            retailer::BASE_QUALITY, Planet, Region, RegionId, TradeEndpoint, Warehouse,
+            WarehouseError, Wrapping,
+        },
+    };
+
+    #[test]
+    fn food_spoils_slower_in_better_warehouses() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let ownership = Ownership::Company(CompanyId::new_unsafe(0));
+        let region = &mut planet.regions[4];
+        let shed = region.add_warehouse(Warehouse::new(ownership.clone(), 1, BASE_QUALITY / 2));
+        let silo = region.add_warehouse(Warehouse::new(ownership, 1, BASE_QUALITY * 3));
+        for index in [shed, silo] {
+            let warehouse = &mut region.warehouses[index];
+            warehouse.store(Commodity::Meat, 500);
+            warehouse.store(Commodity::Metal, 100);
+        }
+
+        for day in 1..=10 {
+            planet.progress_warehouses(day * 24);
+        }
+        let warehouses = planet.regions[4].get_warehouses();
+        let meat = |index: usize| warehouses[index].get_storage().get(Commodity::Meat);
+        assert!(meat(shed) < meat(silo) && meat(silo) < 500);
+        assert_eq!(warehouses[shed].get_storage().get(Commodity::Metal), 100);
+        assert!(warehouses[silo].get_upkeep() > warehouses[shed].get_upkeep());
+    }
+
+    #[test]
+    fn deliveries_overflow_into_the_local_market() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let acme = Ownership::Company(CompanyId::new_unsafe(0));
+        let other = Ownership::Company(CompanyId::new_unsafe(1));
+        let region = &mut planet.regions[4];
+        let first = region.add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        let second = region.add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        let theirs = region.add_warehouse(Warehouse::new(other, 1, BASE_QUALITY));
+
+        region.deliver(&acme, Commodity::Tool, CAPACITY_PER_SCALE * 2 + 300);
+        let tools = |region: &Region, index: usize| {
+            region.get_warehouses()[index]
+                .get_storage()
+                .get(Commodity::Tool)
+        };
+        assert_eq!(tools(region, first), CAPACITY_PER_SCALE);
+        assert_eq!(tools(region, second), CAPACITY_PER_SCALE);
+        assert_eq!(tools(region, theirs), 0);
+        assert_eq!(region.local_market.get_storage().get(Commodity::Tool), 300);
+
+        region.deliver(&Ownership::Independent, Commodity::Tool, 10);
+        assert_eq!(region.local_market.get_storage().get(Commodity::Tool), 310);
+
+        assert_eq!(
+            region.transfer_between_warehouses(first, theirs, Commodity::Tool, 10),
+            Err(WarehouseError::DifferentOwners)
+        );
+        region.warehouses[second].take(Commodity::Tool, 100);
+        assert_eq!(
+            region.transfer_between_warehouses(first, second, Commodity::Tool, 300),
+            Ok(100)
+        );
+        assert_eq!(tools(region, first), CAPACITY_PER_SCALE - 100);
+        assert_eq!(
+            region.transfer_between_warehouses(second, first, Commodity::Crop, 1),
+            Err(WarehouseError::NotEnoughGoods)
+        );
+    }
+
+    #[test]
+    fn closed_warehouses_are_emptied_into_the_market() {
+        let mut planet = Planet::new_test_map(&["...", "...", "..."], Wrapping::None);
+        let acme = Ownership::Company(CompanyId::new_unsafe(0));
+        let (depot, farm) = (RegionId::new_unsafe(4), RegionId::new_unsafe(0));
+        let region = &mut planet.regions[4];
+        let first = region.add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        let second = region.add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        region.warehouses[first].store(Commodity::Tool, 40);
+        let farm_warehouse =
+            planet.regions[0].add_warehouse(Warehouse::new(acme.clone(), 1, BASE_QUALITY));
+        let open = |planet: &mut Planet, index| {
+            planet
+                .open_trade_route(
+                    acme.clone(),
+                    Commodity::Tool,
+                    TradeEndpoint::Warehouse(farm, farm_warehouse),
+                    TradeEndpoint::Warehouse(depot, index),
+                    10,
+                )
+                .unwrap()
+        };
+        let to_first = open(&mut planet, first);
+        let to_second = open(&mut planet, second);
+
+        planet.close_warehouse(depot, first);
+        let region = &planet.regions[4];
+        assert_eq!(region.get_warehouses().len(), 1);
+        assert_eq!(region.local_market.get_storage().get(Commodity::Tool), 40);
+
+        let trade_routes = planet.get_trade_routes();
+        assert!(!trade_routes[to_first].is_open());
+        assert_eq!(
+            trade_routes[to_first].get_to(),
+            TradeEndpoint::Market(depot)
+        );
+        assert!(trade_routes[to_second].is_open());
+        assert_eq!(
+            trade_routes[to_second].get_to(),
+            TradeEndpoint::Warehouse(depot, 0)
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use super::{
+    LandSize, Planet, Plot, Region, RegionSpecialty, Rezoning, Storage, Zone, ZoningError,
+};
+use crate::{
+    economy::{Commodity, Ownership, Quantity},
+    Time,
+};
+
+impl Zone {
+    /// Scaffolds used up to turn the land into this zone.
+    pub fn get_build_cost(&self, size: LandSize) -> Quantity {
+        let per_land = match self {
+            Zone::Farming => 0.1,
+            Zone::Residence => 0.5,
+            Zone::Commercial => 1.,
+            Zone::Industry => 2.,
+            Zone::Special => 4.,
+        };
+        (size as f64 * per_land).ceil() as Quantity
+    }
+
+    /// Ticks it takes to turn the land into this zone, regardless of its size.
+    pub fn get_build_time(&self) -> Time {
+        match self {
+            Zone::Farming => 24,
+            Zone::Residence => 72,
+            Zone::Commercial => 96,
+            Zone::Industry => 168,
+            Zone::Special => 336,
+        }
+    }
+}
+
+impl RegionSpecialty {
+    /// Zones the land of a region can be allocated to.
+    pub fn get_allowed_zones(&self) -> &'static [Zone] {
+        match self {
+            RegionSpecialty::Uncivilized => &[], // has to be settled first
+            RegionSpecialty::Rural => &[Zone::Farming, Zone::Residence, Zone::Commercial],
+            RegionSpecialty::Forestry | RegionSpecialty::Mining => {
+                &[Zone::Industry, Zone::Residence]
+            }
+            RegionSpecialty::Estate => &[Zone::Residence, Zone::Commercial, Zone::Farming],
+            RegionSpecialty::Arcane => &[Zone::Special, Zone::Residence, Zone::Commercial],
+            RegionSpecialty::Urban => &[Zone::Residence, Zone::Commercial, Zone::Industry],
+            RegionSpecialty::Industry => &[Zone::Industry, Zone::Commercial],
+            RegionSpecialty::RocketPad | RegionSpecialty::DataCenter => {
+                &[Zone::Special, Zone::Industry]
+            }
+            RegionSpecialty::SpaceElevator => &[Zone::Special, Zone::Industry, Zone::Commercial],
+        }
+    }
+
+    pub fn allows(&self, zone: Zone) -> bool {
+        self.get_allowed_zones().contains(&zone)
+    }
+}
+
+impl Plot {
+    pub fn get_zone(&self) -> Zone {
+        self.zone
+    }
+
+    pub fn get_owner(&self) -> &Ownership {
+        &self.owner
+    }
+
+    pub fn get_size(&self) -> LandSize {
+        self.size
+    }
+
+    pub fn get_rezoning(&self) -> Option<Rezoning> {
+        self.rezoning
+    }
+}
+
+impl Rezoning {
+    pub fn get_zone(&self) -> Zone {
+        self.zone
+    }
+
+    pub fn get_done_at(&self) -> Time {
+        self.done_at
+    }
+}
+
+impl Region {
+    pub fn get_plots(&self) -> &[Plot] {
+        &self.plots
+    }
+
+    /// Land that hasn't been allocated to any plot yet.
+    pub fn get_free_land(&self) -> LandSize {
+        let allocated: LandSize = self.plots.iter().map(|plot| plot.size).sum();
+        self.get_land_capacity().saturating_sub(allocated)
+    }
+
+    pub fn get_zoned_land(&self, zone: Zone) -> LandSize {
+        self.plots
+            .iter()
+            .filter(|plot| plot.zone == zone)
+            .map(|plot| plot.size)
+            .sum()
+    }
+
+    /// Hands free land over to an owner, and returns the index of the new plot.
+    pub fn allocate_land(
+        &mut self,
+        zone: Zone,
+        owner: Ownership,
+        size: LandSize,
+    ) -> Result<usize, ZoningError> {
+        if !self.specialty.allows(zone) {
+            return Err(ZoningError::NotAllowed);
+        }
+        if size > self.get_free_land() {
+            return Err(ZoningError::NotEnoughLand);
+        }
+
+        self.plots.push(Plot {
+            zone,
+            owner,
+            size,
+            rezoning: None,
+        });
+        Ok(self.plots.len() - 1)
+    }
+
+    /**
+    Starts turning a plot into another zone, paying the scaffolds out of the materials. Returns
+    when the works will be done.
+    */
+    pub fn rezone(
+        &mut self,
+        plot_index: usize,
+        zone: Zone,
+        materials: &mut Storage,
+        time: Time,
+    ) -> Result<Time, ZoningError> {
+        if !self.specialty.allows(zone) {
+            return Err(ZoningError::NotAllowed);
+        }
+        let plot = self.plots.get_mut(plot_index).ok_or(ZoningError::NoPlot)?;
+        if plot.rezoning.is_some() {
+            return Err(ZoningError::AlreadyRezoning);
+        }
+        let cost = zone.get_build_cost(plot.size);
+        if materials.get(Commodity::Scaffold) < cost {
+            return Err(ZoningError::NotEnoughMaterials);
+        }
+
+        materials.take(Commodity::Scaffold, cost);
+        let done_at = time + zone.get_build_time();
+        plot.rezoning = Some(Rezoning { zone, done_at });
+        Ok(done_at)
+    }
+
+    /// Finishes the rezonings that are due.
+    pub(super) fn progress_zoning(&mut self, time: Time) {
+        for plot in &mut self.plots {
+            if let Some(rezoning) = plot.rezoning {
+                if rezoning.done_at <= time {
+                    plot.zone = rezoning.zone;
+                    plot.rezoning = None;
+                }
+            }
+        }
+    }
+}
+
+impl Planet {
+    pub fn progress_zoning(&mut self, time: Time) {
+        for region in &mut self.regions {
+            region.progress_zoning(time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        economy::{Commodity, Ownership},
+        planet::{test_util::rural_planet, Storage, Zone, ZoningError},
+    };
+
+    #[test]
+    fn land_is_limited_and_zoned_by_specialty() {
+        let mut planet = rural_planet();
+        let region = &mut planet.regions[4];
+        let capacity = region.get_land_capacity();
+        assert!(capacity > 100);
+
+        assert_eq!(
+            region.allocate_land(Zone::Industry, Ownership::Independent, 10),
+            Err(ZoningError::NotAllowed)
+        );
+        assert_eq!(
+            region.allocate_land(Zone::Farming, Ownership::Independent, capacity + 1),
+            Err(ZoningError::NotEnoughLand)
+        );
+
+        region
+            .allocate_land(Zone::Farming, Ownership::Independent, 100)
+            .unwrap();
+        region
+            .allocate_land(Zone::Residence, Ownership::Independent, 20)
+            .unwrap();
+        assert_eq!(region.get_free_land(), capacity - 120);
+        assert_eq!(region.get_zoned_land(Zone::Farming), 100);
+        assert_eq!(region.get_zoned_land(Zone::Commercial), 0);
+    }
+
+    #[test]
+    fn rezoning_costs_scaffolds_and_takes_time() {
+        let mut planet = rural_planet();
+        let region = &mut planet.regions[4];
+        let plot = region
+            .allocate_land(Zone::Farming, Ownership::Independent, 100)
+            .unwrap();
+
+        let mut materials = Storage::default();
+        assert_eq!(
+            region.rezone(plot, Zone::Commercial, &mut materials, 0),
+            Err(ZoningError::NotEnoughMaterials)
+        );
+
+        let cost = Zone::Commercial.get_build_cost(100);
+        materials.add(Commodity::Scaffold, cost + 5);
+        let done_at = region
+            .rezone(plot, Zone::Commercial, &mut materials, 10)
+            .unwrap();
+        assert_eq!(done_at, 10 + Zone::Commercial.get_build_time());
+        assert_eq!(materials.get(Commodity::Scaffold), 5);
+        assert_eq!(
+            region.rezone(plot, Zone::Residence, &mut materials, 10),
+            Err(ZoningError::AlreadyRezoning)
+        );
+
+        region.progress_zoning(done_at - 1);
+        assert_eq!(region.get_plots()[plot].get_zone(), Zone::Farming);
+        region.progress_zoning(done_at);
+        assert_eq!(region.get_plots()[plot].get_zone(), Zone::Commercial);
+        assert!(region.get_plots()[plot].get_rezoning().is_none());
+    }
+}
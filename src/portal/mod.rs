@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use crate::{
+    dynasty::DynastyId,
+    planet::{PlanetId, RegionId},
+    travel::Traveller,
+    universe::UniverseId,
+    Time,
+};
+
+mod portal_id;
+mod portals;
+mod traversal;
+
+/// One side of a portal, which sits in a region of a planet.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PortalEnd {
+    pub universe_id: UniverseId,
+    pub planet_id: PlanetId,
+    pub region_id: RegionId,
+}
+
+/// Who is allowed to go through a portal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortalAccess {
+    Anyone,
+    Dynasty(DynastyId), // only members of the dynasty, e.g. the one that found it
+    Sealed,
+}
+
+/**
+A link between a location in one universe and a location in another. Going through it takes a
+while, during which the traveller stays at the entrance and can't move, travel or trade until they
+come out at the exit.
+*/
+pub struct Portal {
+    name: String,
+    ends: [PortalEnd; 2],
+    access: PortalAccess,
+    is_one_way: bool, // can only be entered from the first end
+    max_party_size: u32,
+    transit_time: Time,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PortalId(usize);
+
+#[derive(Clone, Copy)]
+pub struct Transit {
+    traveller: Traveller,
+    portal_id: PortalId,
+    exit: PortalEnd,
+    arrival: Time,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortalError {
+    UnknownTraveller,
+    AlreadyTravelling,
+    NotAtPortal,
+    Sealed,
+    NotInDynasty,
+    OneWay, // tried to enter from the exit of a one-way portal
+    PartyTooLarge,
+}
+
+#[derive(Default)]
+pub struct Portals {
+    portals: Vec<Portal>,
+    transits: HashMap<Traveller, Transit>,
+}
@@ -0,0 +1,13 @@
+use super::PortalId;
+
+impl PortalId {
+    pub fn new_unsafe(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl From<PortalId> for usize {
+    fn from(id: PortalId) -> Self {
+        id.0
+    }
+}
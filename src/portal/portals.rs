@@ -0,0 +1,131 @@
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Portal, PortalAccess, PortalEnd, PortalError, PortalId, Portals, Transit};
+use crate::{
+    dynasty::DynastyId,
+    naming::{Culture, NameGenerator},
+    planet::PlanetId,
+    travel::Traveller,
+    universe::{Universe, UniverseId},
+    Time,
+};
+
+// bounds of the randomly generated transit times, in ticks
+const MIN_TRANSIT_TIME: Time = 12;
+const MAX_TRANSIT_TIME: Time = 96;
+const MAX_PARTY_SIZE: u32 = 12;
+
+impl Portals {
+    pub fn create(&mut self, portal: Portal) -> PortalId {
+        self.portals.push(portal);
+        PortalId::new_unsafe(self.portals.len() - 1)
+    }
+
+    /// Opens a portal between random land regions of the 2 universes.
+    pub fn create_random(
+        &mut self,
+        creation_rng: &mut dyn rand_core::RngCore,
+        names: &NameGenerator,
+        (from_id, from): (UniverseId, &Universe),
+        (to_id, to): (UniverseId, &Universe),
+        access: PortalAccess,
+        is_one_way: bool,
+    ) -> PortalId {
+        let mut random_end = |universe_id, universe: &Universe| {
+            let planet_index = creation_rng.gen_range(0..universe.get_planets().len());
+            let region_id = *universe
+                .get_planet_unsafe(planet_index)
+                .get_land_indices()
+                .choose(creation_rng)
+                .expect("cannot randomly choose a land region");
+            PortalEnd {
+                universe_id,
+                planet_id: PlanetId::new_unsafe(planet_index),
+                region_id,
+            }
+        };
+        let ends = [random_end(from_id, from), random_end(to_id, to)];
+
+        let culture = Culture::random(creation_rng);
+        let name = format!("{} Gate", names.place(creation_rng, culture));
+        let transit_time = creation_rng.gen_range(MIN_TRANSIT_TIME..=MAX_TRANSIT_TIME);
+
+        self.create(Portal::new(
+            name,
+            ends,
+            access,
+            is_one_way,
+            MAX_PARTY_SIZE,
+            transit_time,
+        ))
+    }
+
+    pub fn get_portals(&self) -> &[Portal] {
+        &self.portals
+    }
+
+    pub fn get_portal(&self, portal_id: PortalId) -> &Portal {
+        &self.portals[usize::from(portal_id)]
+    }
+
+    /// Portal ends on the planet, with the portal they belong to.
+    pub fn iter_ends_on_planet(
+        &self,
+        universe_id: UniverseId,
+        planet_id: PlanetId,
+    ) -> impl Iterator<Item = (PortalId, PortalEnd)> + '_ {
+        self.portals
+            .iter()
+            .enumerate()
+            .flat_map(|(index, portal)| {
+                portal
+                    .ends
+                    .into_iter()
+                    .map(move |end| (PortalId::new_unsafe(index), end))
+            })
+            .filter(move |(_, end)| end.universe_id == universe_id && end.planet_id == planet_id)
+    }
+
+    pub fn get_transit(&self, traveller: Traveller) -> Option<&Transit> {
+        self.transits.get(&traveller)
+    }
+
+    pub fn traverse(
+        &mut self,
+        portal_id: PortalId,
+        traveller: Traveller,
+        entrance: PortalEnd,
+        dynasty_ids: &[DynastyId],
+        time: Time,
+    ) -> Result<&Transit, PortalError> {
+        if self.transits.contains_key(&traveller) {
+            return Err(PortalError::AlreadyTravelling);
+        }
+
+        let portal = self.get_portal(portal_id);
+        let exit = portal.check_traversal(entrance, dynasty_ids)?;
+        let transit = Transit {
+            traveller,
+            portal_id,
+            exit,
+            arrival: time + portal.transit_time,
+        };
+
+        Ok(self.transits.entry(traveller).or_insert(transit))
+    }
+
+    /// Returns the transits that have ended.
+    pub fn progress(&mut self, time: Time) -> Vec<Transit> {
+        let arrived: Vec<_> = self
+            .transits
+            .values()
+            .filter(|transit| transit.arrival <= time)
+            .map(|transit| transit.traveller)
+            .collect();
+
+        arrived
+            .into_iter()
+            .filter_map(|traveller| self.transits.remove(&traveller))
+            .collect()
+    }
+}
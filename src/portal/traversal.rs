@@ -0,0 +1,148 @@
+use super::{Portal, PortalAccess, PortalEnd, PortalError, PortalId, Transit};
+use crate::{dynasty::DynastyId, travel::Traveller, Time};
+
+impl Portal {
+    pub fn new(
+        name: String,
+        ends: [PortalEnd; 2],
+        access: PortalAccess,
+        is_one_way: bool,
+        max_party_size: u32,
+        transit_time: Time,
+    ) -> Self {
+        Self {
+            name,
+            ends,
+            access,
+            is_one_way,
+            max_party_size,
+            transit_time,
+        }
+    }
+
+    pub fn clone_name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    pub fn get_ends(&self) -> [PortalEnd; 2] {
+        self.ends
+    }
+
+    pub fn get_access(&self) -> PortalAccess {
+        self.access
+    }
+
+    pub fn is_one_way(&self) -> bool {
+        self.is_one_way
+    }
+
+    pub fn get_transit_time(&self) -> Time {
+        self.transit_time
+    }
+
+    /// Where a traveller entering from the given end comes out.
+    pub fn get_exit(&self, entrance: PortalEnd) -> Option<PortalEnd> {
+        match self.ends {
+            [a, b] if a == entrance => Some(b),
+            [a, b] if b == entrance && !self.is_one_way => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Checks that the members, given by their dynasties, can go through together from the entrance.
+    pub fn check_traversal(
+        &self,
+        entrance: PortalEnd,
+        dynasty_ids: &[DynastyId],
+    ) -> Result<PortalEnd, PortalError> {
+        if !self.ends.contains(&entrance) {
+            return Err(PortalError::NotAtPortal);
+        }
+        let exit = self.get_exit(entrance).ok_or(PortalError::OneWay)?;
+
+        match self.access {
+            PortalAccess::Anyone => {}
+            PortalAccess::Dynasty(dynasty_id) => {
+                if dynasty_ids.iter().any(|&id| id != dynasty_id) {
+                    return Err(PortalError::NotInDynasty);
+                }
+            }
+            PortalAccess::Sealed => return Err(PortalError::Sealed),
+        }
+
+        if dynasty_ids.len() as u32 > self.max_party_size {
+            return Err(PortalError::PartyTooLarge);
+        }
+
+        Ok(exit)
+    }
+}
+
+impl Transit {
+    pub fn get_traveller(&self) -> Traveller {
+        self.traveller
+    }
+
+    pub fn get_portal_id(&self) -> PortalId {
+        self.portal_id
+    }
+
+    pub fn get_exit(&self) -> PortalEnd {
+        self.exit
+    }
+
+    pub fn get_arrival(&self) -> Time {
+        self.arrival
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Portal, PortalAccess, PortalEnd, PortalError};
+    use crate::{
+        dynasty::DynastyId,
+        planet::{PlanetId, RegionId},
+        universe::UniverseId,
+    };
+
+    fn end(universe: usize) -> PortalEnd {
+        PortalEnd {
+            universe_id: UniverseId::new_unsafe(universe),
+            planet_id: PlanetId::new_unsafe(0),
+            region_id: RegionId::new_unsafe(0),
+        }
+    }
+
+    #[test]
+    fn traversal_rules() {
+        let (a, b, elsewhere) = (end(0), end(1), end(2));
+        let mine = DynastyId::new_unsafe(0);
+        let theirs = DynastyId::new_unsafe(1);
+
+        let open = Portal::new("".into(), [a, b], PortalAccess::Anyone, false, 2, 10);
+        assert_eq!(open.check_traversal(a, &[mine, theirs]), Ok(b));
+        assert_eq!(open.check_traversal(b, &[mine]), Ok(a));
+        assert_eq!(
+            open.check_traversal(elsewhere, &[mine]),
+            Err(PortalError::NotAtPortal)
+        );
+        assert_eq!(
+            open.check_traversal(a, &[mine, mine, mine]),
+            Err(PortalError::PartyTooLarge)
+        );
+
+        let private = Portal::new("".into(), [a, b], PortalAccess::Dynasty(mine), true, 2, 10);
+        assert_eq!(private.check_traversal(a, &[mine]), Ok(b));
+        assert_eq!(
+            private.check_traversal(a, &[mine, theirs]),
+            Err(PortalError::NotInDynasty)
+        );
+        assert_eq!(
+            private.check_traversal(b, &[mine]),
+            Err(PortalError::OneWay)
+        );
+
+        let sealed = Portal::new("".into(), [a, b], PortalAccess::Sealed, false, 2, 10);
+        assert_eq!(sealed.check_traversal(a, &[mine]), Err(PortalError::Sealed));
+    }
+}
@@ -16,4 +16,56 @@ impl Terrain {
             Terrain::DeepOcean | Terrain::ShallowWater => false,
         }
     }
+
+    /// Portion of the land that can be used for buildings & farms.
+    pub fn get_usable_land_ratio(&self) -> f64 {
+        match self {
+            Terrain::Plain => 1.,
+            Terrain::Hill => 0.6,
+            Terrain::Mountain => 0.25,
+            Terrain::ShallowWater | Terrain::DeepOcean => 0.,
+        }
+    }
+
+    /// How many times longer it takes to walk across compared to plains, or None if it can't be
+    /// crossed on foot.
+    pub fn get_foot_cost(&self) -> Option<f64> {
+        match self {
+            Terrain::Plain => Some(1.),
+            Terrain::Hill => Some(2.),
+            Terrain::Mountain => Some(5.),
+            Terrain::ShallowWater => Some(4.), // wading & fording
+            Terrain::DeepOcean => None,
+        }
+    }
+
+    /// How many times longer it takes to sail across compared to open water, or None if ships
+    /// can't go there.
+    pub fn get_sailing_cost(&self, is_ocean_going: bool) -> Option<f64> {
+        match self {
+            Terrain::ShallowWater => Some(1.),
+            Terrain::DeepOcean if is_ocean_going => Some(1.),
+            _ => None,
+        }
+    }
+
+    /// How well crops grow, compared to plains.
+    pub fn get_fertility(&self) -> f64 {
+        match self {
+            Terrain::Plain => 1.,
+            Terrain::Hill => 0.4,
+            Terrain::Mountain => 0.1,
+            Terrain::ShallowWater | Terrain::DeepOcean => 0.,
+        }
+    }
+
+    /// How well livestock grazes, compared to hills.
+    pub fn get_pasture_quality(&self) -> f64 {
+        match self {
+            Terrain::Hill => 1.,
+            Terrain::Mountain => 0.5,
+            Terrain::Plain => 0.3, // mostly left to crops
+            Terrain::ShallowWater | Terrain::DeepOcean => 0.,
+        }
+    }
 }
@@ -0,0 +1,176 @@
+use super::{travels::get_flight_time, Freight, FreightError, Spaceport, Travels};
+use crate::{
+    economy::{Commodity, Quantity},
+    planet::PlanetId,
+    universe::Universe,
+    Time,
+};
+
+impl Freight {
+    pub fn get_commodity(&self) -> Commodity {
+        self.commodity
+    }
+
+    pub fn get_quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    pub fn get_origin(&self) -> Spaceport {
+        self.origin
+    }
+
+    pub fn get_destination_planet_id(&self) -> PlanetId {
+        self.destination_planet_id
+    }
+
+    pub fn get_arrival(&self) -> Time {
+        self.arrival
+    }
+}
+
+impl Travels {
+    /// Goods that can still be launched or landed at the spaceport this tick.
+    pub fn get_remaining_throughput(&self, universe: &Universe, spaceport: Spaceport) -> Quantity {
+        let specialty = universe.get_planet(spaceport.planet_id).get_regions()
+            [usize::from(spaceport.region_id)]
+        .get_specialty();
+        let lifted = self.lifted.get(&spaceport).copied().unwrap_or(0);
+        specialty.get_freight_throughput().saturating_sub(lifted)
+    }
+
+    /// Uses up to the quantity of the throughput of the spaceport, and returns how much was used.
+    pub fn use_throughput(
+        &mut self,
+        universe: &Universe,
+        spaceport: Spaceport,
+        quantity: Quantity,
+    ) -> Quantity {
+        let used = quantity.min(self.get_remaining_throughput(universe, spaceport));
+        *self.lifted.entry(spaceport).or_insert(0) += used;
+        used
+    }
+
+    /**
+    Launches goods from the spaceport to the orbit of another planet in the same universe, which
+    takes the launch time of the spaceport plus the flight. The goods have to be taken out of the
+    spaceport's storage by the caller.
+    */
+    pub fn send_freight(
+        &mut self,
+        universe: &Universe,
+        origin: Spaceport,
+        destination_planet_id: PlanetId,
+        commodity: Commodity,
+        quantity: Quantity,
+        time: Time,
+    ) -> Result<&Freight, FreightError> {
+        let origin_specialty = universe.get_planet(origin.planet_id).get_regions()
+            [usize::from(origin.region_id)]
+        .get_specialty();
+        if !origin_specialty.is_spaceport() {
+            return Err(FreightError::NotAtSpaceport);
+        }
+        if origin.planet_id == destination_planet_id {
+            return Err(FreightError::SamePlanet);
+        }
+        if quantity > self.get_remaining_throughput(universe, origin) {
+            return Err(FreightError::ThroughputReached);
+        }
+
+        self.use_throughput(universe, origin, quantity);
+        let liftoff = time + origin_specialty.get_launch_time();
+        self.freights.push(Freight {
+            commodity,
+            quantity,
+            origin,
+            destination_planet_id,
+            arrival: liftoff
+                + get_flight_time(universe, origin.planet_id, destination_planet_id, time),
+        });
+        Ok(self.freights.last().expect("just pushed"))
+    }
+
+    /// Returns the freights that have reached the orbit of their destination.
+    pub fn progress_freights(&mut self, time: Time) -> Vec<Freight> {
+        let (arrived, in_flight) = std::mem::take(&mut self.freights)
+            .into_iter()
+            .partition(|freight| freight.arrival <= time);
+        self.freights = in_flight;
+        arrived
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{prelude::StdRng, SeedableRng};
+
+    use crate::{
+        economy::Commodity,
+        naming::NameGenerator,
+        planet::{layout::LayoutKind, PlanetConfig, Wrapping},
+        travel::{FreightError, Spaceport, Travels},
+        universe::Universes,
+    };
+
+    #[test]
+    fn freight_is_limited_by_throughput() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let names = NameGenerator::default();
+        let mut universes = Universes::default();
+        let (universe_id, universe) = universes.create(&mut rng, &names);
+        let config = PlanetConfig {
+            layout: LayoutKind::SquareGrid,
+            wrapping: Wrapping::None,
+            model_width: Some(20.),
+        };
+        let (home_id, _) = universe.create_planet(&mut rng, &config, &names);
+        let (other_id, _) = universe.create_planet(&mut rng, &config, &names);
+        let universe = &universes.get_universes()[usize::from(universe_id)];
+
+        let origin = Spaceport {
+            universe_id,
+            planet_id: home_id,
+            region_id: universe.get_planet(home_id).get_spaceports()[0],
+        };
+        let mut travels = Travels::default();
+        let throughput = travels.get_remaining_throughput(universe, origin);
+        assert!(throughput > 0);
+
+        assert_eq!(
+            travels
+                .send_freight(universe, origin, home_id, Commodity::Metal, 1, 0)
+                .err(),
+            Some(FreightError::SamePlanet)
+        );
+        assert_eq!(
+            travels
+                .send_freight(
+                    universe,
+                    origin,
+                    other_id,
+                    Commodity::Metal,
+                    throughput + 1,
+                    0
+                )
+                .err(),
+            Some(FreightError::ThroughputReached)
+        );
+        let arrival = travels
+            .send_freight(universe, origin, other_id, Commodity::Metal, throughput, 0)
+            .unwrap()
+            .get_arrival();
+        assert_eq!(travels.get_remaining_throughput(universe, origin), 0);
+
+        // the spaceport is free again the next tick
+        assert!(travels.progress(1).is_empty());
+        assert_eq!(
+            travels.get_remaining_throughput(universe, origin),
+            throughput
+        );
+        assert!(travels.progress_freights(arrival - 1).is_empty());
+        let arrived = travels.progress_freights(arrival);
+        assert_eq!(arrived.len(), 1);
+        assert_eq!(arrived[0].get_destination_planet_id(), other_id);
+        assert_eq!(arrived[0].get_quantity(), throughput);
+    }
+}
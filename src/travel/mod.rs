@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use crate::{
+    character::CharacterId,
+    economy::{Commodity, Quantity},
+    planet::{PlanetId, RegionId},
+    unit::PartyId,
+    universe::UniverseId,
+    Time,
+};
+
+mod freight;
+mod travels;
+mod voyage;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Traveller {
+    Character(CharacterId),
+    Party(PartyId), // all members of the party move together
+}
+
+/// A region with a rocket pad or a space elevator.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Spaceport {
+    pub universe_id: UniverseId,
+    pub planet_id: PlanetId,
+    pub region_id: RegionId,
+}
+
+/**
+A trip between 2 planets. The traveller spends the first ticks launching from the origin and the
+last ones landing at the destination; in between, they fly in a straight line through space.
+*/
+#[derive(Clone)]
+pub struct Voyage {
+    traveller: Traveller,
+    origin: Spaceport,
+    destination: Spaceport,
+    liftoff: Time,   // end of launch
+    touchdown: Time, // start of landing
+    arrival: Time,
+}
+
+/// Goods launched from a spaceport to the orbital station of another planet.
+#[derive(Clone, Debug)]
+pub struct Freight {
+    commodity: Commodity,
+    quantity: Quantity,
+    origin: Spaceport,
+    destination_planet_id: PlanetId,
+    arrival: Time, // in orbit of the destination, waiting to land
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FreightError {
+    NotAtSpaceport,
+    SamePlanet,
+    ThroughputReached, // the spaceport has lifted as much as it can this tick
+    NotEnoughGoods,
+    NotEnoughFuel,
+    InsufficientFunds, // to buy the fuel
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TravelError {
+    UnknownTraveller,
+    AlreadyTravelling,
+    NotAtSpaceport,
+    SamePlanet,
+    NoSpaceport,           // the destination has nowhere to land
+    LaunchCapacityReached, // the spaceport has launched as many passengers as it can this tick
+}
+
+#[derive(Default)]
+pub struct Travels {
+    voyages: HashMap<Traveller, Voyage>,
+    launched: HashMap<Spaceport, u32>, // passengers launched from each spaceport this tick
+    freights: Vec<Freight>,
+    lifted: HashMap<Spaceport, Quantity>, // goods launched or landed at each spaceport this tick
+}
@@ -0,0 +1,210 @@
+use super::{Spaceport, TravelError, Traveller, Travels, Voyage};
+use crate::{planet::PlanetId, universe::Universe, Time};
+
+// distance travelled in space per tick, in galaxy units
+const SPACE_SPEED: f64 = 0.02;
+
+/// Ticks it takes to fly between 2 planets, by their distance at departure.
+pub(super) fn get_flight_time(
+    universe: &Universe,
+    from: PlanetId,
+    to: PlanetId,
+    departure: Time,
+) -> Time {
+    let galaxy = universe.get_galaxy();
+    let distance = nalgebra::distance(
+        &galaxy.get_planet_coor(from, departure),
+        &galaxy.get_planet_coor(to, departure),
+    );
+    ((distance / SPACE_SPEED).ceil() as Time).max(1)
+}
+
+impl Travels {
+    pub fn get_voyage(&self, traveller: Traveller) -> Option<&Voyage> {
+        self.voyages.get(&traveller)
+    }
+
+    pub fn iter_voyages(&self) -> impl Iterator<Item = &Voyage> {
+        self.voyages.values()
+    }
+
+    /// Passengers that can still leave from the spaceport this tick.
+    pub fn get_remaining_capacity(&self, universe: &Universe, spaceport: Spaceport) -> u32 {
+        let specialty = universe.get_planet(spaceport.planet_id).get_regions()
+            [usize::from(spaceport.region_id)]
+        .get_specialty();
+        let launched = self.launched.get(&spaceport).copied().unwrap_or(0);
+        specialty.get_launch_capacity().saturating_sub(launched)
+    }
+
+    /**
+    Sends the traveller from the spaceport to the first spaceport of the destination planet in the
+    same universe. Travel time is the launch & landing times of both spaceports plus the flight,
+    which is proportional to the distance between the planets at departure.
+    */
+    pub fn launch(
+        &mut self,
+        universe: &Universe,
+        traveller: Traveller,
+        passengers: u32,
+        origin: Spaceport,
+        destination_planet_id: PlanetId,
+        time: Time,
+    ) -> Result<&Voyage, TravelError> {
+        if self.voyages.contains_key(&traveller) {
+            return Err(TravelError::AlreadyTravelling);
+        }
+
+        let origin_specialty = universe.get_planet(origin.planet_id).get_regions()
+            [usize::from(origin.region_id)]
+        .get_specialty();
+        if !origin_specialty.is_spaceport() {
+            return Err(TravelError::NotAtSpaceport);
+        }
+
+        if origin.planet_id == destination_planet_id {
+            return Err(TravelError::SamePlanet);
+        }
+
+        let destination_planet = universe.get_planet(destination_planet_id);
+        let destination_region_id = *destination_planet
+            .get_spaceports()
+            .first()
+            .ok_or(TravelError::NoSpaceport)?;
+        let destination_specialty =
+            destination_planet.get_regions()[usize::from(destination_region_id)].get_specialty();
+
+        if passengers > self.get_remaining_capacity(universe, origin) {
+            return Err(TravelError::LaunchCapacityReached);
+        }
+
+        let flight_time = get_flight_time(universe, origin.planet_id, destination_planet_id, time);
+        let liftoff = time + origin_specialty.get_launch_time();
+        let touchdown = liftoff + flight_time;
+        let voyage = Voyage {
+            traveller,
+            origin,
+            destination: Spaceport {
+                universe_id: origin.universe_id,
+                planet_id: destination_planet_id,
+                region_id: destination_region_id,
+            },
+            liftoff,
+            touchdown,
+            arrival: touchdown + destination_specialty.get_launch_time(),
+        };
+
+        *self.launched.entry(origin).or_insert(0) += passengers;
+        Ok(self.voyages.entry(traveller).or_insert(voyage))
+    }
+
+    /// Frees up the launch capacity for the new tick, and returns the voyages that have ended.
+    pub fn progress(&mut self, time: Time) -> Vec<Voyage> {
+        self.launched.clear();
+        self.lifted.clear();
+
+        let arrived: Vec<_> = self
+            .voyages
+            .iter()
+            .filter(|(_, voyage)| voyage.is_arrived(time))
+            .map(|(&traveller, _)| traveller)
+            .collect();
+
+        arrived
+            .into_iter()
+            .filter_map(|traveller| self.voyages.remove(&traveller))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{prelude::StdRng, SeedableRng};
+
+    use super::{Spaceport, TravelError, Traveller, Travels};
+    use crate::{
+        character::CharacterId,
+        naming::NameGenerator,
+        planet::{layout::LayoutKind, PlanetConfig, Wrapping},
+        universe::Universes,
+        CompleteCoor,
+    };
+
+    #[test]
+    fn voyage_between_planets() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let names = NameGenerator::default();
+        let mut universes = Universes::default();
+        let (universe_id, universe) = universes.create(&mut rng, &names);
+        let config = PlanetConfig {
+            layout: LayoutKind::SquareGrid,
+            wrapping: Wrapping::None,
+            model_width: Some(20.),
+        };
+        let (home_id, _) = universe.create_planet(&mut rng, &config, &names);
+        let (other_id, _) = universe.create_planet(&mut rng, &config, &names);
+        let universe = &universes.get_universes()[usize::from(universe_id)];
+
+        let home = universe.get_planet(home_id);
+        let origin = Spaceport {
+            universe_id,
+            planet_id: home_id,
+            region_id: home.get_spaceports()[0],
+        };
+        let traveller = Traveller::Character(CharacterId::new_unsafe(0));
+        let mut travels = Travels::default();
+
+        assert_eq!(
+            travels
+                .launch(universe, traveller, 1, origin, home_id, 0)
+                .err(),
+            Some(TravelError::SamePlanet)
+        );
+        let spaceports = home.get_spaceports();
+        let not_a_port = Spaceport {
+            region_id: *home
+                .get_land_indices()
+                .iter()
+                .find(|region_id| !spaceports.contains(region_id))
+                .expect("a planet should have land without a spaceport"),
+            ..origin
+        };
+        assert_eq!(
+            travels
+                .launch(universe, traveller, 1, not_a_port, other_id, 0)
+                .err(),
+            Some(TravelError::NotAtSpaceport)
+        );
+        let capacity = travels.get_remaining_capacity(universe, origin);
+        assert_eq!(
+            travels
+                .launch(universe, traveller, capacity + 1, origin, other_id, 0)
+                .err(),
+            Some(TravelError::LaunchCapacityReached)
+        );
+
+        let voyage = travels
+            .launch(universe, traveller, 1, origin, other_id, 0)
+            .unwrap()
+            .clone();
+        assert_eq!(
+            travels.get_remaining_capacity(universe, origin),
+            capacity - 1
+        );
+
+        let mid_flight = (voyage.liftoff + voyage.touchdown) / 2;
+        assert!(matches!(
+            voyage.get_coor(universe, mid_flight),
+            CompleteCoor::InSpace(..)
+        ));
+
+        assert!(travels.progress(voyage.get_arrival() - 1).is_empty());
+        assert_eq!(travels.get_remaining_capacity(universe, origin), capacity);
+        assert_eq!(travels.progress(voyage.get_arrival()).len(), 1);
+        assert!(matches!(
+            voyage.get_coor(universe, voyage.get_arrival()),
+            CompleteCoor::OnPlanetRegion(_, planet_id, _) if planet_id == other_id
+        ));
+        assert!(travels.get_voyage(traveller).is_none());
+    }
+}
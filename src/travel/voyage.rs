@@ -0,0 +1,49 @@
+use super::{Spaceport, Traveller, Voyage};
+use crate::{universe::Universe, CompleteCoor, Time};
+
+impl Voyage {
+    pub fn get_traveller(&self) -> Traveller {
+        self.traveller
+    }
+
+    pub fn get_origin(&self) -> Spaceport {
+        self.origin
+    }
+
+    pub fn get_arrival(&self) -> Time {
+        self.arrival
+    }
+
+    pub fn is_arrived(&self, time: Time) -> bool {
+        time >= self.arrival
+    }
+
+    /// Where the traveller is at the given time. The universe is the one both spaceports are in.
+    pub fn get_coor(&self, universe: &Universe, time: Time) -> CompleteCoor {
+        if time < self.liftoff {
+            let Spaceport {
+                universe_id,
+                planet_id,
+                region_id,
+            } = self.origin;
+            return CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id);
+        }
+
+        if time >= self.touchdown {
+            let Spaceport {
+                universe_id,
+                planet_id,
+                region_id,
+            } = self.destination;
+            return CompleteCoor::OnPlanetRegion(universe_id, planet_id, region_id);
+        }
+
+        // planets keep orbiting, so aim for where the destination will be
+        let galaxy = universe.get_galaxy();
+        let from = galaxy.get_planet_coor(self.origin.planet_id, self.liftoff);
+        let to = galaxy.get_planet_coor(self.destination.planet_id, self.touchdown);
+        let progress = (time - self.liftoff) as f64 / (self.touchdown - self.liftoff) as f64;
+
+        CompleteCoor::InSpace(self.origin.universe_id, from + (to - from) * progress)
+    }
+}
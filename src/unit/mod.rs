@@ -2,6 +2,8 @@ use std::collections::HashSet;
 
 use crate::{character::CharacterId, entity::Tec};
 
+mod party;
+
 enum AttackMove {
     Slash,
 }
@@ -15,8 +17,8 @@ pub struct Unit {
     piercing: u32,
 }
 
-#[derive(Default)]
 pub struct Party {
+    leader: CharacterId, // where the party is, is where the leader is
     characters: HashSet<CharacterId>,
 }
 
@@ -24,3 +26,14 @@ pub struct Party {
 pub struct Parties {
     free: Tec<Party>,
 }
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PartyId(u32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartyError {
+    NoMembers,
+    AlreadyInParty,
+    AlreadyTravelling,
+    NotTogether, // the members have to stand in the same region
+}
@@ -0,0 +1,36 @@
+use std::collections::HashSet;
+
+use super::{Parties, Party, PartyId};
+use crate::character::CharacterId;
+
+impl Party {
+    pub fn get_leader(&self) -> CharacterId {
+        self.leader
+    }
+
+    /// Everyone in the party, the leader included.
+    pub fn get_characters(&self) -> &HashSet<CharacterId> {
+        &self.characters
+    }
+}
+
+impl Parties {
+    /// Puts the leader & the others together in a new party.
+    pub fn create(&mut self, leader: CharacterId, others: &[CharacterId]) -> PartyId {
+        let mut characters: HashSet<_> = others.iter().copied().collect();
+        characters.insert(leader);
+        PartyId(self.free.alloc(Party { leader, characters }))
+    }
+
+    pub fn get(&self, party_id: PartyId) -> Option<&Party> {
+        self.free.get(party_id.0)
+    }
+
+    /// The party the character is in, if any.
+    pub fn find_party(&self, character_id: CharacterId) -> Option<PartyId> {
+        self.free
+            .iter()
+            .find(|(_, party)| party.characters.contains(&character_id))
+            .map(|(index, _)| PartyId(index))
+    }
+}
@@ -0,0 +1,202 @@
+use ordered_float::OrderedFloat;
+use poisson_diskus::bridson_rng;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    prelude::SliceRandom,
+    Rng,
+};
+use voronoice::{BoundingBox, VoronoiBuilder};
+
+use super::{Galaxy, StarKind, StarSystem, StarSystemId};
+use crate::{
+    naming::{Culture, NameGenerator},
+    planet::PlanetId,
+    Coor, Time,
+};
+
+const GALAXY_WIDTH: f64 = 10.;
+const SYSTEM_MIN_DISTANCE: f64 = 0.8;
+
+// relative chance for each kind of star to show up
+const STAR_KINDS: [(StarKind, u32); 6] = [
+    (StarKind::RedDwarf, 50),
+    (StarKind::YellowDwarf, 25),
+    (StarKind::WhiteDwarf, 10),
+    (StarKind::BlueGiant, 8),
+    (StarKind::NeutronStar, 5),
+    (StarKind::BlackHole, 2),
+];
+
+impl Galaxy {
+    /**
+    Scatters star systems with poisson-disk sampling. Lanes connect the systems whose voronoi
+    cells are adjacent, i.e. the edges of the delaunay triangulation, so the lane graph is
+    connected and lanes don't cross each other.
+    */
+    pub fn new(mut creation_rng: &mut dyn rand_core::RngCore, names: &NameGenerator) -> Self {
+        let width = GALAXY_WIDTH;
+
+        let sites: Vec<_> = bridson_rng(
+            &[width, width],
+            SYSTEM_MIN_DISTANCE,
+            30,
+            false,
+            &mut creation_rng,
+        )
+        .expect("The galaxy sites weren't generated")
+        .into_iter()
+        .map(|[x, y]| voronoice::Point { x, y })
+        .collect();
+
+        let graph = VoronoiBuilder::default()
+            .set_sites(sites)
+            .set_bounding_box(BoundingBox::new(
+                voronoice::Point {
+                    x: width / 2.,
+                    y: width / 2.,
+                },
+                width,
+                width,
+            ))
+            .set_lloyd_relaxation_iterations(2)
+            .build()
+            .unwrap();
+
+        let star_distribution = WeightedIndex::new(STAR_KINDS.iter().map(|(_, weight)| weight))
+            .expect("star weights should be valid");
+
+        let systems = graph
+            .iter_cells()
+            .map(|cell| {
+                let site = cell.site_position();
+                let star = STAR_KINDS[star_distribution.sample(&mut creation_rng)].0;
+                let culture = Culture::random(creation_rng);
+                let name = names.planet(creation_rng, culture);
+
+                let mut system = StarSystem::new(name, Coor::new(site.x, site.y), star);
+                system.lanes = cell
+                    .iter_neighbors()
+                    .map(StarSystemId::new_unsafe)
+                    .collect();
+                system
+            })
+            .collect();
+
+        Self {
+            width,
+            systems,
+            planet_systems: Default::default(),
+        }
+    }
+
+    pub fn get_width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn get_systems(&self) -> &[StarSystem] {
+        &self.systems
+    }
+
+    pub fn get_system(&self, system_id: StarSystemId) -> &StarSystem {
+        &self.systems[usize::from(system_id)]
+    }
+
+    /// Each lane once, as (lower id, higher id).
+    pub fn iter_lanes(&self) -> impl Iterator<Item = (StarSystemId, StarSystemId)> + '_ {
+        self.systems.iter().enumerate().flat_map(|(index, system)| {
+            system
+                .lanes
+                .iter()
+                .filter(move |&&other| usize::from(other) > index)
+                .map(move |&other| (StarSystemId::new_unsafe(index), other))
+        })
+    }
+
+    pub fn find_system_id(&self, coor: Coor) -> StarSystemId {
+        let index = self
+            .systems
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, system)| OrderedFloat(nalgebra::distance_squared(&system.coor, &coor)))
+            .expect("should have at least 1 star system")
+            .0;
+        StarSystemId::new_unsafe(index)
+    }
+
+    /// Puts a new planet in orbit of a random star that can support life.
+    pub fn assign_planet(
+        &mut self,
+        creation_rng: &mut dyn rand_core::RngCore,
+        planet_id: PlanetId,
+    ) -> StarSystemId {
+        assert_eq!(
+            usize::from(planet_id),
+            self.planet_systems.len(),
+            "planets should be assigned in the order they're created"
+        );
+
+        let habitable: Vec<_> = self
+            .systems
+            .iter()
+            .enumerate()
+            .filter(|(_, system)| system.star.is_habitable())
+            .map(|(index, _)| StarSystemId::new_unsafe(index))
+            .collect();
+
+        let system_id = *habitable
+            .choose(creation_rng)
+            .expect("should have at least 1 habitable star system");
+
+        let phase = creation_rng.gen_range(0.0..std::f64::consts::TAU);
+        self.systems[usize::from(system_id)].add_orbit(planet_id, phase);
+        self.planet_systems.push(system_id);
+
+        system_id
+    }
+
+    pub fn get_planet_system_id(&self, planet_id: PlanetId) -> StarSystemId {
+        self.planet_systems[usize::from(planet_id)]
+    }
+
+    /// Where the planet is in the galaxy at the given time.
+    pub fn get_planet_coor(&self, planet_id: PlanetId, time: Time) -> Coor {
+        let system = self.get_system(self.get_planet_system_id(planet_id));
+        let orbit = system
+            .orbits
+            .iter()
+            .find(|orbit| orbit.planet_id == planet_id)
+            .expect("planet should be in the orbit of its system");
+        system.coor + orbit.get_offset(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{prelude::StdRng, SeedableRng};
+
+    use super::Galaxy;
+    use crate::{naming::NameGenerator, planet::PlanetId};
+
+    #[test]
+    fn lanes_and_orbits() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut galaxy = Galaxy::new(&mut rng, &NameGenerator::default());
+
+        galaxy.iter_lanes().for_each(|(a, b)| {
+            assert!(galaxy.get_system(b).get_lanes().contains(&a));
+        });
+
+        let planet_id = PlanetId::new_unsafe(0);
+        let system_id = galaxy.assign_planet(&mut rng, planet_id);
+        let system = galaxy.get_system(system_id);
+        assert!(system.get_star().is_habitable());
+
+        // the planet goes around its star at a constant distance
+        let orbit = &system.get_orbits()[0];
+        let half_period = (orbit.get_period() / 2.) as u64;
+        let distance =
+            |time| nalgebra::distance(&galaxy.get_planet_coor(planet_id, time), &system.get_coor());
+        assert!((distance(0) - distance(half_period)).abs() < 1e-9);
+        assert_eq!(galaxy.find_system_id(system.get_coor()), system_id);
+    }
+}
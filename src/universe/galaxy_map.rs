@@ -0,0 +1,106 @@
+use yew::prelude::*;
+
+use super::{StarKind, UniverseId};
+use crate::{
+    app::{Action, ViewModelContext},
+    planet::PlanetId,
+};
+
+// pixels per unit of galaxy coordinates
+const GALAXY_SCALE: f64 = 60.;
+
+#[derive(PartialEq, Properties)]
+pub struct GalaxyMapProps {
+    pub universe_id: UniverseId,
+}
+
+#[function_component(GalaxyMap)]
+pub fn create_galaxy_map(props: &GalaxyMapProps) -> Html {
+    let view_model = use_context::<ViewModelContext>().expect("no view model context found");
+    let game = view_model.game.borrow();
+    let universe_id = props.universe_id;
+    let universe = game.get_universe(universe_id);
+    let galaxy = universe.get_galaxy();
+    let time = game.get_time();
+
+    let to_vp = |value: f64| (value * GALAXY_SCALE).to_string();
+    let vp_size = to_vp(galaxy.get_width());
+
+    let lanes = galaxy.iter_lanes().map(|(a, b)| {
+        let a = galaxy.get_system(a).get_coor();
+        let b = galaxy.get_system(b).get_coor();
+        html! {
+            <line x1={to_vp(a.x)} y1={to_vp(a.y)} x2={to_vp(b.x)} y2={to_vp(b.y)} stroke="#444" />
+        }
+    });
+
+    let systems = galaxy.get_systems().iter().map(|system| {
+        let coor = system.get_coor();
+        let (color, radius) = star_style(system.get_star());
+        html! {
+            <circle cx={to_vp(coor.x)} cy={to_vp(coor.y)} r={radius} fill={color}>
+                <title>{system.clone_name()}</title>
+            </circle>
+        }
+    });
+
+    let planets = universe
+        .get_planets()
+        .iter()
+        .enumerate()
+        .map(|(index, planet)| {
+            let planet_id = PlanetId::new_unsafe(index);
+            let coor = galaxy.get_planet_coor(planet_id, time);
+            let onclick = {
+                let view_model = view_model.clone();
+                move |_| view_model.dispatch(Action::UpdatePlanetId(planet_id))
+            };
+            html! {
+                <circle cx={to_vp(coor.x)} cy={to_vp(coor.y)} r="3" fill="#3c3" style="cursor: pointer" {onclick}>
+                    <title>{planet.clone_name()}</title>
+                </circle>
+            }
+        });
+
+    // a ring around each planet with a portal; clicking it goes to the universe on the other side
+    let portals = game.get_portals().get_portals().iter().flat_map(|portal| {
+        let [a, b] = portal.get_ends();
+        [(a, b), (b, a)]
+            .into_iter()
+            .filter(|(here, _)| here.universe_id == universe_id)
+            .map(|(here, there)| {
+                let coor = galaxy.get_planet_coor(here.planet_id, time);
+                let there_name = game.get_universe(there.universe_id).clone_name();
+                let onclick = {
+                    let view_model = view_model.clone();
+                    move |_| view_model.dispatch(Action::UpdateUniverseId(there.universe_id))
+                };
+                html! {
+                    <circle cx={to_vp(coor.x)} cy={to_vp(coor.y)} r="7" fill="none" stroke="#c6f" stroke-width="2" style="cursor: pointer" {onclick}>
+                        <title>{format!("{} to {}", portal.clone_name(), there_name)}</title>
+                    </circle>
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    html! {
+        <svg width={vp_size.clone()} height={vp_size.clone()} style="background: black">
+            {for lanes}
+            {for systems}
+            {for portals}
+            {for planets}
+        </svg>
+    }
+}
+
+fn star_style(star: StarKind) -> (&'static str, &'static str) {
+    match star {
+        StarKind::RedDwarf => ("#f64", "3"),
+        StarKind::YellowDwarf => ("#fd4", "4"),
+        StarKind::BlueGiant => ("#6af", "6"),
+        StarKind::WhiteDwarf => ("#eee", "2"),
+        StarKind::NeutronStar => ("#c9f", "2"),
+        StarKind::BlackHole => ("#606", "5"),
+    }
+}
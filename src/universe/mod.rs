@@ -1,7 +1,17 @@
+use std::collections::HashSet;
+
 use serde::Serialize;
 
-use crate::{entity::Sequence, planet::Planet, GalaxyEntityId};
+use crate::{
+    entity::Sequence,
+    planet::{Planet, PlanetId},
+    Coor, GalaxyEntityId,
+};
 
+mod galaxy;
+pub mod galaxy_map;
+mod star_system;
+mod star_system_id;
 mod universe;
 mod universe_id;
 
@@ -9,13 +19,50 @@ mod universe_id;
 pub struct Universe {
     name: String,
     planets: Vec<Planet>,
+    galaxy: Galaxy,
     entity_idgen: Sequence<GalaxyEntityId>,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq, Hash)]
 pub struct UniverseId(usize);
 
 #[derive(Default)]
 pub struct Universes {
     data: Vec<Universe>,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StarKind {
+    RedDwarf,
+    YellowDwarf,
+    BlueGiant,
+    WhiteDwarf,
+    NeutronStar,
+    BlackHole,
+}
+
+/// A planet going around its star. Orbits are circular.
+pub struct Orbit {
+    planet_id: PlanetId,
+    radius: f64, // distance from the star, in astronomical units
+    phase: f64,  // angle (in radians) at time 0
+}
+
+pub struct StarSystem {
+    name: String,
+    coor: Coor, // position in the galaxy
+    star: StarKind,
+    orbits: Vec<Orbit>,
+    lanes: HashSet<StarSystemId>, // directly reachable systems
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq, Hash)]
+pub struct StarSystemId(usize);
+
+/// 2D map of the star systems in a universe; space coordinates are in this map.
+#[derive(Default)]
+pub struct Galaxy {
+    width: f64,
+    systems: Vec<StarSystem>,
+    planet_systems: Vec<StarSystemId>, // indexed by planet id
+}
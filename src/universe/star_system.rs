@@ -0,0 +1,89 @@
+use std::{collections::HashSet, f64::consts::TAU};
+
+use nalgebra::Vector2;
+
+use super::{Orbit, StarKind, StarSystem, StarSystemId};
+use crate::{planet::PlanetId, Coor, Time};
+
+// ticks for a planet at 1 AU to go around its star
+const ORBIT_BASE_PERIOD: f64 = 2000.;
+
+// size of 1 AU in galaxy coordinates; systems are small compared to the distances between them
+const ORBIT_SCALE: f64 = 0.1;
+
+impl StarKind {
+    pub fn is_habitable(&self) -> bool {
+        match self {
+            StarKind::RedDwarf | StarKind::YellowDwarf | StarKind::BlueGiant => true,
+            StarKind::WhiteDwarf | StarKind::NeutronStar | StarKind::BlackHole => false,
+        }
+    }
+}
+
+impl Orbit {
+    pub fn get_planet_id(&self) -> PlanetId {
+        self.planet_id
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Ticks per revolution, by Kepler's third law.
+    pub fn get_period(&self) -> f64 {
+        ORBIT_BASE_PERIOD * self.radius.powf(1.5)
+    }
+
+    /// Position of the planet relative to its star, in galaxy coordinates.
+    pub fn get_offset(&self, time: Time) -> Vector2<f64> {
+        let angle = self.phase + TAU * (time as f64 / self.get_period());
+        Vector2::new(angle.cos(), angle.sin()) * self.radius * ORBIT_SCALE
+    }
+}
+
+impl StarSystem {
+    pub fn new(name: String, coor: Coor, star: StarKind) -> Self {
+        Self {
+            name,
+            coor,
+            star,
+            orbits: Default::default(),
+            lanes: HashSet::new(),
+        }
+    }
+
+    pub fn clone_name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    pub fn get_coor(&self) -> Coor {
+        self.coor
+    }
+
+    pub fn get_star(&self) -> StarKind {
+        self.star
+    }
+
+    pub fn get_orbits(&self) -> &[Orbit] {
+        &self.orbits
+    }
+
+    pub fn get_lanes(&self) -> &HashSet<StarSystemId> {
+        &self.lanes
+    }
+
+    /// Puts a planet on the next free orbit, further away than the existing ones.
+    pub fn add_orbit(&mut self, planet_id: PlanetId, phase: f64) -> &Orbit {
+        let radius = self
+            .orbits
+            .last()
+            .map_or(1., |outermost| outermost.radius * 1.6);
+
+        self.orbits.push(Orbit {
+            planet_id,
+            radius,
+            phase,
+        });
+        self.orbits.last().expect("just pushed an orbit")
+    }
+}
@@ -0,0 +1,13 @@
+use super::StarSystemId;
+
+impl StarSystemId {
+    pub fn new_unsafe(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl From<StarSystemId> for usize {
+    fn from(id: StarSystemId) -> Self {
+        id.0
+    }
+}
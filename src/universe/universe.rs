@@ -1,9 +1,10 @@
 use std::borrow::Cow;
 
-use super::{Universe, UniverseId, Universes};
+use super::{Galaxy, Universe, UniverseId, Universes};
 use crate::{
     entity::Sequence,
-    planet::{Planet, PlanetId},
+    naming::{Culture, NameGenerator},
+    planet::{Planet, PlanetConfig, PlanetId},
 };
 
 impl Universe {
@@ -15,13 +16,17 @@ impl Universe {
         self.name.to_owned()
     }
 
-    fn create(creation_rng: &mut dyn rand_core::RngCore) -> Self {
+    fn create(creation_rng: &mut dyn rand_core::RngCore, names: &NameGenerator) -> Self {
         let entity_idgen = Sequence::default();
 
-        let name = format!("{} {}", "Main", creation_rng.next_u32());
+        let culture = Culture::random(creation_rng);
+        let name = format!("{} Cluster", names.planet(creation_rng, culture));
+        let galaxy = Galaxy::new(creation_rng, names);
+
         Self {
             name,
             planets: Default::default(),
+            galaxy,
             entity_idgen,
         }
     }
@@ -29,11 +34,15 @@ impl Universe {
     pub fn create_planet(
         &mut self,
         creation_rng: &mut dyn rand_core::RngCore,
+        config: &PlanetConfig,
+        names: &NameGenerator,
     ) -> (PlanetId, &mut Planet) {
         let mut planet_global_idgen = Sequence::default();
-        let planet = Planet::new(creation_rng, planet_global_idgen.next());
+        let planet = Planet::new(creation_rng, planet_global_idgen.next(), config, names);
         let planet_id = self.planets.len();
         self.planets.push(planet);
+        self.galaxy
+            .assign_planet(creation_rng, PlanetId::new_unsafe(planet_id));
         (
             PlanetId::new_unsafe(planet_id),
             &mut self.planets[planet_id],
@@ -52,14 +61,23 @@ impl Universe {
     pub fn get_planets(&self) -> &[Planet] {
         &self.planets
     }
+
+    pub fn get_planets_mut(&mut self) -> &mut [Planet] {
+        &mut self.planets
+    }
+
+    pub fn get_galaxy(&self) -> &Galaxy {
+        &self.galaxy
+    }
 }
 
 impl Universes {
     pub fn create(
         &mut self,
         creation_rng: &mut dyn rand_core::RngCore,
+        names: &NameGenerator,
     ) -> (UniverseId, &mut Universe) {
-        let universe = Universe::create(creation_rng);
+        let universe = Universe::create(creation_rng, names);
         let data = &mut self.data;
         let index = data.len();
         data.push(universe);
@@ -69,4 +87,8 @@ impl Universes {
     pub fn get_universes(&self) -> &[Universe] {
         &self.data
     }
+
+    pub fn get_universes_mut(&mut self) -> &mut [Universe] {
+        &mut self.data
+    }
 }